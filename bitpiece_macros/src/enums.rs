@@ -3,9 +3,11 @@ use quote::{format_ident, quote, ToTokens};
 use syn::{DataEnum, DeriveInput, Fields};
 
 use crate::{
+    fuzzing_impls::{gen_arbitrary_enum_impl, gen_proptest_enum_impl},
     newtypes::{BitLenExpr, StorageTypeExpr, TypeExpr},
+    serde_impls::gen_serde_enum_impl,
     utils::{bitpiece_gen_impl, not_supported_err, BitPieceGenImplParams},
-    MacroArgs,
+    MacroArgs, OptIn,
 };
 
 fn enum_variant_values<'a>(
@@ -96,6 +98,104 @@ fn gen_try_from_bits_code(
     }
 }
 
+/// generates an implementation of the `BitPieceHasLayout` trait, which lists the variants of the enum.
+fn gen_layout_impl(enum_ident: &syn::Ident, data_enum: &DataEnum) -> proc_macro2::TokenStream {
+    let type_name = enum_ident.to_string();
+    let variants = data_enum.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let name = ident.to_string();
+        quote! {
+            ::bitpiece::VariantInfo {
+                name: #name,
+                value: #enum_ident::#ident as u64,
+            }
+        }
+    });
+    quote! {
+        #[automatically_derived]
+        impl ::bitpiece::BitPieceHasLayout for #enum_ident {
+            const TYPE_NAME: &'static str = #type_name;
+            const VARIANTS: &'static [::bitpiece::VariantInfo] = &[#(#variants),*];
+        }
+    }
+}
+
+/// generates an implementation of the `BitPieceVisit` trait, which passes the variant name to the visitor.
+fn gen_visit_impl(enum_ident: &syn::Ident, data_enum: &DataEnum) -> proc_macro2::TokenStream {
+    let arms = data_enum.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let name = ident.to_string();
+        quote! {
+            #enum_ident::#ident => #name,
+        }
+    });
+    quote! {
+        #[automatically_derived]
+        impl ::bitpiece::BitPieceVisit for #enum_ident {
+            fn visit_as(&self, name: &str, visitor: &mut impl ::bitpiece::BitPieceVisitor) {
+                let variant_name = match self {
+                    #(#arms)*
+                };
+                visitor.visit_enum(name, variant_name, *self as u64)
+            }
+        }
+    }
+}
+
+/// generates the `variant_name` function and the `ALL_VARIANTS` and `VARIANT_COUNT` constants.
+fn gen_variants_code(enum_ident: &syn::Ident, data_enum: &DataEnum) -> proc_macro2::TokenStream {
+    let num_variants = data_enum.variants.len();
+    let variant_values = enum_variant_values(enum_ident, data_enum);
+    let arms = data_enum.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let name = ident.to_string();
+        quote! {
+            #enum_ident::#ident => #name,
+        }
+    });
+    quote! {
+        impl #enum_ident {
+            /// all variants of this enum, in declaration order.
+            pub const ALL_VARIANTS: [Self; #num_variants] = [#(#variant_values),*];
+
+            /// the amount of variants of this enum.
+            pub const VARIANT_COUNT: usize = #num_variants;
+
+            /// returns the name of this variant.
+            pub const fn variant_name(self) -> &'static str {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    }
+}
+
+/// generates an implementation of the `Display` trait, which formats the name of the variant.
+fn gen_display_impl(enum_ident: &syn::Ident) -> proc_macro2::TokenStream {
+    quote! {
+        #[automatically_derived]
+        impl ::core::fmt::Display for #enum_ident {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.pad(self.variant_name())
+            }
+        }
+    }
+}
+
+/// generates an implementation of the `FromStr` trait, which parses either the name of a variant or its value.
+fn gen_from_str_impl(enum_ident: &syn::Ident) -> proc_macro2::TokenStream {
+    quote! {
+        #[automatically_derived]
+        impl ::core::str::FromStr for #enum_ident {
+            type Err = ::bitpiece::ParseEnumError;
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                ::bitpiece::parse_enum(s, &Self::ALL_VARIANTS, Self::variant_name)
+            }
+        }
+    }
+}
+
 pub fn bitpiece_enum(
     input: &DeriveInput,
     data_enum: &DataEnum,
@@ -109,6 +209,40 @@ pub fn bitpiece_enum(
         return not_supported_err("enum variants with data");
     }
 
+    if macro_args.opt_ins.contains(&OptIn::Debug) {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "the `debug` opt in is only supported on structs, use `#[derive(Debug)]` for enums",
+        )
+        .to_compile_error()
+        .into();
+    }
+    if macro_args.opt_ins.contains(&OptIn::Flags) {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "the `flags` opt in is only supported on structs",
+        )
+        .to_compile_error()
+        .into();
+    }
+    if macro_args.opt_ins.contains(&OptIn::Bitops) {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "the `bitops` opt in is only supported on structs",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    if let Some(validate_arg) = &macro_args.validate {
+        return syn::Error::new_spanned(
+            &validate_arg.ident,
+            "the `validate` argument is only supported on structs",
+        )
+        .to_compile_error()
+        .into();
+    }
+
     let ident = &input.ident;
     let num_variants = data_enum.variants.len();
 
@@ -160,15 +294,39 @@ pub fn bitpiece_enum(
         try_from_bits_code: gen_try_from_bits_code(ident, data_enum, &storage_type),
         fields_type: &TypeExpr(quote! { Self }),
         to_fields_code: quote! { self },
-        from_fields_code: quote! { fields },
+        try_from_fields_code: quote! { Some(fields) },
+        call_setters_code: None,
         storage_type: &storage_type,
         bit_len: &bit_len,
+        field_lens: quote! { [#bit_len] },
         zeroes: min_variant.clone(),
         ones: max_variant.clone(),
         min: min_variant,
         max: max_variant,
     });
 
+    let opt_layout_code =
+        macro_args.filter_opt_in_code(OptIn::Layout, gen_layout_impl(ident, data_enum));
+
+    let opt_visit_code =
+        macro_args.filter_opt_in_code(OptIn::Visit, gen_visit_impl(ident, data_enum));
+
+    let opt_serde_code =
+        macro_args.filter_opt_in_code(OptIn::Serde, gen_serde_enum_impl(ident, data_enum));
+
+    let opt_arbitrary_code =
+        macro_args.filter_opt_in_code(OptIn::Arbitrary, gen_arbitrary_enum_impl(ident));
+
+    let opt_proptest_code =
+        macro_args.filter_opt_in_code(OptIn::Proptest, gen_proptest_enum_impl(ident));
+
+    let opt_variants_code =
+        macro_args.filter_opt_in_code(OptIn::Variants, gen_variants_code(ident, data_enum));
+
+    let opt_display_code = macro_args.filter_opt_in_code(OptIn::Display, gen_display_impl(ident));
+
+    let opt_from_str_code = macro_args.filter_opt_in_code(OptIn::FromStr, gen_from_str_impl(ident));
+
     let vis = &input.vis;
 
     quote! {
@@ -180,6 +338,14 @@ pub fn bitpiece_enum(
         #input
 
         #implementation
+        #opt_variants_code
+        #opt_layout_code
+        #opt_visit_code
+        #opt_serde_code
+        #opt_arbitrary_code
+        #opt_proptest_code
+        #opt_display_code
+        #opt_from_str_code
     }
     .into()
 }
@@ -0,0 +1,247 @@
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token::Comma,
+    Field, FieldsNamed,
+};
+
+use crate::newtypes::BitLenExpr;
+
+/// a custom converter of the value of a field, which is used instead of the `BitPiece` implementation of its type.
+///
+/// the module must provide `const fn encode(value: T) -> Bits` and `const fn decode(bits: Bits) -> T`, where `T` is the
+/// type of the field and `Bits` is the smallest storage type which can hold the bit length of the field. it may also
+/// provide `const fn try_decode(bits: Bits) -> Option<T>`, in which case bit patterns for which it returns `None` are
+/// rejected. otherwise, all bit patterns are considered valid.
+pub struct FieldCodec {
+    /// the module which provides the conversion functions.
+    pub module: syn::Path,
+
+    /// the bit length of the field.
+    pub bits: syn::Expr,
+
+    /// the function which checks whether raw bits of the field are valid, if not all of them are.
+    pub validate: Option<syn::Path>,
+}
+impl FieldCodec {
+    /// returns the bit length of the field.
+    pub fn bit_len(&self) -> BitLenExpr {
+        let bits = &self.bits;
+        BitLenExpr(quote! { (#bits) })
+    }
+}
+
+/// a native integer field which is stored in fewer bits than its type, for example `#[bits(3)] x: u8`.
+pub struct NarrowedInt {
+    /// the bit length of the field.
+    pub bits: syn::Expr,
+
+    /// whether the type of the field is a signed integer type.
+    pub is_signed: bool,
+}
+impl NarrowedInt {
+    /// returns the bit length of the field.
+    pub fn bit_len(&self) -> BitLenExpr {
+        let bits = &self.bits;
+        BitLenExpr(quote! { (#bits) })
+    }
+
+    /// returns the narrowed integer representation of a field of the given type with the given bit length, or `None` if
+    /// the type is not a native integer type.
+    fn new(ty: &syn::Type, bits: syn::Expr) -> Option<Self> {
+        let syn::Type::Path(ty) = ty else {
+            return None;
+        };
+        if ty.qself.is_some() {
+            return None;
+        }
+        let ident = ty.path.get_ident()?.to_string();
+        let is_signed = match ident.as_str() {
+            "u8" | "u16" | "u32" | "u64" => false,
+            "i8" | "i16" | "i32" | "i64" => true,
+            _ => return None,
+        };
+        Some(Self { bits, is_signed })
+    }
+}
+
+/// the way in which the value of a field is stored in the bits of the struct.
+pub enum FieldRepr {
+    /// the field is stored using the `BitPiece` implementation of its type.
+    BitPiece,
+
+    /// the field is stored using a custom converter module.
+    Codec(FieldCodec),
+
+    /// the field is a native integer which is stored in fewer bits than its type.
+    NarrowedInt(NarrowedInt),
+}
+impl FieldRepr {
+    /// returns whether the value of the field is a bitpiece.
+    pub fn is_bitpiece(&self) -> bool {
+        matches!(self, FieldRepr::BitPiece)
+    }
+
+    /// returns the bit length of the field, unless it is stored using the `BitPiece` implementation of its type.
+    pub fn explicit_bit_len(&self) -> Option<BitLenExpr> {
+        match self {
+            FieldRepr::BitPiece => None,
+            FieldRepr::Codec(codec) => Some(codec.bit_len()),
+            FieldRepr::NarrowedInt(narrowed_int) => Some(narrowed_int.bit_len()),
+        }
+    }
+}
+
+/// a single argument of a `#[bitpiece(...)]` attribute on a field.
+enum FieldArg {
+    With(syn::Ident, syn::Path),
+    Bits(syn::Ident, syn::Expr),
+    Validate(syn::Ident, syn::Path),
+}
+impl Parse for FieldArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: syn::Ident = input.parse()?;
+        input.parse::<syn::Token![=]>()?;
+        if ident == "with" {
+            Ok(FieldArg::With(ident, input.parse()?))
+        } else if ident == "bits" {
+            Ok(FieldArg::Bits(ident, input.parse()?))
+        } else if ident == "validate" {
+            Ok(FieldArg::Validate(ident, input.parse()?))
+        } else {
+            Err(syn::Error::new_spanned(
+                ident,
+                "unknown field argument, expected a converter module (`with = path`), a bit length (`bits = 4`) or a validation function (`validate = path`)",
+            ))
+        }
+    }
+}
+
+/// the arguments passed to a field using `#[bitpiece(...)]` and `#[bits(...)]` attributes.
+pub struct FieldArgs {
+    /// the way in which the value of the field is stored.
+    pub repr: FieldRepr,
+}
+impl FieldArgs {
+    /// parses the arguments of the given field.
+    pub fn parse(field: &Field) -> syn::Result<Self> {
+        let mut with: Option<(syn::Ident, syn::Path)> = None;
+        let mut bits: Option<(syn::Ident, syn::Expr)> = None;
+        let mut validate: Option<(syn::Ident, syn::Path)> = None;
+        let mut narrowed_bits: Option<(&syn::Attribute, syn::Expr)> = None;
+        for attr in field.attrs.iter().filter(|attr| is_field_bits_attr(attr)) {
+            if let Some((existing_attr, _)) = narrowed_bits {
+                let mut err = syn::Error::new_spanned(attr, "duplicate `bits` attribute");
+                err.combine(syn::Error::new_spanned(
+                    existing_attr,
+                    "conflicts with this previous `bits` attribute",
+                ));
+                return Err(err);
+            }
+            narrowed_bits = Some((attr, attr.parse_args()?));
+        }
+        for attr in field.attrs.iter().filter(|attr| is_field_args_attr(attr)) {
+            let args = attr.parse_args_with(Punctuated::<FieldArg, Comma>::parse_terminated)?;
+            for arg in args {
+                let (ident, existing_ident) = match arg {
+                    FieldArg::With(ident, path) => {
+                        let existing = with.replace((ident.clone(), path));
+                        (ident, existing.map(|(ident, _)| ident))
+                    }
+                    FieldArg::Bits(ident, expr) => {
+                        let existing = bits.replace((ident.clone(), expr));
+                        (ident, existing.map(|(ident, _)| ident))
+                    }
+                    FieldArg::Validate(ident, path) => {
+                        let existing = validate.replace((ident.clone(), path));
+                        (ident, existing.map(|(ident, _)| ident))
+                    }
+                };
+                if let Some(existing_ident) = existing_ident {
+                    let mut err = syn::Error::new_spanned(&ident, "duplicate field argument");
+                    err.combine(syn::Error::new_spanned(
+                        existing_ident,
+                        "conflicts with this previous field argument",
+                    ));
+                    return Err(err);
+                }
+            }
+        }
+        if let Some((attr, narrowed_bits)) = narrowed_bits {
+            let with_ident = with.as_ref().map(|(ident, _)| ident);
+            let bits_ident = bits.as_ref().map(|(ident, _)| ident);
+            let validate_ident = validate.as_ref().map(|(ident, _)| ident);
+            if let Some(ident) = with_ident.or(bits_ident).or(validate_ident) {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "the `bits` attribute can't be combined with the arguments of a `#[bitpiece(...)]` field attribute",
+                ));
+            }
+            let narrowed_int = NarrowedInt::new(&field.ty, narrowed_bits).ok_or_else(|| {
+                syn::Error::new_spanned(
+                    attr,
+                    "the `bits` attribute is only supported on fields of native integer types (`u8` to `u64` and `i8` to `i64`)",
+                )
+            })?;
+            return Ok(FieldArgs {
+                repr: FieldRepr::NarrowedInt(narrowed_int),
+            });
+        }
+        if let (None, Some((ident, _))) = (&with, &validate) {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "a validation function can only be specified along with a converter module (`with = path`)",
+            ));
+        }
+        match (with, bits) {
+            (Some((_, module)), Some((_, bits))) => Ok(FieldArgs {
+                repr: FieldRepr::Codec(FieldCodec {
+                    module,
+                    bits,
+                    validate: validate.map(|(_, path)| path),
+                }),
+            }),
+            (Some((ident, _)), None) => Err(syn::Error::new_spanned(
+                ident,
+                "a field with a converter module must also specify its bit length (`bits = 4`)",
+            )),
+            (None, Some((ident, _))) => Err(syn::Error::new_spanned(
+                ident,
+                "the bit length of a field can only be specified along with a converter module (`with = path`)",
+            )),
+            (None, None) => Ok(FieldArgs {
+                repr: FieldRepr::BitPiece,
+            }),
+        }
+    }
+}
+
+/// returns the representation of a field whose arguments were already validated using [`FieldArgs::parse`].
+pub fn field_repr(field: &Field) -> FieldRepr {
+    FieldArgs::parse(field)
+        .expect("field arguments are validated before generating code")
+        .repr
+}
+
+/// returns whether the given attribute of a field holds arguments for the macro.
+fn is_field_args_attr(attr: &syn::Attribute) -> bool {
+    attr.path().is_ident("bitpiece")
+}
+
+/// returns whether the given attribute of a field specifies the bit length of a native integer field.
+fn is_field_bits_attr(attr: &syn::Attribute) -> bool {
+    attr.path().is_ident("bits")
+}
+
+/// returns a copy of the given fields without the attributes which hold arguments for the macro, since they are not
+/// valid outside of the macro's input.
+pub fn strip_field_args_attrs(fields: &FieldsNamed) -> FieldsNamed {
+    let mut fields = fields.clone();
+    for field in &mut fields.named {
+        field
+            .attrs
+            .retain(|attr| !is_field_args_attr(attr) && !is_field_bits_attr(attr));
+    }
+    fields
+}
@@ -0,0 +1,132 @@
+use quote::{format_ident, quote};
+
+/// generates a hidden mirror struct of the fields of a named struct, which holds the generated value of each field.
+fn gen_repr_struct<'a>(fields: impl Iterator<Item = &'a syn::Field>) -> proc_macro2::TokenStream {
+    let repr_fields = fields.map(|field| {
+        let ident = &field.ident;
+        let ty = &field.ty;
+        quote! { #ident: #ty, }
+    });
+    quote! {
+        struct Repr {
+            #(#repr_fields)*
+        }
+    }
+}
+
+/// generates an implementation of the `arbitrary::Arbitrary` trait for a named struct, which generates an arbitrary
+/// value for each field and then constructs the struct from them. field values which break the invariants of the
+/// struct are reported as an incorrect format.
+///
+/// `field_values` holds an expression for each field which generates its value from the `u: Unstructured`.
+pub fn gen_arbitrary_struct_impl<'a>(
+    type_ident: &syn::Ident,
+    fields: impl Iterator<Item = &'a syn::Field> + Clone,
+    field_values: impl Iterator<Item = proc_macro2::TokenStream>,
+    from_repr_code: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let repr_struct = gen_repr_struct(fields.clone());
+    let repr_initializers = fields.zip(field_values).map(|(field, value)| {
+        let ident = &field.ident;
+        quote! { #ident: #value, }
+    });
+    quote! {
+        const _: () = {
+            #repr_struct
+
+            #[automatically_derived]
+            impl<'a> ::bitpiece::arbitrary::Arbitrary<'a> for #type_ident {
+                fn arbitrary(u: &mut ::bitpiece::arbitrary::Unstructured<'a>) -> ::bitpiece::arbitrary::Result<Self> {
+                    let repr = Repr {
+                        #(#repr_initializers)*
+                    };
+                    (#from_repr_code).ok_or(::bitpiece::arbitrary::Error::IncorrectFormat)
+                }
+            }
+        };
+    }
+}
+
+/// generates an implementation of the `arbitrary::Arbitrary` trait for an enum, which chooses one of its variants.
+pub fn gen_arbitrary_enum_impl(type_ident: &syn::Ident) -> proc_macro2::TokenStream {
+    quote! {
+        #[automatically_derived]
+        impl<'a> ::bitpiece::arbitrary::Arbitrary<'a> for #type_ident {
+            fn arbitrary(u: &mut ::bitpiece::arbitrary::Unstructured<'a>) -> ::bitpiece::arbitrary::Result<Self> {
+                Ok(*u.choose(&Self::ALL_VARIANTS)?)
+            }
+        }
+    }
+}
+
+/// generates an implementation of the `proptest::arbitrary::Arbitrary` trait for a named struct, which combines the
+/// strategies of all fields and then constructs the struct from the generated field values. field values which break
+/// the invariants of the struct are rejected.
+///
+/// `field_strategies` holds an expression for each field which evaluates to the strategy of its values.
+pub fn gen_proptest_struct_impl<'a>(
+    type_ident: &syn::Ident,
+    fields: impl Iterator<Item = &'a syn::Field> + Clone,
+    field_strategies: impl Iterator<Item = proc_macro2::TokenStream>,
+    from_repr_code: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let repr_struct = gen_repr_struct(fields.clone());
+    let field_idents = fields.clone().map(|field| &field.ident);
+
+    // proptest only implements `Strategy` for tuples of up to 12 strategies, so nest the strategies in pairs, for
+    // example `(a, (b, c))`, to support any amount of fields.
+    let fields_and_strategies: Vec<_> = fields.zip(field_strategies).collect();
+    let mut fields_rev = fields_and_strategies.into_iter().rev();
+    let (last_field, last_strategy) = fields_rev
+        .next()
+        .expect("structs without fields are not supported");
+    let last_value_ident = format_ident!("value_{}", last_field.ident.as_ref().unwrap());
+    let mut strategy = last_strategy;
+    let mut pattern = quote! { #last_value_ident };
+    for (field, field_strategy) in fields_rev {
+        let value_ident = format_ident!("value_{}", field.ident.as_ref().unwrap());
+        strategy = quote! { (#field_strategy, #strategy) };
+        pattern = quote! { (#value_ident, #pattern) };
+    }
+    let value_idents = field_idents
+        .clone()
+        .map(|ident| format_ident!("value_{}", ident.as_ref().unwrap()));
+    let reject_reason = format!("invalid value of `{}`", type_ident);
+    quote! {
+        const _: () = {
+            #repr_struct
+
+            #[automatically_derived]
+            impl ::bitpiece::proptest::arbitrary::Arbitrary for #type_ident {
+                type Parameters = ();
+                type Strategy = ::bitpiece::proptest::strategy::BoxedStrategy<Self>;
+                fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+                    ::bitpiece::proptest::strategy::Strategy::boxed(
+                        ::bitpiece::proptest::strategy::Strategy::prop_filter_map(#strategy, #reject_reason, |#pattern| {
+                            let repr = Repr {
+                                #(#field_idents: #value_idents,)*
+                            };
+                            #from_repr_code
+                        })
+                    )
+                }
+            }
+        };
+    }
+}
+
+/// generates an implementation of the `proptest::arbitrary::Arbitrary` trait for an enum, which selects one of its
+/// variants.
+pub fn gen_proptest_enum_impl(type_ident: &syn::Ident) -> proc_macro2::TokenStream {
+    quote! {
+        #[automatically_derived]
+        impl ::bitpiece::proptest::arbitrary::Arbitrary for #type_ident {
+            type Parameters = ();
+            type Strategy = ::bitpiece::proptest::strategy::BoxedStrategy<Self>;
+            fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+                let variants: &'static [Self] = &Self::ALL_VARIANTS;
+                ::bitpiece::proptest::strategy::Strategy::boxed(::bitpiece::proptest::sample::select(variants))
+            }
+        }
+    }
+}
@@ -1,6 +1,9 @@
 mod enums;
+mod field_args;
+mod fuzzing_impls;
 mod named_structs;
 mod newtypes;
+mod serde_impls;
 mod utils;
 
 use std::{collections::HashSet, str::FromStr};
@@ -43,6 +46,44 @@ enum OptIn {
     MutStructFieldSet,
     MutStructFieldGetNoshift,
     MutStructFieldMut,
+    Serde,
+    SerdeStorage,
+    Debug,
+    Fmt,
+    Layout,
+    Visit,
+    Bitops,
+    Flags,
+    Variants,
+    Display,
+    FromStr,
+    Arbitrary,
+    Proptest,
+}
+impl OptIn {
+    /// whether this opt in is part of the `all` preset.
+    ///
+    /// opt ins which implement traits of optional dependencies are excluded from it, so that using the `all` preset
+    /// does not require enabling any features of `bitpiece`. opt ins which implement standard traits are also excluded,
+    /// since they would conflict with derives or manual implementations of those traits, and so are opt ins which
+    /// require the types of all fields to implement additional traits or which restrict the types of the fields.
+    fn is_in_all_preset(&self) -> bool {
+        !matches!(
+            self,
+            OptIn::Serde
+                | OptIn::SerdeStorage
+                | OptIn::Arbitrary
+                | OptIn::Proptest
+                | OptIn::Debug
+                | OptIn::Fmt
+                | OptIn::Layout
+                | OptIn::Visit
+                | OptIn::Bitops
+                | OptIn::Flags
+                | OptIn::Display
+                | OptIn::FromStr
+        )
+    }
 }
 
 #[derive(EnumString, VariantNames, Hash, Clone, Copy, Debug, PartialEq, Eq)]
@@ -52,11 +93,15 @@ enum OptInPreset {
     MutStructAll,
 }
 impl OptInPreset {
-    fn opt_ins(&self) -> &'static [OptIn] {
+    fn opt_ins(&self) -> Vec<OptIn> {
         match self {
-            OptInPreset::Basic => &[OptIn::Get, OptIn::Set, OptIn::With],
-            OptInPreset::All => OptIn::ALL_VALUES.as_slice(),
-            OptInPreset::MutStructAll => &[
+            OptInPreset::Basic => vec![OptIn::Get, OptIn::Set, OptIn::With],
+            OptInPreset::All => OptIn::ALL_VALUES
+                .iter()
+                .copied()
+                .filter(OptIn::is_in_all_preset)
+                .collect(),
+            OptInPreset::MutStructAll => vec![
                 OptIn::MutStruct,
                 OptIn::MutStructFieldGet,
                 OptIn::MutStructFieldSet,
@@ -81,10 +126,16 @@ struct OptInPresetArg {
     ident: syn::Ident,
 }
 
+struct ValidateArg {
+    path: syn::Path,
+    ident: syn::Ident,
+}
+
 enum MacroArg {
     ExplicitBitLength(ExplicitBitLengthArg),
     OptIn(OptInArg),
     OptInPreset(OptInPresetArg),
+    Validate(ValidateArg),
 }
 impl Parse for MacroArg {
     fn parse(input: ParseStream) -> syn::Result<Self> {
@@ -97,7 +148,7 @@ impl Parse for MacroArg {
             .map(|v| format!("`{}`", v.to_snake_case()))
             .join(", ");
         let unknown_macro_arg_err = format!(
-            "unknown macro argument, expected an integer bit-length (e.g. `32`), an opt-in flag ({opt_in_names}), or an opt-in preset ({preset_names})"
+            "unknown macro argument, expected an integer bit-length (e.g. `32`), an opt-in flag ({opt_in_names}), an opt-in preset ({preset_names}), or a validation function (`validate = path`)"
         );
 
         // explicit bit length
@@ -113,6 +164,18 @@ impl Parse for MacroArg {
         if input.peek(syn::Ident) {
             let ident: syn::Ident = input.parse()?;
 
+            // named arguments
+            if input.peek(syn::Token![=]) {
+                if ident != "validate" {
+                    return Err(syn::Error::new_spanned(&ident, unknown_macro_arg_err));
+                }
+                input.parse::<syn::Token![=]>()?;
+                return Ok(MacroArg::Validate(ValidateArg {
+                    path: input.parse()?,
+                    ident,
+                }));
+            }
+
             let ident_pascal_case = ident.to_string().to_upper_camel_case();
 
             if let Ok(opt_in) = OptIn::from_str(&ident_pascal_case) {
@@ -144,11 +207,7 @@ impl OptInArgsCollector {
         Self(Vec::new())
     }
     fn add_opt_in(&mut self, arg: OptInArg) -> Result<(), syn::Error> {
-        if let Some(existing_arg) = self
-            .0
-            .iter()
-            .find(|existing_arg| existing_arg.opt_in == arg.opt_in)
-        {
+        if let Some(existing_arg) = self.find(arg.opt_in) {
             let mut err = syn::Error::new_spanned(arg.ident, "duplicate opt in arg");
             err.combine(syn::Error::new_spanned(
                 existing_arg.ident.clone(),
@@ -156,7 +215,11 @@ impl OptInArgsCollector {
             ));
             return Err(err);
         }
-        Ok(self.0.push(arg))
+        self.0.push(arg);
+        Ok(())
+    }
+    fn find(&self, opt_in: OptIn) -> Option<&OptInArg> {
+        self.0.iter().find(|arg| arg.opt_in == opt_in)
     }
 }
 
@@ -164,6 +227,9 @@ impl OptInArgsCollector {
 struct MacroArgs {
     explicit_bit_length: Option<usize>,
     opt_ins: HashSet<OptIn>,
+
+    /// a const function which checks the invariants of the type, which is called on each value before accepting it.
+    validate: Option<ValidateArg>,
 }
 impl MacroArgs {
     pub fn filter_opt_in_code(
@@ -184,6 +250,7 @@ impl Parse for MacroArgs {
 
         let mut explicit_bit_length_arg: Option<ExplicitBitLengthArg> = None;
         let mut opt_in_args = OptInArgsCollector::new();
+        let mut validate_arg: Option<ValidateArg> = None;
         for arg in raw_args.0 {
             match arg {
                 MacroArg::ExplicitBitLength(arg) => {
@@ -203,24 +270,70 @@ impl Parse for MacroArgs {
                 MacroArg::OptIn(arg) => {
                     opt_in_args.add_opt_in(arg)?;
                 }
+                MacroArg::Validate(arg) => {
+                    if let Some(existing_arg) = validate_arg {
+                        let mut err = syn::Error::new_spanned(
+                            arg.ident,
+                            "found more than one `validate` argument but only one is allowed",
+                        );
+                        err.combine(syn::Error::new_spanned(
+                            existing_arg.ident,
+                            "conflicts with this previous `validate` argument",
+                        ));
+                        return Err(err);
+                    }
+                    validate_arg = Some(arg);
+                }
                 MacroArg::OptInPreset(opt_in_preset_arg) => {
                     for opt_in in opt_in_preset_arg.opt_in_preset.opt_ins() {
                         opt_in_args.add_opt_in(OptInArg {
-                            opt_in: *opt_in,
+                            opt_in,
                             ident: opt_in_preset_arg.ident.clone(),
                         })?;
                     }
                 }
             }
         }
+        if let (Some(serde_arg), Some(serde_storage_arg)) = (
+            opt_in_args.find(OptIn::Serde),
+            opt_in_args.find(OptIn::SerdeStorage),
+        ) {
+            let mut err = syn::Error::new_spanned(
+                serde_storage_arg.ident.clone(),
+                "the `serde` and `serde_storage` opt ins can't be used together",
+            );
+            err.combine(syn::Error::new_spanned(
+                serde_arg.ident.clone(),
+                "conflicts with this opt in arg",
+            ));
+            return Err(err);
+        }
+        let mut opt_ins: HashSet<OptIn> = if opt_in_args.0.is_empty() {
+            // if no opt ins are specified, use the basic preset
+            OptInPreset::Basic.opt_ins().into_iter().collect()
+        } else {
+            opt_in_args.0.iter().map(|arg| arg.opt_in).collect()
+        };
+        if opt_ins.contains(&OptIn::Flags) {
+            // flag sets are combined using the bitwise operators
+            opt_ins.insert(OptIn::Bitops);
+        }
+        if [
+            OptIn::Display,
+            OptIn::FromStr,
+            OptIn::Arbitrary,
+            OptIn::Proptest,
+        ]
+        .iter()
+        .any(|opt_in| opt_ins.contains(opt_in))
+        {
+            // these are implemented using the variants of enums
+            opt_ins.insert(OptIn::Variants);
+        }
         Ok(MacroArgs {
             explicit_bit_length: explicit_bit_length_arg.map(|arg| arg.bit_length),
-            opt_ins: if opt_in_args.0.is_empty() {
-                // if no opt ins are specified, use the basic preset
-                OptInPreset::Basic.opt_ins().iter().copied().collect()
-            } else {
-                opt_in_args.0.iter().map(|arg| arg.opt_in).collect()
-            },
+            opt_ins,
+            validate: validate_arg,
         })
     }
 }
@@ -3,7 +3,10 @@ use quote::{format_ident, quote, ToTokens};
 use syn::{DeriveInput, Field, FieldsNamed};
 
 use crate::{
+    field_args::{field_repr, strip_field_args_attrs, FieldArgs, FieldCodec, FieldRepr},
+    fuzzing_impls::{gen_arbitrary_struct_impl, gen_proptest_struct_impl},
     newtypes::{BitLenExpr, BitOffsetExpr, StorageTypeExpr, TypeExpr},
+    serde_impls::gen_serde_struct_impl,
     utils::{
         bitpiece_gen_impl, gen_explicit_bit_length_assertion, not_supported_err,
         BitPieceGenImplParams,
@@ -19,6 +22,22 @@ pub fn bitpiece_named_struct(
     if fields.named.is_empty() {
         return not_supported_err("empty structs");
     }
+    for (opt_in, name) in [(OptIn::Display, "display"), (OptIn::FromStr, "from_str")] {
+        if macro_args.opt_ins.contains(&opt_in) {
+            return syn::Error::new(
+                proc_macro2::Span::call_site(),
+                format!("the `{name}` opt in is only supported on enums"),
+            )
+            .to_compile_error()
+            .into();
+        }
+    }
+
+    for field in &fields.named {
+        if let Err(err) = FieldArgs::parse(field) {
+            return err.to_compile_error().into();
+        }
+    }
 
     let ident = &input.ident;
 
@@ -56,15 +75,28 @@ pub fn bitpiece_named_struct(
         bit_len: &bit_len,
         storage_type: &storage_type,
         to_bits_code: quote! { self.storage },
-        try_from_bits_code: gen_try_from_bits_code(ident, fields, &storage_type),
+        try_from_bits_code: gen_try_from_bits_code(ident, fields, &storage_type, &macro_args),
         mut_type_ident: &mut_type_ident,
         fields_type: &fields_type,
+        field_lens: gen_field_lens_array(ident, fields),
         zeroes: gen_const_instantiation(ident, fields, &storage_type, "ZEROES"),
         ones: gen_const_instantiation(ident, fields, &storage_type, "ONES"),
         min: gen_const_instantiation(ident, fields, &storage_type, "MIN"),
         max: gen_const_instantiation(ident, fields, &storage_type, "MAX"),
-        to_fields_code: gen_to_fields(fields, &fields_struct_ident),
-        from_fields_code: gen_from_fields(fields, input, &storage_type),
+        to_fields_code: gen_to_fields(ident, fields, &storage_type, &fields_struct_ident),
+        try_from_fields_code: gen_try_from_field_values(
+            ident,
+            fields,
+            &storage_type,
+            quote! { fields },
+        ),
+        call_setters_code: gen_call_setters_code(
+            ident,
+            fields,
+            &storage_type,
+            &mut_type_ident,
+            &macro_args,
+        ),
     });
 
     let field_access_fns = macro_args.filter_opt_in_code(
@@ -79,8 +111,21 @@ pub fn bitpiece_named_struct(
         OptIn::With,
         gen_field_with_fns(ident, fields, &storage_type),
     );
+    let field_checked_with_fns = if macro_args.validate.is_some() {
+        macro_args.filter_opt_in_code(OptIn::With, gen_field_checked_with_fns(fields))
+    } else {
+        quote! {}
+    };
+    let field_try_with_fns = macro_args.filter_opt_in_code(
+        OptIn::With,
+        gen_field_try_with_fns(ident, fields, &storage_type),
+    );
     let field_set_fns =
         macro_args.filter_opt_in_code(OptIn::Set, gen_field_set_fns(ident, fields, &storage_type));
+    let field_try_set_fns = macro_args.filter_opt_in_code(
+        OptIn::Set,
+        gen_field_try_set_fns(ident, fields, &storage_type),
+    );
     let field_mut_fns = macro_args.filter_opt_in_code(
         OptIn::GetMut,
         gen_field_mut_fns(ident, fields, &storage_type),
@@ -125,7 +170,10 @@ pub fn bitpiece_named_struct(
             #field_access_fns
             #field_access_noshift_fns
             #field_with_fns
+            #field_checked_with_fns
+            #field_try_with_fns
             #field_set_fns
+            #field_try_set_fns
             #field_mut_fns
         }
     };
@@ -142,18 +190,34 @@ pub fn bitpiece_named_struct(
         },
     );
 
-    let opt_fields_struct_code = macro_args.filter_opt_in_code(
-        OptIn::FieldsStruct,
+    let fields_struct_fields = strip_field_args_attrs(fields);
+    // the validation function may reject the fields, so the conversion is fallible instead of panicking.
+    let from_fields_struct_impl = if macro_args.validate.is_some() {
+        quote! {
+            impl ::core::convert::TryFrom<#fields_struct_ident> for #ident {
+                type Error = ::bitpiece::InvalidFieldsError;
+                fn try_from(fields: #fields_struct_ident) -> Result<Self, Self::Error> {
+                    Self::try_from_fields(fields).ok_or(::bitpiece::InvalidFieldsError)
+                }
+            }
+        }
+    } else {
         quote! {
-            #(#attrs)*
-            #[derive(Clone, Copy)]
-            #vis struct #fields_struct_ident #fields
-
             impl ::core::convert::From<#fields_struct_ident> for #ident {
                 fn from(fields: #fields_struct_ident) -> Self {
                     Self::from_fields(fields)
                 }
             }
+        }
+    };
+    let opt_fields_struct_code = macro_args.filter_opt_in_code(
+        OptIn::FieldsStruct,
+        quote! {
+            #(#attrs)*
+            #[derive(Clone, Copy)]
+            #vis struct #fields_struct_ident #fields_struct_fields
+
+            #from_fields_struct_impl
             impl ::core::convert::From<#ident> for #fields_struct_ident {
                 fn from(value: #ident) -> Self {
                     value.to_fields()
@@ -162,10 +226,85 @@ pub fn bitpiece_named_struct(
         },
     );
 
+    let opt_serde_code = macro_args.filter_opt_in_code(
+        OptIn::Serde,
+        gen_serde_struct_impl(
+            ident,
+            fields.named.iter(),
+            fields_values(ident, fields, &storage_type, quote! { self.storage }),
+            gen_try_from_field_values(ident, fields, &storage_type, quote! { repr }),
+        ),
+    );
+
+    let opt_arbitrary_code = macro_args.filter_opt_in_code(
+        OptIn::Arbitrary,
+        gen_arbitrary_struct_impl(
+            ident,
+            fields.named.iter(),
+            fields
+                .named
+                .iter()
+                .map(|field| gen_field_arbitrary_value(ident, field)),
+            gen_try_from_field_values(ident, fields, &storage_type, quote! { repr }),
+        ),
+    );
+
+    let opt_proptest_code = macro_args.filter_opt_in_code(
+        OptIn::Proptest,
+        gen_proptest_struct_impl(
+            ident,
+            fields.named.iter(),
+            fields
+                .named
+                .iter()
+                .map(|field| gen_field_proptest_strategy(ident, field)),
+            gen_try_from_field_values(ident, fields, &storage_type, quote! { repr }),
+        ),
+    );
+
+    let opt_layout_diagram_code =
+        macro_args.filter_opt_in_code(OptIn::Fmt, gen_layout_diagram_fn(ident, fields));
+
+    let opt_layout_code =
+        macro_args.filter_opt_in_code(OptIn::Layout, gen_layout_impl(ident, fields));
+
+    let opt_visit_code =
+        macro_args.filter_opt_in_code(OptIn::Visit, gen_visit_impl(ident, fields, &storage_type));
+
+    let opt_bitops_code = if macro_args.opt_ins.contains(&OptIn::Bitops) {
+        match check_bitops_fields(fields, &macro_args) {
+            Ok(()) => gen_bitops_impl(ident, &bit_len, &storage_type),
+            Err(err) => return err.to_compile_error().into(),
+        }
+    } else {
+        quote! {}
+    };
+
+    let opt_flags_code = if macro_args.opt_ins.contains(&OptIn::Flags) {
+        match gen_flags_impl(ident, fields, &storage_type) {
+            Ok(code) => code,
+            Err(err) => return err.to_compile_error().into(),
+        }
+    } else {
+        quote! {}
+    };
+
+    let opt_debug_code =
+        macro_args.filter_opt_in_code(OptIn::Debug, gen_debug_impl(ident, fields, &storage_type));
+
     quote! {
         #base_code
         #opt_mut_struct_code
         #opt_fields_struct_code
+        #opt_layout_code
+        #opt_visit_code
+        #opt_bitops_code
+        #opt_flags_code
+        #opt_serde_code
+        #opt_arbitrary_code
+        #opt_proptest_code
+        #opt_debug_code
+        #opt_layout_diagram_code
     }
     .into()
 }
@@ -178,22 +317,58 @@ fn gen_const_instantiation(
 ) -> proc_macro2::TokenStream {
     let const_name_ident = syn::Ident::new(const_name, proc_macro2::Span::mixed_site());
     let bitwise_or_each_field = fields.named.iter().map(|f| {
-        let field_ty = &f.ty;
         let offset = get_field_offset(type_ident, f);
+        let len = get_field_len(type_ident, f);
+        let ones = quote! { ::bitpiece::extract_bits(u64::MAX, 0, #len) };
+        let bits = match field_repr(f) {
+            FieldRepr::BitPiece => {
+                let field_ty = &f.ty;
+                quote! {
+                    <#field_ty as ::bitpiece::BitPiece>::Converter::to_bits(
+                        <#field_ty as ::bitpiece::BitPiece>::#const_name_ident
+                    )
+                }
+            }
+            // there is no meaningful ordering of the values of a field with a custom converter, so the min and max
+            // constants use the lowest and highest valid bits, respectively, which are the zero and one bits unless
+            // the converter rejects them.
+            FieldRepr::Codec(_) => {
+                let is_valid_bits = field_is_valid_bits(f, quote! { bits });
+                let (start, end, step) = match const_name {
+                    "ZEROES" | "MIN" => (quote! { 0 }, ones, quote! { bits + 1 }),
+                    _ => (ones, quote! { 0 }, quote! { bits - 1 }),
+                };
+                quote! {
+                    {
+                        let mut bits: u64 = #start;
+                        while bits != #end && !#is_valid_bits {
+                            bits = #step;
+                        }
+                        bits
+                    }
+                }
+            }
+            FieldRepr::NarrowedInt(narrowed_int) => match (const_name, narrowed_int.is_signed) {
+                ("ZEROES", _) | ("MIN", false) => quote! { 0 },
+                ("MIN", true) => quote! { (1u64 << (#len - 1)) },
+                ("MAX", true) => quote! { (#ones >> 1) },
+                _ => ones,
+            },
+        };
         quote! {
             | (
                 (
-                    <#field_ty as ::bitpiece::BitPiece>::Converter::to_bits(
-                        <#field_ty as ::bitpiece::BitPiece>::#const_name_ident
-                    ) as #storage_type
+                    #bits as #storage_type
                 ) << #offset
             )
         }
     });
+    // each field holds a valid value of its type, but the validation function of the struct is not called, since a
+    // constant which breaks the invariants would otherwise fail to compile, along with any struct containing this one.
     quote! {
-        #type_ident::from_bits(
-            0 #(#bitwise_or_each_field)*
-        )
+        #type_ident {
+            storage: 0 #(#bitwise_or_each_field)*
+        }
     }
 }
 
@@ -230,6 +405,255 @@ fn get_field_len(type_ident: &syn::Ident, field: &Field) -> BitLenExpr {
     })
 }
 
+/// returns the bit length of the given field.
+fn field_bit_len(field: &Field) -> BitLenExpr {
+    let ty = &field.ty;
+    match field_repr(field) {
+        FieldRepr::BitPiece => TypeExpr::from_type(ty).bit_len(),
+        FieldRepr::Codec(codec) => codec.bit_len(),
+        FieldRepr::NarrowedInt(narrowed_int) => {
+            let len = narrowed_int.bit_len();
+            BitLenExpr(quote! {
+                ::bitpiece::check_narrowed_bit_len(#len, <#ty as ::bitpiece::BitPiece>::BITS)
+            })
+        }
+    }
+}
+
+/// returns the type of the raw bits of the given field, which its value is converted from and to.
+fn field_bits_type(field: &Field) -> TypeExpr {
+    let ty = &field.ty;
+    match field_repr(field).explicit_bit_len() {
+        Some(len) => len.storage_type(),
+        None => TypeExpr(quote! { <#ty as ::bitpiece::BitPiece>::Bits }),
+    }
+}
+
+/// returns an expression which converts the given raw bits of a field to its value.
+fn field_from_bits(field: &Field, bits: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let ty = &field.ty;
+    let bits_type = field_bits_type(field);
+    match field_repr(field) {
+        FieldRepr::BitPiece => quote! {
+            <#ty as ::bitpiece::BitPiece>::Converter::from_bits(#bits as #bits_type)
+        },
+        FieldRepr::Codec(FieldCodec { module, .. }) => quote! {
+            #module::decode(#bits as #bits_type)
+        },
+        FieldRepr::NarrowedInt(narrowed_int) => {
+            if narrowed_int.is_signed {
+                let len = narrowed_int.bit_len();
+                quote! {
+                    (::bitpiece::sign_extend_bits(#bits as u64, #len) as #ty)
+                }
+            } else {
+                quote! {
+                    (#bits as #ty)
+                }
+            }
+        }
+    }
+}
+
+/// returns an expression which converts the given value of a field to its raw bits, panicking if it doesn't fit in the
+/// field.
+fn field_to_bits(field: &Field, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let ty = &field.ty;
+    match field_repr(field) {
+        FieldRepr::BitPiece => quote! {
+            <#ty as ::bitpiece::BitPiece>::Converter::to_bits(#value)
+        },
+        FieldRepr::Codec(codec) => {
+            let module = &codec.module;
+            let len = codec.bit_len();
+            quote! {
+                ::bitpiece::check_encoded_bits(#module::encode(#value) as u64, #len)
+            }
+        }
+        FieldRepr::NarrowedInt(narrowed_int) => {
+            let len = narrowed_int.bit_len();
+            if narrowed_int.is_signed {
+                quote! { ::bitpiece::narrow_signed(#value as i64, #len) }
+            } else {
+                quote! { ::bitpiece::narrow_unsigned(#value as u64, #len) }
+            }
+        }
+    }
+}
+
+/// returns an expression which converts the given value of a field to its raw bits, evaluating to a
+/// `Result<u64, TryFromBitIntError>` which is an error if the value doesn't fit in the field.
+///
+/// returns `None` if all values of the type of the field fit in it.
+fn field_checked_to_bits(
+    field: &Field,
+    value: proc_macro2::TokenStream,
+) -> Option<proc_macro2::TokenStream> {
+    match field_repr(field) {
+        FieldRepr::BitPiece => None,
+        FieldRepr::Codec(codec) => {
+            let module = &codec.module;
+            let len = codec.bit_len();
+            Some(quote! {
+                ::bitpiece::try_narrow_unsigned(#module::encode(#value) as u64, #len)
+            })
+        }
+        FieldRepr::NarrowedInt(narrowed_int) => {
+            let len = narrowed_int.bit_len();
+            Some(if narrowed_int.is_signed {
+                quote! { ::bitpiece::try_narrow_signed(#value as i64, #len) }
+            } else {
+                quote! { ::bitpiece::try_narrow_unsigned(#value as u64, #len) }
+            })
+        }
+    }
+}
+
+/// returns an expression which checks whether the given raw bits are a valid value of a field.
+fn field_is_valid_bits(field: &Field, bits: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let ty = &field.ty;
+    let bits_type = field_bits_type(field);
+    match field_repr(field) {
+        FieldRepr::BitPiece => quote! {
+            <#ty as ::bitpiece::BitPiece>::Converter::try_from_bits(#bits as #bits_type).is_some()
+        },
+        FieldRepr::Codec(FieldCodec { validate, .. }) => match validate {
+            Some(validate) => quote! { #validate(#bits as #bits_type) },
+            // without a validation function, all bits of the field are valid
+            None => quote! { true },
+        },
+        // all bits of a narrowed integer field are valid
+        FieldRepr::NarrowedInt(_) => quote! { true },
+    }
+}
+
+/// generates the body of the `call_setters` method, which sets each field of `value` to its value in `source`, once
+/// using each of the generated setters of the field, and passes each result to `f`. this allows `testing::check` to
+/// verify the setters, which can't be called generically.
+///
+/// returns `None` if none of the setters are generated.
+fn gen_call_setters_code(
+    type_ident: &syn::Ident,
+    fields: &FieldsNamed,
+    storage_type: &StorageTypeExpr,
+    mut_type_ident: &syn::Ident,
+    macro_args: &MacroArgs,
+) -> Option<proc_macro2::TokenStream> {
+    let has_opt_in = |opt_in| macro_args.opt_ins.contains(&opt_in);
+    let has_mut_struct_opt_in = |opt_in| has_opt_in(OptIn::MutStruct) && has_opt_in(opt_in);
+    let mut_struct_storage =
+        storage_type.convert_mut_ref_to_storage_mut_ref(quote! { &mut modified.storage });
+    let mut_struct = quote! {
+        #mut_type_ident::new(#mut_struct_storage, 0)
+    };
+    let calls: Vec<_> = fields_values(type_ident, fields, storage_type, quote! { source.storage })
+        .zip(fields.named.iter())
+        .flat_map(|(new_value, field)| {
+            let name = field.ident.as_ref().unwrap().to_string();
+            let offset = get_field_offset(type_ident, field);
+            let len = get_field_len(type_ident, field);
+            let ident = field.ident.as_ref().unwrap();
+            let set_ident = format_ident!("set_{}", ident);
+            let with_ident = format_ident!("with_{}", ident);
+            let mut_ident = format_ident!("{}_mut", ident);
+            let repr = field_repr(field);
+            let has_try_setters = !repr.is_bitpiece();
+            let has_mut_ref = !matches!(repr, FieldRepr::NarrowedInt(_));
+
+            let mut setters = Vec::new();
+            if has_opt_in(OptIn::Set) {
+                setters.push((
+                    set_ident.to_string(),
+                    quote! { modified.#set_ident(new_value); Some(modified) },
+                ));
+                if has_try_setters {
+                    let try_set_ident = format_ident!("try_set_{}", ident);
+                    setters.push((
+                        try_set_ident.to_string(),
+                        quote! { modified.#try_set_ident(new_value).ok().map(|()| modified) },
+                    ));
+                }
+            }
+            if has_opt_in(OptIn::With) {
+                setters.push((
+                    with_ident.to_string(),
+                    quote! { Some(modified.#with_ident(new_value)) },
+                ));
+                if macro_args.validate.is_some() {
+                    let checked_with_ident = format_ident!("checked_with_{}", ident);
+                    setters.push((
+                        checked_with_ident.to_string(),
+                        quote! { modified.#checked_with_ident(new_value) },
+                    ));
+                }
+                if has_try_setters {
+                    let try_with_ident = format_ident!("try_with_{}", ident);
+                    setters.push((
+                        try_with_ident.to_string(),
+                        quote! { modified.#try_with_ident(new_value).ok() },
+                    ));
+                }
+            }
+            // the mutable reference of a field with a custom converter is not a `BitPieceMutRef`, but has a `set` method
+            // of its own, which is preferred over the trait method.
+            if has_opt_in(OptIn::GetMut) && has_mut_ref {
+                setters.push((
+                    format!("{}().set", mut_ident),
+                    quote! {
+                        #[allow(unused_imports)]
+                        use ::bitpiece::BitPieceMutRef as _;
+                        modified.#mut_ident().set(new_value);
+                        Some(modified)
+                    },
+                ));
+            }
+            if has_mut_struct_opt_in(OptIn::MutStructFieldSet) {
+                setters.push((
+                    format!("{}::{}", mut_type_ident, set_ident),
+                    quote! { #mut_struct.#set_ident(new_value); Some(modified) },
+                ));
+            }
+            if has_mut_struct_opt_in(OptIn::MutStructFieldMut) && has_mut_ref {
+                setters.push((
+                    format!("{}::{}().set", mut_type_ident, mut_ident),
+                    quote! {
+                        #[allow(unused_imports)]
+                        use ::bitpiece::BitPieceMutRef as _;
+                        #mut_struct.#mut_ident().set(new_value);
+                        Some(modified)
+                    },
+                ));
+            }
+
+            setters
+                .into_iter()
+                .map(move |(setter, call)| {
+                    quote! {
+                        f(::bitpiece::testing::SetterCall {
+                            field: #name,
+                            setter: #setter,
+                            offset: #offset,
+                            len: #len,
+                            result: {
+                                #[allow(unused_mut)]
+                                let mut modified = value;
+                                let new_value = #new_value;
+                                #call
+                            },
+                        });
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    if calls.is_empty() {
+        return None;
+    }
+    Some(quote! {
+        #(#calls)*
+    })
+}
+
 fn gen_fields_offsets_and_lens_consts(
     type_ident: &syn::Ident,
     fields: &FieldsNamed,
@@ -258,7 +682,7 @@ fn gen_fields_offsets_and_lens_consts(
                 }
                 None => quote! { 0 },
             };
-            let len = TypeExpr::from_type(&cur.ty).bit_len();
+            let len = field_bit_len(cur);
             quote! {
                 pub const #len_const_ident: usize = #len;
                 pub const #offset_const_ident: usize = #offset;
@@ -268,11 +692,7 @@ fn gen_fields_offsets_and_lens_consts(
 }
 
 fn calc_bit_len(fields: &FieldsNamed) -> BitLenExpr {
-    let field_types = fields
-        .named
-        .iter()
-        .map(|field| TypeExpr::from_type(&field.ty));
-    field_types.clone().map(|field_ty| field_ty.bit_len()).sum()
+    fields.named.iter().map(field_bit_len).sum()
 }
 
 /// returns an iterator over the extracted bits of each field.
@@ -294,6 +714,18 @@ fn fields_extracted_bits<'a>(
     })
 }
 
+/// returns an iterator over the values of each field, extracted from the given storage bits.
+fn fields_values<'a>(
+    type_ident: &'a syn::Ident,
+    fields: &'a FieldsNamed,
+    storage_type: &'a StorageTypeExpr,
+    storage_bits_expr: proc_macro2::TokenStream,
+) -> impl Iterator<Item = proc_macro2::TokenStream> + 'a {
+    fields_extracted_bits(type_ident, fields, storage_type, storage_bits_expr)
+        .zip(fields.named.iter())
+        .map(|(bits, field)| field_from_bits(field, bits))
+}
+
 /// returns an iterator over the extracted bits (mask only, no shift) of each field.
 fn fields_extracted_bits_noshift<'a>(
     type_ident: &'a syn::Ident,
@@ -377,30 +809,42 @@ fn modify_bits(params: ModifyBitsParams) -> proc_macro2::TokenStream {
     }
 }
 
-fn gen_from_fields(
+/// generates code for constructing the type from the field values stored in the fields of the given struct value. the
+/// generated expression evaluates to `None` if some field value doesn't fit in its field, or if the field values break
+/// the invariants of the type.
+fn gen_try_from_field_values(
+    type_ident: &syn::Ident,
     fields: &FieldsNamed,
-    input: &DeriveInput,
     storage_type: &StorageTypeExpr,
+    field_values_struct: proc_macro2::TokenStream,
 ) -> proc_macro2::TokenStream {
-    let type_ident = &input.ident;
     let bitwise_or_each_field = fields.named.iter().map(|f| {
-        let field_ty = &f.ty;
         let field_ident = &f.ident;
         let offset = get_field_offset(type_ident, f);
+        let value = quote! { #field_values_struct.#field_ident };
+        let bits = match field_checked_to_bits(f, value.clone()) {
+            Some(checked_bits) => quote! {
+                match #checked_bits {
+                    Ok(bits) => bits,
+                    Err(_) => break 'try_from_field_values None,
+                }
+            },
+            None => field_to_bits(f, value),
+        };
         quote! {
             | (
                 (
-                    <#field_ty as ::bitpiece::BitPiece>::Converter::to_bits(
-                        fields.#field_ident
-                    ) as #storage_type
+                    #bits as #storage_type
                 ) << #offset
             )
         }
     });
     quote! {
-        #type_ident::from_bits(
-            0 #(#bitwise_or_each_field)*
-        )
+        'try_from_field_values: {
+            #type_ident::try_from_bits(
+                0 #(#bitwise_or_each_field)*
+            )
+        }
     }
 }
 
@@ -408,38 +852,505 @@ fn gen_try_from_bits_code(
     type_ident: &syn::Ident,
     fields: &FieldsNamed,
     storage_type: &StorageTypeExpr,
+    macro_args: &MacroArgs,
 ) -> proc_macro2::TokenStream {
     // before constructing the type, make sure that the values of all fields are valid
     let per_field_call = fields_extracted_bits(type_ident, fields, storage_type, quote! { bits })
         .zip(fields.named.iter())
         .map(|(bits, field)| {
-            let ty = &field.ty;
+            let is_valid = field_is_valid_bits(field, bits);
             quote! {
-                if <#ty as ::bitpiece::BitPiece>::Converter::try_from_bits(#bits as <#ty as ::bitpiece::BitPiece>::Bits).is_none() {
+                if !#is_valid {
                     return None;
                 }
             }
         });
+    // after all fields are known to be valid, check the invariants of the struct itself
+    let validate_call = macro_args.validate.as_ref().map(|validate_arg| {
+        let path = &validate_arg.path;
+        quote! {
+            if !#path(result) {
+                return None;
+            }
+        }
+    });
     quote! {
         let result = Self { storage: bits };
         #(#per_field_call)*
+        #validate_call
         Some(result)
     }
 }
 
-fn gen_to_fields<'a>(
-    fields: &'a FieldsNamed,
+fn gen_to_fields(
+    type_ident: &syn::Ident,
+    fields: &FieldsNamed,
+    storage_type: &StorageTypeExpr,
     fields_struct_ident: &syn::Ident,
 ) -> proc_macro2::TokenStream {
-    let field_initializers = fields.named.iter().map(|field| {
-        let field_ident = field.ident.as_ref().unwrap();
+    let field_initializers =
+        fields_values(type_ident, fields, storage_type, quote! { self.storage })
+            .zip(fields.named.iter())
+            .map(|(value, field)| {
+                let field_ident = field.ident.as_ref().unwrap();
+                quote! {
+                    #field_ident: #value,
+                }
+            });
+    quote! {
+        #fields_struct_ident {
+            #(#field_initializers)*
+        }
+    }
+}
+
+/// generates an implementation of the `BitPieceHasLayout` trait, which lists the fields of the struct.
+fn gen_layout_impl(type_ident: &syn::Ident, fields: &FieldsNamed) -> proc_macro2::TokenStream {
+    let type_name = type_ident.to_string();
+    let field_infos = fields.named.iter().map(|field| {
+        let name = field.ident.as_ref().unwrap().to_string();
+        let offset = get_field_offset(type_ident, field);
+        let len = get_field_len(type_ident, field);
+        let ty = &field.ty;
+        let repr = field_repr(field);
+        if !repr.is_bitpiece() {
+            // a field with a custom converter is described as an opaque value of its type, and a narrowed integer field
+            // is described using its native integer type.
+            let (type_name, is_signed) = match repr {
+                FieldRepr::NarrowedInt(_) => (
+                    quote! { <#ty as ::bitpiece::BitPieceHasLayout>::TYPE_NAME },
+                    quote! { <#ty as ::bitpiece::BitPieceHasLayout>::IS_SIGNED },
+                ),
+                _ => {
+                    let type_name = ty.to_token_stream().to_string().replace(' ', "");
+                    (quote! { #type_name }, quote! { false })
+                }
+            };
+            let is_valid = field_is_valid_bits(field, quote! { bits });
+            return quote! {
+                ::bitpiece::FieldInfo {
+                    name: #name,
+                    offset: #offset,
+                    len: #len,
+                    type_name: #type_name,
+                    is_signed: #is_signed,
+                    variants: &[],
+                    fields: &[],
+                    is_valid_bits: {
+                        fn is_valid_bits(bits: u64) -> bool {
+                            ::bitpiece::extract_bits(bits, 0, #len) == bits && #is_valid
+                        }
+                        is_valid_bits
+                    },
+                }
+            };
+        }
         quote! {
-            #field_ident: self.#field_ident(),
+            ::bitpiece::FieldInfo {
+                name: #name,
+                offset: #offset,
+                len: #len,
+                type_name: <#ty as ::bitpiece::BitPieceHasLayout>::TYPE_NAME,
+                is_signed: <#ty as ::bitpiece::BitPieceHasLayout>::IS_SIGNED,
+                variants: <#ty as ::bitpiece::BitPieceHasLayout>::VARIANTS,
+                fields: <#ty as ::bitpiece::BitPieceHasLayout>::FIELDS,
+                is_valid_bits: ::bitpiece::is_valid_bits::<#ty>,
+            }
         }
     });
     quote! {
-        #fields_struct_ident {
-            #(#field_initializers)*
+        #[automatically_derived]
+        impl ::bitpiece::BitPieceHasLayout for #type_ident {
+            const TYPE_NAME: &'static str = #type_name;
+            const FIELDS: &'static [::bitpiece::FieldInfo] = &[#(#field_infos),*];
+        }
+    }
+}
+
+/// generates an expression which generates an arbitrary value of a field from the `u: Unstructured`.
+///
+/// the value of a field which is not stored as a bitpiece is decoded from arbitrary raw bits, since not all values of
+/// its type necessarily fit in the field, and the type of a field with a custom converter doesn't necessarily implement
+/// `Arbitrary`.
+fn gen_field_arbitrary_value(type_ident: &syn::Ident, field: &Field) -> proc_macro2::TokenStream {
+    if field_repr(field).is_bitpiece() {
+        return quote! { ::bitpiece::arbitrary::Arbitrary::arbitrary(u)? };
+    }
+    let len = get_field_len(type_ident, field);
+    let is_valid = field_is_valid_bits(field, quote! { bits });
+    let value = field_from_bits(field, quote! { bits });
+    quote! {
+        {
+            let bits: u64 = u.int_in_range(0..=::bitpiece::extract_bits(u64::MAX, 0, #len))?;
+            if !#is_valid {
+                return Err(::bitpiece::arbitrary::Error::IncorrectFormat);
+            }
+            #value
+        }
+    }
+}
+
+/// generates an expression which evaluates to the proptest strategy of the values of a field.
+///
+/// like with `arbitrary`, the value of a field which is not stored as a bitpiece is decoded from raw bits.
+fn gen_field_proptest_strategy(type_ident: &syn::Ident, field: &Field) -> proc_macro2::TokenStream {
+    let ty = &field.ty;
+    if field_repr(field).is_bitpiece() {
+        return quote! { ::bitpiece::proptest::arbitrary::any::<#ty>() };
+    }
+    let len = get_field_len(type_ident, field);
+    let is_valid = field_is_valid_bits(field, quote! { bits });
+    let value = field_from_bits(field, quote! { bits });
+    let reject_reason = format!("invalid value of field `{}`", field.ident.as_ref().unwrap());
+    quote! {
+        ::bitpiece::proptest::strategy::Strategy::prop_filter_map(
+            0..=::bitpiece::extract_bits(u64::MAX, 0, #len),
+            #reject_reason,
+            |bits: u64| if #is_valid { Some(#value) } else { None },
+        )
+    }
+}
+
+/// generates an implementation of the `BitPieceVisit` trait, which passes each field to the visitor, between calls to
+/// `enter_struct` and `exit_struct`.
+fn gen_visit_impl(
+    type_ident: &syn::Ident,
+    fields: &FieldsNamed,
+    storage_type: &StorageTypeExpr,
+) -> proc_macro2::TokenStream {
+    let type_name = type_ident.to_string();
+    let per_field_call =
+        fields_extracted_bits(type_ident, fields, storage_type, quote! { self.storage })
+            .zip(fields.named.iter())
+            .map(|(bits, field)| {
+                let name = field.ident.as_ref().unwrap().to_string();
+                let ty = &field.ty;
+                let len = get_field_len(type_ident, field);
+                let value = field_from_bits(field, bits.clone());
+                match field_repr(field) {
+                    FieldRepr::BitPiece => {}
+                    // the value of a field with a custom converter is not a bitpiece, so its raw bits are visited instead
+                    FieldRepr::Codec(_) => {
+                        return quote! {
+                            visitor.visit_unsigned(#name, #bits as u64, #len);
+                        };
+                    }
+                    FieldRepr::NarrowedInt(narrowed_int) => {
+                        return if narrowed_int.is_signed {
+                            quote! { visitor.visit_signed(#name, #value as i64, #len); }
+                        } else {
+                            quote! { visitor.visit_unsigned(#name, #value as u64, #len); }
+                        };
+                    }
+                }
+                quote! {
+                    <#ty as ::bitpiece::BitPieceVisit>::visit_as(&#value, #name, visitor);
+                }
+            });
+    quote! {
+        #[automatically_derived]
+        impl ::bitpiece::BitPieceVisit for #type_ident {
+            fn visit_as(&self, name: &str, visitor: &mut impl ::bitpiece::BitPieceVisitor) {
+                visitor.enter_struct(name, #type_name);
+                #(#per_field_call)*
+                visitor.exit_struct(name, #type_name);
+            }
+        }
+    }
+}
+
+/// checks that every bit pattern is a valid value of the struct, which the bitwise operators rely on, since their
+/// results are built from arbitrary combinations of the bits of their operands.
+///
+/// this holds if the struct has no validation function, and all of its fields are `bool`s or integers.
+fn check_bitops_fields(fields: &FieldsNamed, macro_args: &MacroArgs) -> syn::Result<()> {
+    if let Some(validate_arg) = &macro_args.validate {
+        return Err(syn::Error::new_spanned(
+            &validate_arg.ident,
+            "the `bitops` opt in can't be used together with a validation function",
+        ));
+    }
+    for field in &fields.named {
+        let is_bool_or_int = match field_repr(field) {
+            FieldRepr::BitPiece => is_bool_or_int_type(&field.ty),
+            FieldRepr::Codec(_) => false,
+            FieldRepr::NarrowedInt(_) => true,
+        };
+        if !is_bool_or_int {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                "the `bitops` opt in requires all fields to be `bool`s or integers, so that every bit pattern is valid",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// returns whether the given type is `bool`, a native integer type, or one of the `B*` and `SB*` types.
+fn is_bool_or_int_type(ty: &syn::Type) -> bool {
+    let syn::Type::Path(ty) = ty else {
+        return false;
+    };
+    let Some(ident) = ty.path.get_ident().filter(|_| ty.qself.is_none()) else {
+        return false;
+    };
+    let ident = ident.to_string();
+    let is_arbitrary_width_int = ident
+        .strip_prefix("SB")
+        .or_else(|| ident.strip_prefix('B'))
+        .is_some_and(|len| {
+            len.parse::<usize>()
+                .is_ok_and(|len| (1..=64).contains(&len))
+        });
+    is_arbitrary_width_int
+        || matches!(
+            ident.as_str(),
+            "bool" | "u8" | "u16" | "u32" | "u64" | "i8" | "i16" | "i32" | "i64"
+        )
+}
+
+/// generates implementations of the bitwise operator traits and the set-like const methods, which operate on the raw
+/// storage bits.
+///
+/// the results are constructed using `from_bits`, which is only valid since [`check_bitops_fields`] makes sure that
+/// every bit pattern is a valid value of the struct.
+fn gen_bitops_impl(
+    type_ident: &syn::Ident,
+    bit_len: &BitLenExpr,
+    storage_type: &StorageTypeExpr,
+) -> proc_macro2::TokenStream {
+    let binary_ops = [
+        ("BitOr", "bitor", "BitOrAssign", "bitor_assign", "union"),
+        (
+            "BitAnd",
+            "bitand",
+            "BitAndAssign",
+            "bitand_assign",
+            "intersection",
+        ),
+        (
+            "BitXor",
+            "bitxor",
+            "BitXorAssign",
+            "bitxor_assign",
+            "symmetric_difference",
+        ),
+    ]
+    .map(
+        |(trait_name, fn_name, assign_trait_name, assign_fn_name, method_name)| {
+            let trait_ident = format_ident!("{}", trait_name);
+            let fn_ident = format_ident!("{}", fn_name);
+            let assign_trait_ident = format_ident!("{}", assign_trait_name);
+            let assign_fn_ident = format_ident!("{}", assign_fn_name);
+            let method_ident = format_ident!("{}", method_name);
+            quote! {
+                #[automatically_derived]
+                impl ::core::ops::#trait_ident for #type_ident {
+                    type Output = Self;
+                    fn #fn_ident(self, rhs: Self) -> Self {
+                        self.#method_ident(rhs)
+                    }
+                }
+                #[automatically_derived]
+                impl ::core::ops::#assign_trait_ident for #type_ident {
+                    fn #assign_fn_ident(&mut self, rhs: Self) {
+                        *self = self.#method_ident(rhs);
+                    }
+                }
+            }
+        },
+    );
+    quote! {
+        #(#binary_ops)*
+
+        #[automatically_derived]
+        impl ::core::ops::Not for #type_ident {
+            type Output = Self;
+            fn not(self) -> Self {
+                self.complement()
+            }
+        }
+
+        impl #type_ident {
+            /// returns the bitwise or of the two values.
+            pub const fn union(self, other: Self) -> Self {
+                Self::from_bits(self.storage | other.storage)
+            }
+
+            /// returns the bitwise and of the two values.
+            pub const fn intersection(self, other: Self) -> Self {
+                Self::from_bits(self.storage & other.storage)
+            }
+
+            /// returns the bits which are set in `self` but not in `other`.
+            pub const fn difference(self, other: Self) -> Self {
+                Self::from_bits(self.storage & !other.storage)
+            }
+
+            /// returns the bitwise xor of the two values.
+            pub const fn symmetric_difference(self, other: Self) -> Self {
+                Self::from_bits(self.storage ^ other.storage)
+            }
+
+            /// returns the bitwise not of this value. only the bits within the bit length of this type are flipped, so
+            /// the unused bits of the storage stay zero.
+            pub const fn complement(self) -> Self {
+                Self::from_bits(::bitpiece::extract_bits(!self.storage as u64, 0, #bit_len) as #storage_type)
+            }
+
+            /// returns whether all bits which are set in `other` are also set in `self`.
+            pub const fn contains(self, other: Self) -> bool {
+                self.storage & other.storage == other.storage
+            }
+
+            /// returns whether no bits are set.
+            pub const fn is_empty(self) -> bool {
+                self.storage == 0
+            }
+        }
+    }
+}
+
+/// generates the flag set implementation of a struct whose fields are all `bool`s, which includes a constant for each
+/// single flag, named after the field, and the `BitPieceFlags`, `Display` and `FromStr` implementations.
+fn gen_flags_impl(
+    type_ident: &syn::Ident,
+    fields: &FieldsNamed,
+    storage_type: &StorageTypeExpr,
+) -> syn::Result<proc_macro2::TokenStream> {
+    for field in &fields.named {
+        let is_bool = matches!(&field.ty, syn::Type::Path(ty) if ty.qself.is_none() && ty.path.is_ident("bool"))
+            && field_repr(field).is_bitpiece();
+        if !is_bool {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                "the `flags` opt in requires all fields to be `bool`s",
+            ));
+        }
+    }
+    let flag_idents = fields
+        .named
+        .iter()
+        .map(|field| {
+            let field_ident = field.ident.as_ref().unwrap();
+            syn::Ident::new(
+                &field_ident
+                    .to_string()
+                    .to_case(convert_case::Case::Constant),
+                field_ident.span(),
+            )
+        })
+        .collect::<Vec<_>>();
+    let flag_names = flag_idents.iter().map(|ident| ident.to_string());
+    let flag_consts = fields
+        .named
+        .iter()
+        .zip(&flag_idents)
+        .map(|(field, flag_ident)| {
+            let offset = get_field_offset(type_ident, field);
+            let doc = format!(
+                "a value with only the `{}` flag set.",
+                field.ident.as_ref().unwrap()
+            );
+            quote! {
+                #[doc = #doc]
+                pub const #flag_ident: Self = Self::from_bits((1 as #storage_type) << #offset);
+            }
+        });
+    Ok(quote! {
+        impl #type_ident {
+            #(#flag_consts)*
+        }
+        #[automatically_derived]
+        impl ::bitpiece::BitPieceFlags for #type_ident {
+            const FLAGS: &'static [(&'static str, Self)] = &[#((#flag_names, Self::#flag_idents)),*];
+        }
+        #[automatically_derived]
+        impl ::core::fmt::Display for #type_ident {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::bitpiece::fmt_flags(*self, f)
+            }
+        }
+        #[automatically_derived]
+        impl ::core::str::FromStr for #type_ident {
+            type Err = ::bitpiece::ParseFlagsError;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                ::bitpiece::parse_flags(s)
+            }
+        }
+    })
+}
+
+/// generates an array of the bit lengths of all fields.
+fn gen_field_lens_array(type_ident: &syn::Ident, fields: &FieldsNamed) -> proc_macro2::TokenStream {
+    let lens = fields
+        .named
+        .iter()
+        .map(|field| get_field_len(type_ident, field));
+    quote! {
+        [#(#lens),*]
+    }
+}
+
+/// generates a function which returns a diagram of the bit layout of the struct and the current values of its fields.
+fn gen_layout_diagram_fn(
+    type_ident: &syn::Ident,
+    fields: &FieldsNamed,
+) -> proc_macro2::TokenStream {
+    let type_name = type_ident.to_string();
+    let diagram_fields = fields.named.iter().map(|field| {
+        let name = field.ident.as_ref().unwrap().to_string();
+        let offset = get_field_offset(type_ident, field);
+        let len = get_field_len(type_ident, field);
+        quote! {
+            ::bitpiece::LayoutDiagramField {
+                name: #name,
+                offset: #offset,
+                len: #len,
+            }
+        }
+    });
+    quote! {
+        impl #type_ident {
+            /// returns a diagram of the bit layout of this type and the current values of its fields, which can be
+            /// displayed using its `Display` implementation.
+            pub const fn layout_diagram(self) -> ::bitpiece::LayoutDiagram<'static> {
+                const FIELDS: &[::bitpiece::LayoutDiagramField] = &[#(#diagram_fields),*];
+                ::bitpiece::LayoutDiagram::new(#type_name, self.storage as u64, FIELDS)
+            }
+        }
+    }
+}
+
+/// generates a `Debug` implementation which lists the decoded value of each field.
+///
+/// when formatting using the alternate flag (`{:#?}`), the raw storage is also shown, in hex.
+fn gen_debug_impl(
+    type_ident: &syn::Ident,
+    fields: &FieldsNamed,
+    storage_type: &StorageTypeExpr,
+) -> proc_macro2::TokenStream {
+    let type_name = type_ident.to_string();
+    let per_field_call = fields_values(type_ident, fields, storage_type, quote! { self.storage })
+        .zip(fields.named.iter())
+        .map(|(value, field)| {
+            let name = field.ident.as_ref().unwrap().to_string();
+            quote! {
+                debug_struct.field(#name, &#value);
+            }
+        });
+    quote! {
+        #[automatically_derived]
+        impl ::core::fmt::Debug for #type_ident {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let alternate = f.alternate();
+                let mut debug_struct = f.debug_struct(#type_name);
+                if alternate {
+                    debug_struct.field("storage", &format_args!("{:#x}", self.storage));
+                }
+                #(#per_field_call)*
+                debug_struct.finish()
+            }
         }
     }
 }
@@ -449,18 +1360,19 @@ fn gen_field_access_fns(
     fields: &FieldsNamed,
     storage_type: &StorageTypeExpr,
 ) -> proc_macro2::TokenStream {
-    fields_extracted_bits(type_ident, fields, storage_type, quote!{ self.storage })
+    fields_values(type_ident, fields, storage_type, quote! { self.storage })
         .zip(fields.named.iter())
-        .map(|(bits, field)| {
+        .map(|(value, field)| {
             let vis = &field.vis;
             let ident = &field.ident;
             let ty = &field.ty;
             quote! {
                 #vis const fn #ident (self) -> #ty {
-                    <#ty as ::bitpiece::BitPiece>::Converter::from_bits(#bits as <#ty as ::bitpiece::BitPiece>::Bits)
+                    #value
                 }
             }
-        }).collect()
+        })
+        .collect()
 }
 
 fn gen_field_with_fns(
@@ -485,7 +1397,7 @@ fn gen_field_with_fns(
                     extract_offset: offset,
                     extract_len: len,
                 },
-                new_value: quote! { <#ty as ::bitpiece::BitPiece>::Converter::to_bits(new_value) },
+                new_value: field_to_bits(field, quote! { new_value }),
             });
 
             quote! {
@@ -498,6 +1410,71 @@ fn gen_field_with_fns(
         .collect()
 }
 
+/// generates `checked_with_*` functions, which are like the `with_*` functions, but return `None` if the modified value
+/// breaks the invariants checked by the validation function of the struct.
+fn gen_field_checked_with_fns(fields: &FieldsNamed) -> proc_macro2::TokenStream {
+    fields
+        .named
+        .iter()
+        .map(|field| {
+            let vis = &field.vis;
+            let ty = &field.ty;
+            let ident = field.ident.as_ref().unwrap();
+            let with_ident = format_ident!("with_{}", ident);
+            let checked_with_ident = format_ident!("checked_with_{}", ident);
+            quote! {
+                /// returns a copy of this value with the field replaced, or `None` if the result breaks the invariants
+                /// checked by the validation function. unlike `with_*` and `set_*`, this enforces the invariants.
+                #vis const fn #checked_with_ident (self, new_value: #ty) -> Option<Self> {
+                    Self::try_from_bits(self.#with_ident(new_value).storage)
+                }
+            }
+        })
+        .collect()
+}
+
+/// generates `try_with_*` functions for the narrowed integer fields and the fields with custom converters, which are like
+/// the `with_*` functions, but return an error instead of panicking if the new value doesn't fit in the field.
+fn gen_field_try_with_fns(
+    type_ident: &syn::Ident,
+    fields: &FieldsNamed,
+    storage_type: &StorageTypeExpr,
+) -> proc_macro2::TokenStream {
+    fields
+        .named
+        .iter()
+        .filter(|field| !field_repr(field).is_bitpiece())
+        .map(|field| {
+            let len = get_field_len(type_ident, field);
+            let offset = get_field_offset(type_ident, field);
+            let vis = &field.vis;
+            let ty = &field.ty;
+            let ident = field.ident.as_ref().unwrap();
+            let try_with_ident = format_ident!("try_with_{}", ident);
+            let checked_bits = field_checked_to_bits(field, quote! { new_value }).unwrap();
+            let modified_value_expr = modify_bits(ModifyBitsParams {
+                extract_params: ExtractBitsParams {
+                    value: quote! { self.storage },
+                    value_type: storage_type.clone(),
+                    extract_offset: offset,
+                    extract_len: len,
+                },
+                new_value: quote! { new_value_bits },
+            });
+            quote! {
+                #vis const fn #try_with_ident (mut self, new_value: #ty) -> Result<Self, ::bitpiece::TryFromBitIntError> {
+                    let new_value_bits = match #checked_bits {
+                        Ok(bits) => bits,
+                        Err(err) => return Err(err),
+                    };
+                    self.storage = #modified_value_expr;
+                    Ok(self)
+                }
+            }
+        })
+        .collect()
+}
+
 fn gen_field_access_noshift_fns(
     type_ident: &syn::Ident,
     fields: &FieldsNamed,
@@ -531,11 +1508,10 @@ fn gen_mut_struct_field_access_fns(
             let vis = &field.vis;
             let ident = &field.ident;
             let ty = &field.ty;
+            let value = field_from_bits(field, quote! { self.0.get_bits(#offset, #len) });
             quote! {
                 #vis const fn #ident(&self) -> #ty {
-                    <#ty as ::bitpiece::BitPiece>::Converter::from_bits(
-                        self.0.get_bits(#offset, #len) as <#ty as ::bitpiece::BitPiece>::Bits
-                    )
+                    #value
                 }
             }
         })
@@ -556,11 +1532,10 @@ fn gen_mut_struct_field_access_noshift_fns(
             let ty = &field.ty;
             let ident = field.ident.as_ref().unwrap();
             let ident_noshift = format_ident!("{}_noshift", ident);
+            let value = field_from_bits(field, quote! { self.0.get_bits_noshift(#offset, #len) });
             quote! {
                 #vis const fn #ident_noshift(&self) -> #ty {
-                    <#ty as ::bitpiece::BitPiece>::Converter::from_bits(
-                        self.0.get_bits_noshift(#offset, #len) as <#ty as ::bitpiece::BitPiece>::Bits
-                    )
+                    #value
                 }
             }
         })
@@ -581,9 +1556,10 @@ fn gen_mut_struct_field_set_fns(
             let ident = field.ident.as_ref().unwrap();
             let ty = &field.ty;
             let set_ident = format_ident!("set_{}", ident);
+            let new_value_bits = field_to_bits(field, quote! { new_value });
             quote! {
                 #vis const fn #set_ident(&mut self, new_value: #ty) {
-                    let new_value_bits = <#ty as ::bitpiece::BitPiece>::Converter::to_bits(new_value);
+                    let new_value_bits = #new_value_bits;
                     self.0.set_bits(#offset, #len, new_value_bits as u64)
                 }
             }
@@ -598,20 +1574,23 @@ fn gen_mut_struct_field_mut_fns(
     fields
         .named
         .iter()
-        .map(|field| {
+        .filter_map(|field| {
             let offset = get_field_offset(type_ident, field);
             let vis = &field.vis;
             let ident = field.ident.as_ref().unwrap();
-            let ty = &field.ty;
             let ident_mut = format_ident!("{}_mut", ident);
-            let mut_ty = quote! {
-                <#ty as ::bitpiece::BitPieceHasMutRef>::MutRef
-            };
-            quote! {
-                #vis const fn #ident_mut(&'s mut self) -> #mut_ty<'s> {
-                    #mut_ty::new(self.0.storage.reborrow(), self.0.start_bit_index + #offset)
+            let (mut_ty, new_mut_ref) = field_mut_ref(
+                type_ident,
+                field,
+                syn::parse_quote! { 's },
+                quote! { self.0.storage.reborrow() },
+                quote! { self.0.start_bit_index + #offset },
+            )?;
+            Some(quote! {
+                #vis const fn #ident_mut(&'s mut self) -> #mut_ty {
+                    #new_mut_ref
                 }
-            }
+            })
         })
         .collect()
 }
@@ -638,7 +1617,7 @@ fn gen_field_set_fns(
                     extract_offset: offset,
                     extract_len: len,
                 },
-                new_value: quote! { <#ty as ::bitpiece::BitPiece>::Converter::to_bits(new_value) },
+                new_value: field_to_bits(field, quote! { new_value }),
             });
             quote! {
                 #vis const fn #set_ident (&mut self, new_value: #ty) {
@@ -649,7 +1628,10 @@ fn gen_field_set_fns(
         .collect()
 }
 
-fn gen_field_mut_fns(
+/// generates `try_set_*` functions for the narrowed integer fields and the fields with custom converters, which are like
+/// the `set_*` functions, but return an error instead of panicking if the new value doesn't fit in the field, in which
+/// case the value is left unchanged.
+fn gen_field_try_set_fns(
     type_ident: &syn::Ident,
     fields: &FieldsNamed,
     storage_type: &StorageTypeExpr,
@@ -657,23 +1639,109 @@ fn gen_field_mut_fns(
     fields
         .named
         .iter()
-        .map(move |field| {
+        .filter(|field| !field_repr(field).is_bitpiece())
+        .map(|field| {
+            let len = get_field_len(type_ident, field);
             let offset = get_field_offset(type_ident, field);
             let vis = &field.vis;
             let ident = field.ident.as_ref().unwrap();
             let ty = &field.ty;
+            let try_set_ident = format_ident!("try_set_{}", ident);
+            let checked_bits = field_checked_to_bits(field, quote! { new_value }).unwrap();
+            let modified_value_expr = modify_bits(ModifyBitsParams {
+                extract_params: ExtractBitsParams {
+                    value: quote! { self.storage },
+                    value_type: storage_type.clone(),
+                    extract_offset: offset,
+                    extract_len: len,
+                },
+                new_value: quote! { new_value_bits },
+            });
+            quote! {
+                #vis const fn #try_set_ident (&mut self, new_value: #ty) -> Result<(), ::bitpiece::TryFromBitIntError> {
+                    let new_value_bits = match #checked_bits {
+                        Ok(bits) => bits,
+                        Err(err) => return Err(err),
+                    };
+                    self.storage = #modified_value_expr;
+                    Ok(())
+                }
+            }
+        })
+        .collect()
+}
+
+fn gen_field_mut_fns(
+    type_ident: &syn::Ident,
+    fields: &FieldsNamed,
+    storage_type: &StorageTypeExpr,
+) -> proc_macro2::TokenStream {
+    fields
+        .named
+        .iter()
+        .filter_map(move |field| {
+            let offset = get_field_offset(type_ident, field);
+            let vis = &field.vis;
+            let ident = field.ident.as_ref().unwrap();
             let ident_mut = format_ident!("{}_mut", ident);
-            let storage_type = storage_type.clone();
-            let mut_ty = quote! {
-                <#ty as ::bitpiece::BitPieceHasMutRef>::MutRef
-            };
             let storage_mut_ref =
                 storage_type.convert_mut_ref_to_storage_mut_ref(quote! { &mut self.storage });
-            quote! {
-                #vis const fn #ident_mut<'a>(&'a mut self) -> #mut_ty<'a> {
-                    #mut_ty::new(#storage_mut_ref, #offset)
+            let (mut_ty, new_mut_ref) = field_mut_ref(
+                type_ident,
+                field,
+                syn::parse_quote! { 'a },
+                storage_mut_ref,
+                quote! { #offset },
+            )?;
+            Some(quote! {
+                #vis const fn #ident_mut<'a>(&'a mut self) -> #mut_ty {
+                    #new_mut_ref
                 }
-            }
+            })
         })
         .collect()
 }
+
+/// returns the type of a mutable reference with the given lifetime to the given field, and an expression which creates
+/// it from the given storage and bit index.
+///
+/// returns `None` for narrowed integer fields, which have no mutable reference type.
+fn field_mut_ref(
+    type_ident: &syn::Ident,
+    field: &Field,
+    lifetime: syn::Lifetime,
+    storage_mut_ref: proc_macro2::TokenStream,
+    start_bit_index: proc_macro2::TokenStream,
+) -> Option<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
+    let ty = &field.ty;
+    match field_repr(field) {
+        FieldRepr::BitPiece => {
+            let mut_ty = quote! {
+                <#ty as ::bitpiece::BitPieceHasMutRef>::MutRef
+            };
+            Some((
+                quote! { #mut_ty<#lifetime> },
+                quote! { #mut_ty::new(#storage_mut_ref, #start_bit_index) },
+            ))
+        }
+        // the value of a field with a custom converter is not a bitpiece, so it is converted using the functions of the
+        // converter module.
+        FieldRepr::Codec(FieldCodec { module, .. }) => {
+            let len = get_field_len(type_ident, field);
+            let bits_type = field_bits_type(field);
+            Some((
+                quote! { ::bitpiece::CodecMutRef<#lifetime, #ty, #bits_type> },
+                quote! {
+                    ::bitpiece::CodecMutRef::new(
+                        #storage_mut_ref,
+                        #start_bit_index,
+                        #len,
+                        #module::encode,
+                        #module::decode,
+                    )
+                },
+            ))
+        }
+        FieldRepr::NarrowedInt(_) => None,
+    }
+}
@@ -0,0 +1,129 @@
+use quote::quote;
+
+use crate::newtypes::StorageTypeExpr;
+
+/// generates `Serialize` and `Deserialize` implementations which represent the type using its raw storage bits.
+///
+/// deserialization goes through `try_from_bits`, so invalid bit patterns are reported as deserialization errors.
+pub fn gen_serde_storage_impl(
+    type_ident: &syn::Ident,
+    storage_type: &StorageTypeExpr,
+) -> proc_macro2::TokenStream {
+    let expected = format!("a valid bit pattern of `{}`", type_ident);
+    quote! {
+        #[automatically_derived]
+        impl ::bitpiece::serde::Serialize for #type_ident {
+            fn serialize<S: ::bitpiece::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                ::bitpiece::serde::Serialize::serialize(&self.to_bits(), serializer)
+            }
+        }
+        #[automatically_derived]
+        impl<'de> ::bitpiece::serde::Deserialize<'de> for #type_ident {
+            fn deserialize<D: ::bitpiece::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let bits = <#storage_type as ::bitpiece::serde::Deserialize>::deserialize(deserializer)?;
+                Self::try_from_bits(bits).ok_or_else(|| {
+                    <D::Error as ::bitpiece::serde::de::Error>::invalid_value(
+                        ::bitpiece::serde::de::Unexpected::Unsigned(bits as u64),
+                        &#expected,
+                    )
+                })
+            }
+        }
+    }
+}
+
+/// generates `Serialize` and `Deserialize` implementations for a named struct, which represent it as a map of its
+/// field values.
+///
+/// the implementations go through a hidden mirror struct which derives the serde traits. each field is deserialized
+/// using its own type, so the values of all fields are validated, and then the invariants of the struct itself are
+/// checked.
+pub fn gen_serde_struct_impl<'a>(
+    type_ident: &syn::Ident,
+    fields: impl Iterator<Item = &'a syn::Field> + Clone,
+    field_values: impl Iterator<Item = proc_macro2::TokenStream>,
+    from_repr_code: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let type_name = type_ident.to_string();
+    let invalid_value_msg = format!("invalid value of `{}`", type_ident);
+    let repr_fields = fields.clone().map(|field| {
+        let ident = &field.ident;
+        let ty = &field.ty;
+        quote! { #ident: #ty, }
+    });
+    let repr_initializers = fields.zip(field_values).map(|(field, value)| {
+        let ident = &field.ident;
+        quote! { #ident: #value, }
+    });
+    quote! {
+        const _: () = {
+            #[derive(::bitpiece::serde::Serialize, ::bitpiece::serde::Deserialize)]
+            #[serde(crate = "::bitpiece::serde", rename = #type_name)]
+            struct Repr {
+                #(#repr_fields)*
+            }
+
+            #[automatically_derived]
+            impl ::bitpiece::serde::Serialize for #type_ident {
+                fn serialize<S: ::bitpiece::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    let repr = Repr {
+                        #(#repr_initializers)*
+                    };
+                    ::bitpiece::serde::Serialize::serialize(&repr, serializer)
+                }
+            }
+            #[automatically_derived]
+            impl<'de> ::bitpiece::serde::Deserialize<'de> for #type_ident {
+                fn deserialize<D: ::bitpiece::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let repr = <Repr as ::bitpiece::serde::Deserialize>::deserialize(deserializer)?;
+                    (#from_repr_code).ok_or_else(|| {
+                        <D::Error as ::bitpiece::serde::de::Error>::custom(#invalid_value_msg)
+                    })
+                }
+            }
+        };
+    }
+}
+
+/// generates `Serialize` and `Deserialize` implementations for an enum, which represent it using its variant names.
+///
+/// the implementations go through a hidden mirror enum without discriminants which derives the serde traits.
+pub fn gen_serde_enum_impl(
+    type_ident: &syn::Ident,
+    data_enum: &syn::DataEnum,
+) -> proc_macro2::TokenStream {
+    let type_name = type_ident.to_string();
+    let variant_idents = data_enum
+        .variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect::<Vec<_>>();
+    quote! {
+        const _: () = {
+            #[derive(::bitpiece::serde::Serialize, ::bitpiece::serde::Deserialize)]
+            #[serde(crate = "::bitpiece::serde", rename = #type_name)]
+            enum Repr {
+                #(#variant_idents,)*
+            }
+
+            #[automatically_derived]
+            impl ::bitpiece::serde::Serialize for #type_ident {
+                fn serialize<S: ::bitpiece::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    let repr = match self {
+                        #(#type_ident::#variant_idents => Repr::#variant_idents,)*
+                    };
+                    ::bitpiece::serde::Serialize::serialize(&repr, serializer)
+                }
+            }
+            #[automatically_derived]
+            impl<'de> ::bitpiece::serde::Deserialize<'de> for #type_ident {
+                fn deserialize<D: ::bitpiece::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let repr = <Repr as ::bitpiece::serde::Deserialize>::deserialize(deserializer)?;
+                    Ok(match repr {
+                        #(Repr::#variant_idents => #type_ident::#variant_idents,)*
+                    })
+                }
+            }
+        };
+    }
+}
@@ -3,6 +3,7 @@ use syn::Generics;
 
 use crate::{
     newtypes::{BitLenExpr, StorageTypeExpr, TypeExpr},
+    serde_impls::gen_serde_storage_impl,
     MacroArgs, OptIn,
 };
 
@@ -32,8 +33,6 @@ pub fn gen_explicit_bit_length_assertion(
         Some(explicit_bit_length) => quote! {
             const _: () = if (#explicit_bit_length) != (#actual_bit_length) {
                 panic!("explicit bit length does not match actual bit length")
-            } else {
-                ()
             };
         },
         None => quote! {},
@@ -68,9 +67,13 @@ pub struct BitPieceGenImplParams<'a> {
     /// this will be used as the body of the `to_fields` method.
     pub to_fields_code: proc_macro2::TokenStream,
 
-    /// code for constructing this type from its field values.
-    /// this will be used as the body of the `from_fields` method.
-    pub from_fields_code: proc_macro2::TokenStream,
+    /// code for constructing this type from its field values, returning `None` if they don't form a valid value.
+    /// this will be used as the body of the `try_from_fields` method.
+    pub try_from_fields_code: proc_macro2::TokenStream,
+
+    /// the body of the `call_setters` method, which calls every generated setter of this type, or `None` if this type
+    /// has no setters, in which case the default implementation is used.
+    pub call_setters_code: Option<proc_macro2::TokenStream>,
 
     /// code converting this type to its raw bits.
     /// this will be used as the body of the `to_bits` method.
@@ -80,6 +83,10 @@ pub struct BitPieceGenImplParams<'a> {
     /// this will be used as the body of the `try_from_bits` method.
     pub try_from_bits_code: proc_macro2::TokenStream,
 
+    /// an array of the bit lengths of the fields of this type, starting from the least significant field.
+    /// this is used for grouping the digits when formatting this type in binary.
+    pub field_lens: proc_macro2::TokenStream,
+
     /// an instantiation of this type with all bits sets to zero (if possible).
     pub zeroes: proc_macro2::TokenStream,
 
@@ -104,9 +111,11 @@ pub fn bitpiece_gen_impl<'a>(params: BitPieceGenImplParams<'a>) -> proc_macro2::
         fields_type,
         storage_type,
         to_fields_code,
-        from_fields_code,
+        try_from_fields_code,
+        call_setters_code,
         to_bits_code,
         try_from_bits_code,
+        field_lens,
         zeroes,
         ones,
         min,
@@ -155,6 +164,17 @@ pub fn bitpiece_gen_impl<'a>(params: BitPieceGenImplParams<'a>) -> proc_macro2::
             ::bitpiece::bitpiece_define_mut_ref_type! { #type_ident, #mut_type_ident, #type_vis }
         },
     );
+    let call_setters_fn = call_setters_code.map(|call_setters_code| {
+        quote! {
+            fn call_setters(
+                value: Self,
+                source: Self,
+                f: &mut dyn FnMut(::bitpiece::testing::SetterCall<Self>),
+            ) {
+                #call_setters_code
+            }
+        }
+    });
     let opt_fields_struct_code = macro_args.filter_opt_in_code(
         OptIn::FieldsStruct,
         quote! {
@@ -167,10 +187,14 @@ pub fn bitpiece_gen_impl<'a>(params: BitPieceGenImplParams<'a>) -> proc_macro2::
                 fn to_fields(self) -> Self::Fields {
                     self.to_fields()
                 }
+                #call_setters_fn
             }
             impl #type_ident {
+                pub const fn try_from_fields(fields: #fields_type) -> Option<Self> {
+                    #try_from_fields_code
+                }
                 pub const fn from_fields(fields: #fields_type) -> Self {
-                    #from_fields_code
+                    Self::try_from_fields(fields).unwrap()
                 }
                 pub const fn to_fields(self) -> #fields_type {
                     #to_fields_code
@@ -188,10 +212,45 @@ pub fn bitpiece_gen_impl<'a>(params: BitPieceGenImplParams<'a>) -> proc_macro2::
             }
         },
     );
+    let opt_fmt_code = macro_args.filter_opt_in_code(
+        OptIn::Fmt,
+        quote! {
+            #[automatically_derived]
+            impl ::core::fmt::Binary for #type_ident {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    ::bitpiece::fmt_binary_grouped(self.to_bits() as u64, &#field_lens, f)
+                }
+            }
+            #[automatically_derived]
+            impl ::core::fmt::Octal for #type_ident {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    ::bitpiece::fmt_octal(self.to_bits() as u64, #bit_len, f)
+                }
+            }
+            #[automatically_derived]
+            impl ::core::fmt::LowerHex for #type_ident {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    ::bitpiece::fmt_lower_hex(self.to_bits() as u64, #bit_len, f)
+                }
+            }
+            #[automatically_derived]
+            impl ::core::fmt::UpperHex for #type_ident {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    ::bitpiece::fmt_upper_hex(self.to_bits() as u64, #bit_len, f)
+                }
+            }
+        },
+    );
+    let opt_serde_storage_code = macro_args.filter_opt_in_code(
+        OptIn::SerdeStorage,
+        gen_serde_storage_impl(type_ident, storage_type),
+    );
     quote! {
         #base_code
         #opt_mut_struct_code
         #opt_fields_struct_code
         #opt_const_eq_code
+        #opt_fmt_code
+        #opt_serde_storage_code
     }
 }
@@ -0,0 +1,266 @@
+use crate::*;
+
+/// an integer bitpiece type, which provides trait-level access to its numeric value.
+///
+/// this is implemented for the `B*` and `SB*` types, and for the primitive integer types. whether the type is signed is
+/// given by [`BitPieceHasLayout::IS_SIGNED`].
+///
+/// it also provides the constructors and the arithmetic methods of the `B*` and `SB*` types, so that they can be used by
+/// code which is generic over the bit length, for example using [`UBits<N>`]. the `B*` and `SB*` types also implement
+/// these methods as inherent const functions, which take precedence over the methods of this trait.
+pub trait BitInt: BitPieceHasLayout {
+    /// the primitive integer type used to represent the value of this type, for example [`u8`] for `B5`.
+    type Primitive: Copy + Into<i128>;
+
+    /// the min value of this type, as a primitive integer.
+    const MIN_VALUE: Self::Primitive;
+
+    /// the max value of this type, as a primitive integer.
+    const MAX_VALUE: Self::Primitive;
+
+    /// returns the value as an `i128`, which can represent the value of any integer bitpiece type.
+    fn to_i128(self) -> i128;
+
+    /// returns the value as a `u64`, with the same semantics as an `as` cast. negative values are sign extended.
+    ///
+    /// this is named differently from [`BitStorage::to_u64`], since the primitive integer types implement both traits.
+    fn as_u64_bits(self) -> u64;
+
+    /// converts the given value to this type, returning `None` if it is out of the range of this type.
+    fn try_from_i128(value: i128) -> Option<Self>;
+
+    /// converts the given value to this type, clamping it to the range of this type.
+    fn saturating_from_i128(value: i128) -> Self {
+        let clamped = value.clamp(Self::MIN_VALUE.into(), Self::MAX_VALUE.into());
+        Self::try_from_i128(clamped).unwrap()
+    }
+
+    /// converts the given value to this type, keeping only the low bits which fit within the bit length of this type.
+    fn wrapping_from_i128(value: i128) -> Self {
+        let bits = extract_bits(value as u64, 0, Self::BITS);
+        Self::from_bits(Self::Bits::from_u64(bits).unwrap())
+    }
+
+    /// returns the inner value.
+    fn get(&self) -> Self::Primitive;
+
+    /// creates a new instance of this type with the given value.
+    ///
+    /// this function panics if the value does not fit within the bit length of this type.
+    fn new(value: Self::Primitive) -> Self {
+        Self::try_new(value).unwrap()
+    }
+
+    /// creates a new instance of this type with the given value.
+    ///
+    /// if the value does not fit within the bit length of this type, returns `None`.
+    fn try_new(value: Self::Primitive) -> Option<Self> {
+        Self::try_from_i128(value.into())
+    }
+
+    /// creates a new instance of this type with the given value, truncated to the bit length of this type.
+    fn wrapping_new(value: Self::Primitive) -> Self {
+        Self::wrapping_from_i128(value.into())
+    }
+
+    /// creates a new instance of this type with the given value, clamped to the range of this type.
+    fn saturating_new(value: Self::Primitive) -> Self {
+        Self::saturating_from_i128(value.into())
+    }
+
+    /// wrapping addition, wrapping around at the bit length of this type.
+    fn wrapping_add(self, rhs: Self) -> Self {
+        Self::wrapping_from_i128(self.to_i128() + rhs.to_i128())
+    }
+
+    /// wrapping subtraction, wrapping around at the bit length of this type.
+    fn wrapping_sub(self, rhs: Self) -> Self {
+        Self::wrapping_from_i128(self.to_i128() - rhs.to_i128())
+    }
+
+    /// wrapping multiplication, wrapping around at the bit length of this type.
+    fn wrapping_mul(self, rhs: Self) -> Self {
+        Self::wrapping_from_i128(self.to_i128().wrapping_mul(rhs.to_i128()))
+    }
+
+    /// wrapping negation, wrapping around at the bit length of this type.
+    fn wrapping_neg(self) -> Self {
+        Self::wrapping_from_i128(-self.to_i128())
+    }
+
+    /// left shift, where the shift amount is taken modulo the bit length of this type.
+    fn wrapping_shl(self, rhs: u32) -> Self {
+        Self::wrapping_from_i128(self.to_i128() << (rhs % Self::BITS as u32))
+    }
+
+    /// right shift, where the shift amount is taken modulo the bit length of this type. for signed types, this is an
+    /// arithmetic shift.
+    fn wrapping_shr(self, rhs: u32) -> Self {
+        Self::wrapping_from_i128(self.to_i128() >> (rhs % Self::BITS as u32))
+    }
+
+    /// addition, returning the wrapped result along with whether an overflow occurred.
+    fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        (self.wrapping_add(rhs), self.checked_add(rhs).is_none())
+    }
+
+    /// subtraction, returning the wrapped result along with whether an overflow occurred.
+    fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        (self.wrapping_sub(rhs), self.checked_sub(rhs).is_none())
+    }
+
+    /// multiplication, returning the wrapped result along with whether an overflow occurred.
+    fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+        (self.wrapping_mul(rhs), self.checked_mul(rhs).is_none())
+    }
+
+    /// negation, returning the wrapped result along with whether an overflow occurred.
+    fn overflowing_neg(self) -> (Self, bool) {
+        (self.wrapping_neg(), self.checked_neg().is_none())
+    }
+
+    /// checked addition, returning `None` if the result does not fit within the bit length of this type.
+    fn checked_add(self, rhs: Self) -> Option<Self> {
+        Self::try_from_i128(self.to_i128() + rhs.to_i128())
+    }
+
+    /// checked subtraction, returning `None` if the result does not fit within the bit length of this type.
+    fn checked_sub(self, rhs: Self) -> Option<Self> {
+        Self::try_from_i128(self.to_i128() - rhs.to_i128())
+    }
+
+    /// checked multiplication, returning `None` if the result does not fit within the bit length of this type.
+    fn checked_mul(self, rhs: Self) -> Option<Self> {
+        self.to_i128()
+            .checked_mul(rhs.to_i128())
+            .and_then(Self::try_from_i128)
+    }
+
+    /// checked negation, returning `None` if the result does not fit within the bit length of this type.
+    fn checked_neg(self) -> Option<Self> {
+        Self::try_from_i128(-self.to_i128())
+    }
+
+    /// checked left shift, returning `None` if the shift amount is not smaller than the bit length of this type.
+    fn checked_shl(self, rhs: u32) -> Option<Self> {
+        if (rhs as usize) < Self::BITS {
+            Some(self.wrapping_shl(rhs))
+        } else {
+            None
+        }
+    }
+
+    /// checked right shift, returning `None` if the shift amount is not smaller than the bit length of this type.
+    fn checked_shr(self, rhs: u32) -> Option<Self> {
+        if (rhs as usize) < Self::BITS {
+            Some(self.wrapping_shr(rhs))
+        } else {
+            None
+        }
+    }
+
+    /// saturating addition, clamping the result to the range of this type.
+    fn saturating_add(self, rhs: Self) -> Self {
+        Self::saturating_from_i128(self.to_i128() + rhs.to_i128())
+    }
+
+    /// saturating subtraction, clamping the result to the range of this type.
+    fn saturating_sub(self, rhs: Self) -> Self {
+        Self::saturating_from_i128(self.to_i128() - rhs.to_i128())
+    }
+
+    /// saturating multiplication, clamping the result to the range of this type.
+    fn saturating_mul(self, rhs: Self) -> Self {
+        Self::saturating_from_i128(self.to_i128().saturating_mul(rhs.to_i128()))
+    }
+
+    /// saturating negation, clamping the result to the range of this type.
+    fn saturating_neg(self) -> Self {
+        Self::saturating_from_i128(-self.to_i128())
+    }
+}
+
+/// implements `num-traits` traits for a generic bitpiece integer type, using its `BitInt` implementation.
+#[cfg(feature = "num-traits")]
+macro_rules! impl_num_traits {
+    { $ty: ident } => {
+        impl<const N: usize> num_traits::Bounded for $ty<N>
+        where
+            BitLength<N>: AssociatedStorage,
+        {
+            fn min_value() -> Self {
+                <Self as BitPiece>::MIN
+            }
+            fn max_value() -> Self {
+                <Self as BitPiece>::MAX
+            }
+        }
+        impl<const N: usize> num_traits::ToPrimitive for $ty<N>
+        where
+            BitLength<N>: AssociatedStorage,
+        {
+            fn to_i64(&self) -> Option<i64> {
+                i64::try_from(BitInt::to_i128(*self)).ok()
+            }
+            fn to_u64(&self) -> Option<u64> {
+                u64::try_from(BitInt::to_i128(*self)).ok()
+            }
+            fn to_i128(&self) -> Option<i128> {
+                Some(BitInt::to_i128(*self))
+            }
+        }
+        impl<const N: usize> num_traits::FromPrimitive for $ty<N>
+        where
+            BitLength<N>: AssociatedStorage,
+        {
+            fn from_i64(n: i64) -> Option<Self> {
+                Self::try_from_i128(n as i128)
+            }
+            fn from_u64(n: u64) -> Option<Self> {
+                Self::try_from_i128(n as i128)
+            }
+            fn from_i128(n: i128) -> Option<Self> {
+                Self::try_from_i128(n)
+            }
+        }
+        impl<const N: usize> num_traits::Zero for $ty<N>
+        where
+            BitLength<N>: AssociatedStorage,
+        {
+            fn zero() -> Self {
+                <Self as BitPiece>::ZEROES
+            }
+            fn is_zero(&self) -> bool {
+                BitInt::to_i128(*self) == 0
+            }
+        }
+        impl<const N: usize> num_traits::CheckedAdd for $ty<N>
+        where
+            BitLength<N>: AssociatedStorage,
+        {
+            fn checked_add(&self, v: &Self) -> Option<Self> {
+                Self::try_from_i128(BitInt::to_i128(*self) + BitInt::to_i128(*v))
+            }
+        }
+        impl<const N: usize> num_traits::CheckedSub for $ty<N>
+        where
+            BitLength<N>: AssociatedStorage,
+        {
+            fn checked_sub(&self, v: &Self) -> Option<Self> {
+                Self::try_from_i128(BitInt::to_i128(*self) - BitInt::to_i128(*v))
+            }
+        }
+        impl<const N: usize> num_traits::CheckedMul for $ty<N>
+        where
+            BitLength<N>: AssociatedStorage,
+        {
+            fn checked_mul(&self, v: &Self) -> Option<Self> {
+                Self::try_from_i128(BitInt::to_i128(*self) * BitInt::to_i128(*v))
+            }
+        }
+    };
+}
+#[cfg(feature = "num-traits")]
+impl_num_traits! { UBits }
+#[cfg(feature = "num-traits")]
+impl_num_traits! { SBits }
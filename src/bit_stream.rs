@@ -0,0 +1,331 @@
+use crate::*;
+
+/// the order in which the bits of a bit stream are packed into its bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BitOrder {
+    /// the first bit of the stream is the most significant bit of the first byte, and multi-bit values are stored with
+    /// their most significant bit first. this is the order used by most media codecs (e.g. h.264).
+    MsbFirst,
+
+    /// the first bit of the stream is the least significant bit of the first byte, and multi-bit values are stored with
+    /// their least significant bit first. this is the order used by deflate.
+    LsbFirst,
+}
+
+/// an error which occurred while reading from or writing to a bit stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BitStreamError {
+    /// there are not enough bits left in the stream.
+    EndOfStream,
+
+    /// the bits read from the stream do not represent a valid value of the requested type, or the value to be written
+    /// can't be represented by its encoding.
+    InvalidValue,
+}
+impl core::fmt::Display for BitStreamError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            BitStreamError::EndOfStream => write!(f, "unexpected end of bit stream"),
+            BitStreamError::InvalidValue => write!(f, "invalid value in bit stream"),
+        }
+    }
+}
+
+/// returns a mask of the `len` lowest bits of a byte.
+#[inline(always)]
+const fn byte_mask(len: usize) -> u8 {
+    (0xffu16 >> (8 - len)) as u8
+}
+
+/// returns the total amount of bits in a byte slice.
+#[inline(always)]
+const fn bit_len_of(data: &[u8]) -> usize {
+    data.len() * 8
+}
+
+/// a reader which reads a sequence of bit fields of arbitrary widths from a byte slice.
+///
+/// the fields do not need to be aligned to any storage word, each field starts at the bit right after the end of the
+/// previous one.
+#[derive(Debug, Clone)]
+pub struct BitReader<'a> {
+    data: &'a [u8],
+    bit_order: BitOrder,
+    position: usize,
+}
+impl<'a> BitReader<'a> {
+    /// creates a new reader which reads from the start of the given data.
+    pub const fn new(data: &'a [u8], bit_order: BitOrder) -> Self {
+        Self {
+            data,
+            bit_order,
+            position: 0,
+        }
+    }
+
+    /// returns the bit order of this reader.
+    pub const fn bit_order(&self) -> BitOrder {
+        self.bit_order
+    }
+
+    /// returns the current position of this reader, in bits, relative to the start of the data.
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// returns the amount of bits left to be read.
+    pub const fn remaining(&self) -> usize {
+        bit_len_of(self.data) - self.position
+    }
+
+    /// returns whether the current position is aligned to a byte boundary.
+    pub const fn is_byte_aligned(&self) -> bool {
+        self.position.is_multiple_of(8)
+    }
+
+    /// skips the remaining bits of the current byte, if any, so that the next read starts at a byte boundary.
+    pub const fn align_to_byte(&mut self) {
+        self.position = self.position.next_multiple_of(8);
+    }
+
+    /// skips the given amount of bits.
+    pub const fn skip_bits(&mut self, len: usize) -> Result<(), BitStreamError> {
+        if len > self.remaining() {
+            return Err(BitStreamError::EndOfStream);
+        }
+        self.position += len;
+        Ok(())
+    }
+
+    /// reads `len` bits from the stream and returns them as an integer.
+    ///
+    /// if there are not enough bits left in the stream, returns an error and leaves the position unchanged.
+    ///
+    /// this function panics if `len` is larger than 64.
+    pub const fn read_bits(&mut self, len: usize) -> Result<u64, BitStreamError> {
+        assert!(len <= 64, "can't read more than 64 bits at once");
+        if len > self.remaining() {
+            return Err(BitStreamError::EndOfStream);
+        }
+
+        let mut value = 0u64;
+        let mut bits_read = 0;
+        while bits_read < len {
+            let byte = self.data[self.position / 8];
+            let bit_offset_in_byte = self.position % 8;
+            let bits_left_in_byte = 8 - bit_offset_in_byte;
+            let chunk_len = if len - bits_read < bits_left_in_byte {
+                len - bits_read
+            } else {
+                bits_left_in_byte
+            };
+            match self.bit_order {
+                BitOrder::MsbFirst => {
+                    let chunk = (byte >> (bits_left_in_byte - chunk_len)) & byte_mask(chunk_len);
+                    value = (value << chunk_len) | chunk as u64;
+                }
+                BitOrder::LsbFirst => {
+                    let chunk = (byte >> bit_offset_in_byte) & byte_mask(chunk_len);
+                    value |= (chunk as u64) << bits_read;
+                }
+            }
+            bits_read += chunk_len;
+            self.position += chunk_len;
+        }
+        Ok(value)
+    }
+
+    /// reads a single bit from the stream.
+    pub const fn read_bit(&mut self) -> Result<bool, BitStreamError> {
+        match self.read_bits(1) {
+            Ok(bit) => Ok(bit != 0),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// reads a value from the stream.
+    ///
+    /// bitpieces are read using exactly `T::BITS` bits, and are validated using `T::try_from_bits`. other encodings
+    /// (for example [`Leb128`]) use a variable amount of bits.
+    ///
+    /// if there are not enough bits left in the stream, or if the bits do not represent a valid value of `T`, returns an
+    /// error and leaves the position unchanged.
+    pub fn read<T: BitStreamEncode>(&mut self) -> Result<T, BitStreamError> {
+        let start_position = self.position;
+        let result = T::read_from(self);
+        if result.is_err() {
+            self.position = start_position;
+        }
+        result
+    }
+}
+
+/// a writer which writes a sequence of bit fields of arbitrary widths into a byte slice.
+///
+/// the fields do not need to be aligned to any storage word, each field starts at the bit right after the end of the
+/// previous one.
+#[derive(Debug)]
+pub struct BitWriter<'a> {
+    data: &'a mut [u8],
+    bit_order: BitOrder,
+    position: usize,
+
+    /// whether writes should only advance the position without modifying the buffer. this is used by
+    /// [`BitWriter::write`] to check that a value can be written before modifying any bits of the buffer.
+    dry_run: bool,
+}
+impl<'a> BitWriter<'a> {
+    /// creates a new writer which writes to the start of the given buffer.
+    ///
+    /// bits of the buffer which are not written to are left untouched.
+    pub const fn new(data: &'a mut [u8], bit_order: BitOrder) -> Self {
+        Self {
+            data,
+            bit_order,
+            position: 0,
+            dry_run: false,
+        }
+    }
+
+    /// returns the bit order of this writer.
+    pub const fn bit_order(&self) -> BitOrder {
+        self.bit_order
+    }
+
+    /// returns the current position of this writer, in bits, relative to the start of the buffer.
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// returns the amount of bits left in the buffer.
+    pub const fn remaining(&self) -> usize {
+        bit_len_of(self.data) - self.position
+    }
+
+    /// returns whether the current position is aligned to a byte boundary.
+    pub const fn is_byte_aligned(&self) -> bool {
+        self.position.is_multiple_of(8)
+    }
+
+    /// returns the prefix of the buffer which contains all bits written so far, including a partially written last byte.
+    pub fn written(&self) -> &[u8] {
+        &self.data[..self.position.div_ceil(8)]
+    }
+
+    /// pads the current byte with zero bits, if needed, so that the next write starts at a byte boundary.
+    pub const fn align_to_byte(&mut self) {
+        let padding_len = self.position.next_multiple_of(8) - self.position;
+        // the padding always fits, since the buffer length is a whole amount of bytes.
+        if self.write_bits(0, padding_len).is_err() {
+            unreachable!()
+        }
+    }
+
+    /// writes the `len` lowest bits of `value` to the stream. the bits of `value` above `len` are ignored.
+    ///
+    /// if there is not enough space left in the buffer, returns an error and leaves the position unchanged.
+    ///
+    /// this function panics if `len` is larger than 64.
+    pub const fn write_bits(&mut self, value: u64, len: usize) -> Result<(), BitStreamError> {
+        assert!(len <= 64, "can't write more than 64 bits at once");
+        if len > self.remaining() {
+            return Err(BitStreamError::EndOfStream);
+        }
+        if self.dry_run {
+            self.position += len;
+            return Ok(());
+        }
+
+        let mut bits_written = 0;
+        while bits_written < len {
+            let byte_index = self.position / 8;
+            let bit_offset_in_byte = self.position % 8;
+            let bits_left_in_byte = 8 - bit_offset_in_byte;
+            let chunk_len = if len - bits_written < bits_left_in_byte {
+                len - bits_written
+            } else {
+                bits_left_in_byte
+            };
+            let (chunk, shift) = match self.bit_order {
+                BitOrder::MsbFirst => (
+                    extract_bits(value, len - bits_written - chunk_len, chunk_len) as u8,
+                    bits_left_in_byte - chunk_len,
+                ),
+                BitOrder::LsbFirst => (
+                    extract_bits(value, bits_written, chunk_len) as u8,
+                    bit_offset_in_byte,
+                ),
+            };
+            let mask = byte_mask(chunk_len) << shift;
+            self.data[byte_index] = (self.data[byte_index] & !mask) | (chunk << shift);
+            bits_written += chunk_len;
+            self.position += chunk_len;
+        }
+        Ok(())
+    }
+
+    /// writes a single bit to the stream.
+    pub const fn write_bit(&mut self, bit: bool) -> Result<(), BitStreamError> {
+        self.write_bits(bit as u64, 1)
+    }
+
+    /// writes a value to the stream.
+    ///
+    /// bitpieces are written using exactly `T::BITS` bits. other encodings (for example [`Leb128`]) use a variable amount
+    /// of bits.
+    ///
+    /// if there is not enough space left in the buffer, or if the value can't be encoded, returns an error and leaves both
+    /// the position and the contents of the buffer unchanged.
+    pub fn write<T: BitStreamEncode>(&mut self, value: T) -> Result<(), BitStreamError> {
+        let start_position = self.position;
+        if self.dry_run {
+            // we are nested inside the dry run of an outer write, which restores the position when it is done.
+            return value.write_to(self);
+        }
+
+        // variable-length encodings may fail after writing some of their bits, so first do a dry run which only advances
+        // the position, and only write the value to the buffer if the dry run succeeds.
+        self.dry_run = true;
+        let dry_run_result = value.write_to(self);
+        self.dry_run = false;
+        self.position = start_position;
+        dry_run_result?;
+
+        let result = value.write_to(self);
+        if result.is_err() {
+            self.position = start_position;
+        }
+        result
+    }
+}
+
+/// a type which can be read from and written to a bit stream.
+///
+/// this is implemented for all bitpieces, which are encoded using exactly `BITS` bits, and for variable-length
+/// encodings such as [`Leb128`] or [`ExpGolomb`], so that they can be mixed freely in the same stream.
+pub trait BitStreamEncode: Sized {
+    /// reads a value from the given reader.
+    ///
+    /// there is no need to restore the position of the reader on failure, this is handled by [`BitReader::read`].
+    fn read_from(reader: &mut BitReader<'_>) -> Result<Self, BitStreamError>;
+
+    /// writes this value to the given writer.
+    ///
+    /// there is no need to restore the position of the writer on failure, this is handled by [`BitWriter::write`]. this
+    /// may be called more than once for the same value, so it must write the same bits every time.
+    fn write_to(&self, writer: &mut BitWriter<'_>) -> Result<(), BitStreamError>;
+}
+
+impl<T: BitPiece> BitStreamEncode for T {
+    fn read_from(reader: &mut BitReader<'_>) -> Result<Self, BitStreamError> {
+        let bits = reader.read_bits(T::BITS)?;
+        T::Bits::from_u64(bits)
+            .ok()
+            .and_then(T::try_from_bits)
+            .ok_or(BitStreamError::InvalidValue)
+    }
+
+    fn write_to(&self, writer: &mut BitWriter<'_>) -> Result<(), BitStreamError> {
+        writer.write_bits(self.to_bits().to_u64(), T::BITS)
+    }
+}
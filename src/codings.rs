@@ -0,0 +1,223 @@
+use crate::*;
+
+/// an unsigned integer encoded using the unsigned LEB128 variable-length encoding.
+///
+/// the value is split into groups of 7 bits, starting from the least significant group. each group is written as an
+/// 8-bit chunk, where the most significant bit of the chunk indicates whether more groups follow.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Leb128(pub u64);
+impl BitStreamEncode for Leb128 {
+    fn read_from(reader: &mut BitReader<'_>) -> Result<Self, BitStreamError> {
+        let mut value = 0u64;
+        let mut shift = 0;
+        loop {
+            let chunk = reader.read_bits(8)?;
+            let group = chunk & 0x7f;
+            // the last group may only contain the single remaining bit of the value.
+            if shift > 63 || (shift == 63 && group > 1) {
+                return Err(BitStreamError::InvalidValue);
+            }
+            value |= group << shift;
+            if chunk & 0x80 == 0 {
+                return Ok(Self(value));
+            }
+            shift += 7;
+        }
+    }
+
+    fn write_to(&self, writer: &mut BitWriter<'_>) -> Result<(), BitStreamError> {
+        let mut value = self.0;
+        loop {
+            let group = value & 0x7f;
+            value >>= 7;
+            if value == 0 {
+                return writer.write_bits(group, 8);
+            }
+            writer.write_bits(group | 0x80, 8)?;
+        }
+    }
+}
+
+/// a signed integer encoded using the signed LEB128 variable-length encoding.
+///
+/// this is similar to [`Leb128`], but the value is stored in two's complement, and the last group is sign extended.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SLeb128(pub SB64);
+impl BitStreamEncode for SLeb128 {
+    fn read_from(reader: &mut BitReader<'_>) -> Result<Self, BitStreamError> {
+        let mut value = 0u64;
+        let mut shift = 0;
+        loop {
+            let chunk = reader.read_bits(8)?;
+            let group = chunk & 0x7f;
+            // the last group may only contain the sign extension of the remaining bit of the value.
+            if shift > 63 || (shift == 63 && group != 0 && group != 0x7f) {
+                return Err(BitStreamError::InvalidValue);
+            }
+            value |= group << shift;
+            shift += 7;
+            if chunk & 0x80 == 0 {
+                return Ok(Self(SB64::new(sign_extend_bits(value, shift.min(64)))));
+            }
+        }
+    }
+
+    fn write_to(&self, writer: &mut BitWriter<'_>) -> Result<(), BitStreamError> {
+        let mut value = self.0.get();
+        loop {
+            let group = (value & 0x7f) as u64;
+            value >>= 7;
+            let sign_bit_of_group = group & 0x40 != 0;
+            if (value == 0 && !sign_bit_of_group) || (value == -1 && sign_bit_of_group) {
+                return writer.write_bits(group, 8);
+            }
+            writer.write_bits(group | 0x80, 8)?;
+        }
+    }
+}
+
+/// writes an elias gamma style prefix code of the given non-zero value: `n` zero bits, followed by a one bit, followed by
+/// the `n` low bits of the value, where `n` is the index of the most significant set bit of the value.
+fn write_gamma_code(writer: &mut BitWriter<'_>, value: u128) -> Result<(), BitStreamError> {
+    let n = (127 - value.leading_zeros()) as usize;
+    // `n` can be at most 64, so the zero prefix fits in a single write.
+    writer.write_bits(0, n)?;
+    writer.write_bit(true)?;
+    writer.write_bits(value as u64, n)
+}
+
+/// reads an elias gamma style prefix code, as written by [`write_gamma_code`]. the decoded value must have at most
+/// `max_len` bits.
+fn read_gamma_code(reader: &mut BitReader<'_>, max_len: usize) -> Result<u128, BitStreamError> {
+    let mut n = 0;
+    while !reader.read_bit()? {
+        n += 1;
+        if n >= max_len {
+            return Err(BitStreamError::InvalidValue);
+        }
+    }
+    let low_bits = reader.read_bits(n)?;
+    Ok((1u128 << n) | low_bits as u128)
+}
+
+/// an unsigned integer encoded using the unsigned exponential-golomb code, as used by h.264 (`ue(v)`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ExpGolomb(pub u64);
+impl BitStreamEncode for ExpGolomb {
+    fn read_from(reader: &mut BitReader<'_>) -> Result<Self, BitStreamError> {
+        let code_num = read_gamma_code(reader, 65)? - 1;
+        u64::try_from(code_num)
+            .map(Self)
+            .map_err(|_| BitStreamError::InvalidValue)
+    }
+
+    fn write_to(&self, writer: &mut BitWriter<'_>) -> Result<(), BitStreamError> {
+        write_gamma_code(writer, self.0 as u128 + 1)
+    }
+}
+
+/// a signed integer encoded using the signed exponential-golomb code, as used by h.264 (`se(v)`).
+///
+/// the value is mapped to an unsigned code number, such that positive values `k` are mapped to `2k - 1` and non-positive
+/// values `k` are mapped to `-2k`, and the code number is then encoded like [`ExpGolomb`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SignedExpGolomb(pub SB64);
+impl BitStreamEncode for SignedExpGolomb {
+    fn read_from(reader: &mut BitReader<'_>) -> Result<Self, BitStreamError> {
+        let code_num = (read_gamma_code(reader, 65)? - 1) as i128;
+        let value = if code_num % 2 == 1 {
+            (code_num + 1) / 2
+        } else {
+            -(code_num / 2)
+        };
+        SB64::try_from_i128(value)
+            .map(Self)
+            .ok_or(BitStreamError::InvalidValue)
+    }
+
+    fn write_to(&self, writer: &mut BitWriter<'_>) -> Result<(), BitStreamError> {
+        let value = self.0.to_i128();
+        let code_num = if value > 0 { 2 * value - 1 } else { -2 * value };
+        write_gamma_code(writer, code_num as u128 + 1)
+    }
+}
+
+/// a positive integer encoded using the elias gamma code.
+///
+/// zero can't be represented by this encoding, so trying to write it fails with [`BitStreamError::InvalidValue`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EliasGamma(pub u64);
+impl BitStreamEncode for EliasGamma {
+    fn read_from(reader: &mut BitReader<'_>) -> Result<Self, BitStreamError> {
+        Ok(Self(read_gamma_code(reader, 64)? as u64))
+    }
+
+    fn write_to(&self, writer: &mut BitWriter<'_>) -> Result<(), BitStreamError> {
+        if self.0 == 0 {
+            return Err(BitStreamError::InvalidValue);
+        }
+        write_gamma_code(writer, self.0 as u128)
+    }
+}
+
+/// a positive integer encoded using the elias delta code.
+///
+/// the amount of significant bits of the value is encoded using the elias gamma code, followed by the bits of the value
+/// without its leading one bit.
+///
+/// zero can't be represented by this encoding, so trying to write it fails with [`BitStreamError::InvalidValue`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EliasDelta(pub u64);
+impl BitStreamEncode for EliasDelta {
+    fn read_from(reader: &mut BitReader<'_>) -> Result<Self, BitStreamError> {
+        let len = read_gamma_code(reader, 7)? as usize;
+        if len > 64 {
+            return Err(BitStreamError::InvalidValue);
+        }
+        let low_bits = reader.read_bits(len - 1)?;
+        Ok(Self((1u128 << (len - 1)) as u64 | low_bits))
+    }
+
+    fn write_to(&self, writer: &mut BitWriter<'_>) -> Result<(), BitStreamError> {
+        if self.0 == 0 {
+            return Err(BitStreamError::InvalidValue);
+        }
+        let len = 64 - self.0.leading_zeros() as usize;
+        write_gamma_code(writer, len as u128)?;
+        writer.write_bits(self.0, len - 1)
+    }
+}
+
+/// a signed integer bitpiece (for example [`SB5`] or [`i16`]) encoded using the zig-zag encoding, using exactly
+/// `T::BITS` bits.
+///
+/// the zig-zag encoding maps values of small magnitude to small unsigned integers, by interleaving the positive and
+/// negative values: `0, -1, 1, -2, 2, ...` are encoded as `0, 1, 2, 3, 4, ...`.
+///
+/// the value is converted from and to `T` using its [`BitInt`] implementation. using an unsigned type (for example
+/// [`B5`]) fails to compile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ZigZag<T>(pub T);
+impl<T: BitInt> ZigZag<T> {
+    const ASSERT_SIGNED: () = assert!(
+        <T as BitPieceHasLayout>::IS_SIGNED,
+        "the zig-zag encoding can only be used with signed types"
+    );
+}
+impl<T: BitInt> BitStreamEncode for ZigZag<T> {
+    fn read_from(reader: &mut BitReader<'_>) -> Result<Self, BitStreamError> {
+        let () = Self::ASSERT_SIGNED;
+        let encoded = reader.read_bits(T::BITS)?;
+        let value = ((encoded >> 1) as i64) ^ -((encoded & 1) as i64);
+        T::try_from_i128(value as i128)
+            .map(Self)
+            .ok_or(BitStreamError::InvalidValue)
+    }
+
+    fn write_to(&self, writer: &mut BitWriter<'_>) -> Result<(), BitStreamError> {
+        let () = Self::ASSERT_SIGNED;
+        let value = self.0.to_i128() as i64;
+        let encoded = ((value << 1) ^ (value >> 63)) as u64;
+        writer.write_bits(encoded, T::BITS)
+    }
+}
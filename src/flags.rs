@@ -0,0 +1,102 @@
+use crate::*;
+
+/// a bitpiece struct whose fields are all `bool` flags, generated using the `flags` opt in.
+pub trait BitPieceFlags: BitPiece + 'static {
+    /// the name and value of each single flag, ordered by bit offset.
+    const FLAGS: &'static [(&'static str, Self)];
+
+    /// returns the flag with the given name, if any. the name is case sensitive, for example `"WRITE"`.
+    fn from_name(name: &str) -> Option<Self> {
+        Self::FLAGS
+            .iter()
+            .find(|(flag_name, _)| *flag_name == name)
+            .map(|(_, flag)| *flag)
+    }
+
+    /// returns an iterator over the single flags which are set in this value.
+    fn iter(self) -> impl Iterator<Item = Self> {
+        self.iter_names().map(|(_, flag)| flag)
+    }
+
+    /// returns an iterator over the names and values of the flags which are set in this value.
+    fn iter_names(self) -> FlagsIter<Self> {
+        FlagsIter {
+            value: self,
+            index: 0,
+        }
+    }
+}
+
+/// an iterator over the names and values of the flags which are set in a value, see [`BitPieceFlags::iter_names`].
+#[derive(Debug, Clone)]
+pub struct FlagsIter<T: BitPieceFlags> {
+    value: T,
+    index: usize,
+}
+impl<T: BitPieceFlags> Iterator for FlagsIter<T> {
+    type Item = (&'static str, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bits = self.value.to_bits().to_u64();
+        while let Some(&(name, flag)) = T::FLAGS.get(self.index) {
+            self.index += 1;
+            let flag_bits = flag.to_bits().to_u64();
+            if bits & flag_bits == flag_bits {
+                return Some((name, flag));
+            }
+        }
+        None
+    }
+}
+
+/// an error which occurred while parsing a set of flags from a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseFlagsError {
+    /// one of the flag names is empty, for example in `"READ | | WRITE"`.
+    EmptyFlag,
+
+    /// one of the flag names is not the name of any flag.
+    UnknownFlag,
+}
+impl core::fmt::Display for ParseFlagsError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseFlagsError::EmptyFlag => write!(f, "encountered empty flag name"),
+            ParseFlagsError::UnknownFlag => write!(f, "unknown flag name"),
+        }
+    }
+}
+
+/// formats the names of the flags which are set in the given value, separated by ` | `, for example `READ | EXEC`. an
+/// empty set of flags is formatted as an empty string.
+pub fn fmt_flags<T: BitPieceFlags>(
+    value: T,
+    f: &mut core::fmt::Formatter<'_>,
+) -> core::fmt::Result {
+    for (i, (name, _)) in value.iter_names().enumerate() {
+        if i != 0 {
+            f.write_str(" | ")?;
+        }
+        f.write_str(name)?;
+    }
+    Ok(())
+}
+
+/// parses a set of flags from their names separated by `|`, as formatted by [`fmt_flags`]. whitespace around the names
+/// is ignored, and an empty (or all whitespace) string is parsed as an empty set of flags.
+pub fn parse_flags<T: BitPieceFlags>(s: &str) -> Result<T, ParseFlagsError> {
+    let mut bits = 0u64;
+    if !s.trim().is_empty() {
+        for name in s.split('|') {
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(ParseFlagsError::EmptyFlag);
+            }
+            let flag = T::from_name(name).ok_or(ParseFlagsError::UnknownFlag)?;
+            bits |= flag.to_bits().to_u64();
+        }
+    }
+    // all fields are `bool`s, so any combination of flags is valid.
+    let bits = T::Bits::from_u64(bits).unwrap_or_else(|_| unreachable!());
+    Ok(T::from_bits(bits))
+}
@@ -0,0 +1,186 @@
+use core::fmt::{self, Write};
+
+/// formats the lowest bits of a value in binary, padded to the total length of the given groups.
+///
+/// the bits are split into groups according to the given group lengths, which are ordered from the least significant
+/// group to the most significant one, and the groups are separated by `_`. when using the alternate flag, a `0b` prefix
+/// is added.
+pub fn fmt_binary_grouped(
+    bits: u64,
+    group_lens: &[usize],
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    if f.alternate() {
+        f.write_str("0b")?;
+    }
+    let mut offset: usize = group_lens.iter().sum();
+    for (i, &len) in group_lens.iter().enumerate().rev() {
+        offset -= len;
+        if i != group_lens.len() - 1 {
+            f.write_char('_')?;
+        }
+        write!(
+            f,
+            "{:0len$b}",
+            crate::extract_bits(bits, offset, len),
+            len = len
+        )?;
+    }
+    Ok(())
+}
+
+/// formats the `bit_len` lowest bits of a value in binary, padded to `bit_len` digits.
+pub fn fmt_binary(bits: u64, bit_len: usize, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt_binary_grouped(bits, &[bit_len], f)
+}
+
+/// formats the `bit_len` lowest bits of a value in octal, padded to the amount of digits needed for `bit_len` bits.
+pub fn fmt_octal(bits: u64, bit_len: usize, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if f.alternate() {
+        f.write_str("0o")?;
+    }
+    write!(f, "{:0width$o}", bits, width = bit_len.div_ceil(3))
+}
+
+/// formats the `bit_len` lowest bits of a value in lowercase hex, padded to the amount of digits needed for `bit_len`
+/// bits.
+pub fn fmt_lower_hex(bits: u64, bit_len: usize, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if f.alternate() {
+        f.write_str("0x")?;
+    }
+    write!(f, "{:0width$x}", bits, width = bit_len.div_ceil(4))
+}
+
+/// formats the `bit_len` lowest bits of a value in uppercase hex, padded to the amount of digits needed for `bit_len`
+/// bits.
+pub fn fmt_upper_hex(bits: u64, bit_len: usize, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if f.alternate() {
+        f.write_str("0x")?;
+    }
+    write!(f, "{:0width$X}", bits, width = bit_len.div_ceil(4))
+}
+
+/// a field of a [`LayoutDiagram`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LayoutDiagramField {
+    /// the name of the field.
+    pub name: &'static str,
+
+    /// the offset of the field, in bits, from the least significant bit of the containing type.
+    pub offset: usize,
+
+    /// the length of the field, in bits.
+    pub len: usize,
+}
+
+/// a diagram of the bit layout of a bitpiece struct and the current values of its fields.
+///
+/// the diagram is displayed as a line for the whole value, followed by a line for each field, starting from the most
+/// significant field. for example:
+///
+/// ```text
+/// Control (8 bits) = 0b10001_11_1
+///   [7:3] level   = 0b10001 (17)
+///   [2:1] mode    = 0b11 (3)
+///   [0]   enabled = 0b1 (1)
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LayoutDiagram<'a> {
+    type_name: &'static str,
+    bits: u64,
+    fields: &'a [LayoutDiagramField],
+}
+impl<'a> LayoutDiagram<'a> {
+    /// creates a new layout diagram for a value with the given raw bits and fields.
+    ///
+    /// the fields must be ordered by their offsets and cover the whole value.
+    pub const fn new(type_name: &'static str, bits: u64, fields: &'a [LayoutDiagramField]) -> Self {
+        Self {
+            type_name,
+            bits,
+            fields,
+        }
+    }
+}
+
+/// a wrapper which displays the bit range `[hi:lo]` of a field, or `[bit]` for single bit fields.
+struct BitRange(LayoutDiagramField);
+impl fmt::Display for BitRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let LayoutDiagramField { offset, len, .. } = self.0;
+        if len == 1 {
+            write!(f, "[{}]", offset)
+        } else {
+            write!(f, "[{}:{}]", offset + len - 1, offset)
+        }
+    }
+}
+
+/// a wrapper which pads its contents with spaces to a minimal width, since custom `Display` implementations ignore the
+/// padding flags of the formatter.
+struct Padded<T>(T, usize);
+impl<T: fmt::Display> fmt::Display for Padded<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut counter = CharCounter(0);
+        write!(counter, "{}", self.0)?;
+        write!(f, "{}", self.0)?;
+        for _ in counter.0..self.1 {
+            f.write_char(' ')?;
+        }
+        Ok(())
+    }
+}
+
+/// a writer which only counts the characters written to it.
+struct CharCounter(usize);
+impl Write for CharCounter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+impl fmt::Display for LayoutDiagram<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bit_len: usize = self.fields.iter().map(|field| field.len).sum();
+        write!(f, "{} ({} bits) = 0b", self.type_name, bit_len)?;
+        for (i, field) in self.fields.iter().enumerate().rev() {
+            if i != self.fields.len() - 1 {
+                f.write_char('_')?;
+            }
+            let value = crate::extract_bits(self.bits, field.offset, field.len);
+            write!(f, "{:0len$b}", value, len = field.len)?;
+        }
+
+        let range_width = self
+            .fields
+            .iter()
+            .map(|field| {
+                let mut counter = CharCounter(0);
+                let _ = write!(counter, "{}", BitRange(*field));
+                counter.0
+            })
+            .max()
+            .unwrap_or(0);
+        let name_width = self
+            .fields
+            .iter()
+            .map(|field| field.name.chars().count())
+            .max()
+            .unwrap_or(0);
+        for field in self.fields.iter().rev() {
+            let value = crate::extract_bits(self.bits, field.offset, field.len);
+            write!(
+                f,
+                "\n  {} {:<name_width$} = 0b{:0len$b} ({})",
+                Padded(BitRange(*field), range_width),
+                field.name,
+                value,
+                value,
+                name_width = name_width,
+                len = field.len,
+            )?;
+        }
+        Ok(())
+    }
+}
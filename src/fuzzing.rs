@@ -0,0 +1,315 @@
+//! support for generating bitpiece values in fuzzers and property tests, using the `arbitrary` and `proptest` crates.
+
+use crate::*;
+
+/// the amount of values returned by [`edge_values`].
+const EDGE_VALUES_COUNT: usize = 5;
+
+/// returns the values near the edges of the given inclusive range, which are more likely to trigger bugs.
+fn edge_values(min: i128, max: i128) -> [i128; EDGE_VALUES_COUNT] {
+    [
+        min,
+        (min + 1).min(max),
+        0i128.clamp(min, max),
+        (max - 1).max(min),
+        max,
+    ]
+}
+
+/// the amount of bit patterns returned by [`edge_bits`].
+const EDGE_BITS_COUNT: usize = 10;
+
+/// returns raw bit patterns of the given type which are near the edges, which include bit patterns right outside the
+/// range of valid values of the type.
+///
+/// the patterns are derived from the bit length alone rather than from the `MIN` and `MAX` constants, which may break
+/// the invariants of structs with a validation function. the sign bit and its neighbours cover the edges of signed
+/// types, and the neighbours of 0 and of all one bits cover the edges of unsigned types. they wrap around before being
+/// masked, since the sign bit of a 1 bit type is its only bit.
+fn edge_bits<T: BitPiece>() -> [u64; EDGE_BITS_COUNT] {
+    let mask = if T::BITS == 64 {
+        u64::MAX
+    } else {
+        (1u64 << T::BITS) - 1
+    };
+    let sign_bit = 1u64 << (T::BITS - 1);
+    [
+        0,
+        1,
+        2,
+        mask,
+        mask - 1,
+        mask.wrapping_sub(2),
+        sign_bit,
+        sign_bit.wrapping_sub(1),
+        sign_bit.wrapping_add(1),
+        sign_bit.wrapping_sub(2),
+    ]
+    .map(|bits| bits & mask)
+}
+
+/// converts raw bits, which are known to fit within the bit length of the given type, to its storage type.
+fn bits_to_storage<T: BitPiece>(bits: u64) -> T::Bits {
+    T::Bits::from_u64(bits).unwrap_or_else(|_| unreachable!())
+}
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impls {
+    use super::*;
+    use arbitrary::{Arbitrary, Result, Unstructured};
+
+    /// generates an arbitrary value of the given integer type, preferring values near the edges of its range.
+    fn arbitrary_bit_int<T: BitInt>(u: &mut Unstructured<'_>) -> Result<T> {
+        let min = T::MIN_VALUE.into();
+        let max = T::MAX_VALUE.into();
+        let value = if u.ratio(1u8, 4u8)? {
+            *u.choose(&edge_values(min, max))?
+        } else {
+            u.int_in_range(min..=max)?
+        };
+        Ok(T::try_from_i128(value).unwrap())
+    }
+
+    /// generates arbitrary raw bits for the given type, which are not necessarily a valid bit pattern of the type.
+    ///
+    /// bit patterns near the edges of the range of valid values are preferred, which is useful for fuzzing code which
+    /// decodes bitpiece types from raw bits, for example using `try_from_bits`.
+    pub fn arbitrary_raw_bits<T: BitPiece>(u: &mut Unstructured<'_>) -> Result<T::Bits> {
+        let edges = edge_bits::<T>();
+        let bits = if u.ratio(1u8, 4u8)? {
+            *u.choose(&edges)?
+        } else {
+            u.int_in_range(0..=edges[2])?
+        };
+        Ok(bits_to_storage::<T>(bits))
+    }
+
+    macro_rules! impl_arbitrary {
+        { $ty: ident } => {
+            impl<'a, const N: usize> Arbitrary<'a> for $ty<N>
+            where
+                BitLength<N>: AssociatedStorage,
+            {
+                fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                    arbitrary_bit_int(u)
+                }
+            }
+        };
+    }
+    impl_arbitrary! { UBits }
+    impl_arbitrary! { SBits }
+
+    macro_rules! impl_fixed_arbitrary {
+        { $ty: ident, $raw: ty } => {
+            impl<'a, const INT: usize, const FRAC: usize> Arbitrary<'a> for $ty<INT, FRAC>
+            where
+                FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+            {
+                fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                    let min = <Self as BitPiece>::MIN.raw() as i128;
+                    let max = <Self as BitPiece>::MAX.raw() as i128;
+                    let raw = if u.ratio(1u8, 4u8)? {
+                        *u.choose(&edge_values(min, max))?
+                    } else {
+                        u.int_in_range(min..=max)?
+                    };
+                    Ok(Self::from_raw(raw as $raw))
+                }
+            }
+        };
+    }
+    impl_fixed_arbitrary! { UFixed, u64 }
+    impl_fixed_arbitrary! { SFixed, i64 }
+
+    macro_rules! impl_biased_int_arbitrary {
+        { $ty: ident [$($params: tt)*] [$($args: tt)*] } => {
+            impl<'a, $($params)*> Arbitrary<'a> for $ty<$($args)*> {
+                fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                    let min = <Self as BitPiece>::MIN.get() as i128;
+                    let max = <Self as BitPiece>::MAX.get() as i128;
+                    let value = if u.ratio(1u8, 4u8)? {
+                        *u.choose(&edge_values(min, max))?
+                    } else {
+                        u.int_in_range(min..=max)?
+                    };
+                    Ok(Self::new(value as i64))
+                }
+            }
+        };
+    }
+    impl_biased_int_arbitrary! { Biased [B: BitInt, const OFFSET: i64] [B, OFFSET] }
+    impl_biased_int_arbitrary! { Ranged [const MIN: i64, const MAX: i64] [MIN, MAX] }
+
+    macro_rules! impl_encoded_int_arbitrary {
+        { $ty: ident } => {
+            impl<'a, const N: usize> Arbitrary<'a> for $ty<N> {
+                fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                    let max = <Self as BitPiece>::MAX.get() as i128;
+                    let value = if u.ratio(1u8, 4u8)? {
+                        *u.choose(&edge_values(0, max))?
+                    } else {
+                        u.int_in_range(0..=max)?
+                    };
+                    Ok(Self::new(value as u64))
+                }
+            }
+        };
+    }
+    impl_encoded_int_arbitrary! { Bcd }
+    impl_encoded_int_arbitrary! { Gray }
+
+    macro_rules! impl_bit_wrapper_arbitrary {
+        { $ty: ident } => {
+            impl<'a, T: Arbitrary<'a>> Arbitrary<'a> for $ty<T> {
+                fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                    T::arbitrary(u).map(Self)
+                }
+            }
+        };
+    }
+    impl_bit_wrapper_arbitrary! { Inverted }
+    impl_bit_wrapper_arbitrary! { Reversed }
+}
+#[cfg(feature = "arbitrary")]
+pub use arbitrary_impls::arbitrary_raw_bits;
+
+#[cfg(feature = "proptest")]
+mod proptest_impls {
+    use super::*;
+    use proptest::{
+        arbitrary::Arbitrary,
+        prop_oneof,
+        strategy::{BoxedStrategy, Strategy},
+    };
+
+    /// a strategy for values of the given integer type, preferring values near the edges of its range.
+    fn bit_int_strategy<T: BitInt + core::fmt::Debug + 'static>() -> BoxedStrategy<T> {
+        let min = T::MIN_VALUE.into();
+        let max = T::MAX_VALUE.into();
+        let edges = edge_values(min, max);
+        prop_oneof![
+            1 => (0..EDGE_VALUES_COUNT).prop_map(move |i| edges[i]),
+            3 => min..=max,
+        ]
+        .prop_map(|value| T::try_from_i128(value).unwrap())
+        .boxed()
+    }
+
+    /// a strategy for raw bits of the given type, which are not necessarily a valid bit pattern of the type.
+    ///
+    /// bit patterns near the edges of the range of valid values are preferred, which is useful for testing code which
+    /// decodes bitpiece types from raw bits, for example using `try_from_bits`.
+    pub fn proptest_raw_bits<T: BitPiece + 'static>() -> BoxedStrategy<T::Bits>
+    where
+        T::Bits: core::fmt::Debug + 'static,
+    {
+        let edges = edge_bits::<T>();
+        prop_oneof![
+            1 => (0..EDGE_BITS_COUNT).prop_map(move |i| edges[i]),
+            3 => 0..=edges[2],
+        ]
+        .prop_map(bits_to_storage::<T>)
+        .boxed()
+    }
+
+    macro_rules! impl_proptest_arbitrary {
+        { $ty: ident } => {
+            impl<const N: usize> Arbitrary for $ty<N>
+            where
+                BitLength<N>: AssociatedStorage,
+            {
+                type Parameters = ();
+                type Strategy = BoxedStrategy<Self>;
+                fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+                    bit_int_strategy()
+                }
+            }
+        };
+    }
+    impl_proptest_arbitrary! { UBits }
+    impl_proptest_arbitrary! { SBits }
+
+    macro_rules! impl_fixed_proptest_arbitrary {
+        { $ty: ident, $raw: ty } => {
+            impl<const INT: usize, const FRAC: usize> Arbitrary for $ty<INT, FRAC>
+            where
+                FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+            {
+                type Parameters = ();
+                type Strategy = BoxedStrategy<Self>;
+                fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+                    let min = <Self as BitPiece>::MIN.raw() as i128;
+                    let max = <Self as BitPiece>::MAX.raw() as i128;
+                    let edges = edge_values(min, max);
+                    prop_oneof![
+                        1 => (0..EDGE_VALUES_COUNT).prop_map(move |i| edges[i]),
+                        3 => min..=max,
+                    ]
+                    .prop_map(|raw| Self::from_raw(raw as $raw))
+                    .boxed()
+                }
+            }
+        };
+    }
+    impl_fixed_proptest_arbitrary! { UFixed, u64 }
+    impl_fixed_proptest_arbitrary! { SFixed, i64 }
+
+    macro_rules! impl_biased_int_proptest_arbitrary {
+        { $ty: ident [$($params: tt)*] [$($args: tt)*] } => {
+            impl<$($params)*> Arbitrary for $ty<$($args)*> {
+                type Parameters = ();
+                type Strategy = BoxedStrategy<Self>;
+                fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+                    let min = <Self as BitPiece>::MIN.get() as i128;
+                    let max = <Self as BitPiece>::MAX.get() as i128;
+                    let edges = edge_values(min, max);
+                    prop_oneof![
+                        1 => (0..EDGE_VALUES_COUNT).prop_map(move |i| edges[i]),
+                        3 => min..=max,
+                    ]
+                    .prop_map(|value| Self::new(value as i64))
+                    .boxed()
+                }
+            }
+        };
+    }
+    impl_biased_int_proptest_arbitrary! { Biased [B: BitInt + 'static, const OFFSET: i64] [B, OFFSET] }
+    impl_biased_int_proptest_arbitrary! { Ranged [const MIN: i64, const MAX: i64] [MIN, MAX] }
+
+    macro_rules! impl_encoded_int_proptest_arbitrary {
+        { $ty: ident } => {
+            impl<const N: usize> Arbitrary for $ty<N> {
+                type Parameters = ();
+                type Strategy = BoxedStrategy<Self>;
+                fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+                    let max = <Self as BitPiece>::MAX.get() as i128;
+                    let edges = edge_values(0, max);
+                    prop_oneof![
+                        1 => (0..EDGE_VALUES_COUNT).prop_map(move |i| edges[i]),
+                        3 => 0..=max,
+                    ]
+                    .prop_map(|value| Self::new(value as u64))
+                    .boxed()
+                }
+            }
+        };
+    }
+    impl_encoded_int_proptest_arbitrary! { Bcd }
+    impl_encoded_int_proptest_arbitrary! { Gray }
+
+    macro_rules! impl_bit_wrapper_proptest_arbitrary {
+        { $ty: ident } => {
+            impl<T: Arbitrary + 'static> Arbitrary for $ty<T> {
+                type Parameters = T::Parameters;
+                type Strategy = BoxedStrategy<Self>;
+                fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+                    T::arbitrary_with(args).prop_map(Self).boxed()
+                }
+            }
+        };
+    }
+    impl_bit_wrapper_proptest_arbitrary! { Inverted }
+    impl_bit_wrapper_proptest_arbitrary! { Reversed }
+}
+#[cfg(feature = "proptest")]
+pub use proptest_impls::proptest_raw_bits;
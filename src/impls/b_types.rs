@@ -1,75 +1,324 @@
 use crate::*;
 
-macro_rules! b_type_impl_from_int_type {
-    { $b_type_ident: ident, $b_type_storage: ty, $uint_type: ty } => {
-        impl From<$uint_type> for $b_type_ident {
-            fn from(x: $uint_type) -> Self {
-                Self::new(x as $b_type_storage)
-            }
+#[cfg(feature = "serde")]
+use super::ExpectedBitInt;
+
+/// an unsigned integer type with a bit length of `N`.
+///
+/// this is the generic form of the `B1`..`B64` types, which allows code to be generic over the bit length. generic code
+/// can use the [`BitPiece`] and [`BitInt`] traits, which provide the same constructors and arithmetic methods as the
+/// inherent const functions of the concrete types.
+#[derive(Clone, Copy)]
+pub struct UBits<const N: usize>(<BitLength<N> as AssociatedStorage>::Storage)
+where
+    BitLength<N>: AssociatedStorage;
+
+/// a mutable reference to a [`UBits`] value inside another bitpiece.
+#[derive(Debug)]
+pub struct UBitsMutRef<'s, const N: usize>(pub BitsMut<'s>);
+impl<'s, const N: usize> UBitsMutRef<'s, N> {
+    pub const fn new(storage: BitPieceStorageMutRef<'s>, start_bit_index: usize) -> Self {
+        Self(BitsMut::new(storage, start_bit_index))
+    }
+}
+
+impl<const N: usize> BitPiece for UBits<N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    const BITS: usize = N;
+    const ZEROES: Self = Self(<<BitLength<N> as AssociatedStorage>::Storage as BitStorage>::ZEROES);
+    const ONES: Self = Self(<BitLength<N> as AssociatedStorage>::MASK);
+    const MIN: Self = <Self as BitPiece>::ZEROES;
+    const MAX: Self = <Self as BitPiece>::ONES;
+    type Bits = <BitLength<N> as AssociatedStorage>::Storage;
+    type Converter = Self;
+    fn try_from_bits(bits: Self::Bits) -> Option<Self> {
+        if bits.to_u64() <= <BitLength<N> as AssociatedStorage>::MASK.to_u64() {
+            Some(Self(bits))
+        } else {
+            None
         }
     }
+    fn from_bits(bits: Self::Bits) -> Self {
+        <Self as BitPiece>::try_from_bits(bits).unwrap()
+    }
+    fn to_bits(self) -> Self::Bits {
+        self.0
+    }
+}
+impl<const N: usize> BitPieceHasMutRef for UBits<N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    type MutRef<'s> = UBitsMutRef<'s, N>;
+}
+impl<'s, const N: usize> BitPieceMutRef<'s> for UBitsMutRef<'s, N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    type BitPiece = UBits<N>;
+
+    fn new(storage: BitPieceStorageMutRef<'s>, start_bit_index: usize) -> Self {
+        Self::new(storage, start_bit_index)
+    }
+
+    fn get(&self) -> UBits<N> {
+        let bits = self.0.get_bits(0, N);
+        <UBits<N> as BitPiece>::from_bits(BitStorage::from_u64(bits).unwrap())
+    }
+
+    fn set(&mut self, new_value: UBits<N>) {
+        self.0.set_bits(0, N, new_value.0.to_u64())
+    }
+}
+impl<const N: usize> BitPieceHasLayout for UBits<N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    const TYPE_NAME: &'static str = B_TYPE_NAMES[N];
+}
+impl<const N: usize> BitInt for UBits<N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    type Primitive = <BitLength<N> as AssociatedStorage>::Storage;
+    const MIN_VALUE: Self::Primitive = <Self as BitPiece>::MIN.0;
+    const MAX_VALUE: Self::Primitive = <Self as BitPiece>::MAX.0;
+    fn to_i128(self) -> i128 {
+        self.0.into()
+    }
+    fn as_u64_bits(self) -> u64 {
+        self.0.to_u64()
+    }
+    fn try_from_i128(value: i128) -> Option<Self> {
+        u64::try_from(value)
+            .ok()
+            .and_then(|value| BitStorage::from_u64(value).ok())
+            .and_then(<Self as BitPiece>::try_from_bits)
+    }
+    fn get(&self) -> Self::Primitive {
+        self.0
+    }
+}
+impl<const N: usize> BitPieceVisit for UBits<N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    fn visit_as(&self, name: &str, visitor: &mut impl BitPieceVisitor) {
+        visitor.visit_unsigned(name, self.0.to_u64(), N)
+    }
+}
+impl<const N: usize> BitPieceHasFields for UBits<N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    type Fields = Self;
+    fn from_fields(fields: Self::Fields) -> Self {
+        fields
+    }
+    fn to_fields(self) -> Self::Fields {
+        self
+    }
+}
+impl<const N: usize> core::str::FromStr for UBits<N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    type Err = ParseBitIntError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_i128(parse_unsigned(s)?.into()).ok_or(ParseBitIntError::OutOfRange)
+    }
 }
+impl_bit_int_std_traits! { UBits }
+impl_bitpiece_fmt_traits! { UBits }
+impl_bitpiece_arith_ops! { UBits }
 
-macro_rules! b_type_impl_from_int_types {
-    { $b_type_ident: ident, $b_type_storage: ty } => {
-        b_type_impl_from_int_type! { $b_type_ident, $b_type_storage, u8 }
-        b_type_impl_from_int_type! { $b_type_ident, $b_type_storage, u16 }
-        b_type_impl_from_int_type! { $b_type_ident, $b_type_storage, u32 }
-        b_type_impl_from_int_type! { $b_type_ident, $b_type_storage, u64 }
-        b_type_impl_from_int_type! { $b_type_ident, $b_type_storage, i8 }
-        b_type_impl_from_int_type! { $b_type_ident, $b_type_storage, i16 }
-        b_type_impl_from_int_type! { $b_type_ident, $b_type_storage, i32 }
-        b_type_impl_from_int_type! { $b_type_ident, $b_type_storage, i64 }
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for UBits<N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.0.to_u64())
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for UBits<N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = <u64 as serde::Deserialize>::deserialize(deserializer)?;
+        Self::try_from_i128(value.into()).ok_or_else(|| {
+            serde::de::Error::invalid_value(
+                serde::de::Unexpected::Unsigned(value),
+                &ExpectedBitInt::<Self>::new(),
+            )
+        })
     }
 }
 
-macro_rules! define_b_type {
-    { $bit_len: literal, $ident: ident, $storage: ty, $mut_ref_ty_name: ident } => {
-        /// a type used to represent a field with a specific amount of bits.
-        #[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
-        pub struct $ident($storage);
-        impl BitPiece for $ident {
-            const BITS: usize = $bit_len;
-            const ZEROES: Self = Self(0);
-            const ONES: Self = Self(
-                if $bit_len == <$storage>::BITS {
-                    // if the bit length is equal to the amount of bits in our storage type, avoid the overflow
-                    // which will happen when shifting, and just returns the maximum value of the underlying
-                    // storage type.
-                    <$storage>::MAX
+macro_rules! b_type_impl_arith {
+    { $bit_len: literal, $b_type_ident: ident, $b_type_storage: ty } => {
+        impl $b_type_ident {
+            /// creates a new instance of this bitfield type with the given value, truncated to the bit length of this
+            /// type.
+            pub const fn wrapping_new(value: $b_type_storage) -> Self {
+                Self(value & Self::MAX.0)
+            }
+
+            /// creates a new instance of this bitfield type with the given value, clamped to the max value of this
+            /// type.
+            pub const fn saturating_new(value: $b_type_storage) -> Self {
+                if value > Self::MAX.0 {
+                    Self::MAX
                 } else {
-                    ((1 as $storage) << $bit_len).wrapping_sub(1)
+                    Self(value)
                 }
-            );
-            const MIN: Self = Self::ZEROES;
-            const MAX: Self = Self::ONES;
-            type Bits = $storage;
-            type Converter = Self;
-            fn try_from_bits(bits: Self::Bits) -> Option<Self> {
-                <Self as BitPiece>::Converter::try_from_bits(bits)
             }
-            fn from_bits(bits: Self::Bits) -> Self {
-                <Self as BitPiece>::Converter::from_bits(bits)
+
+            /// wrapping addition, wrapping around at the bit length of this type.
+            pub const fn wrapping_add(self, rhs: Self) -> Self {
+                Self::wrapping_new(self.0.wrapping_add(rhs.0))
             }
-            fn to_bits(self) -> Self::Bits {
-                <Self as BitPiece>::Converter::to_bits(self)
+
+            /// wrapping subtraction, wrapping around at the bit length of this type.
+            pub const fn wrapping_sub(self, rhs: Self) -> Self {
+                Self::wrapping_new(self.0.wrapping_sub(rhs.0))
             }
-        }
 
-        b_type_impl_from_int_types! { $ident, $storage }
+            /// wrapping multiplication, wrapping around at the bit length of this type.
+            pub const fn wrapping_mul(self, rhs: Self) -> Self {
+                Self::wrapping_new(self.0.wrapping_mul(rhs.0))
+            }
 
-        impl BitPieceHasMutRef for $ident {
-            type MutRef<'s> = $mut_ref_ty_name<'s>;
-        }
-        impl BitPieceHasFields for $ident {
-            type Fields = Self;
-            fn from_fields(fields: Self::Fields) -> Self {
-                <Self as BitPiece>::Converter::from_fields(fields)
+            /// wrapping negation, wrapping around at the bit length of this type.
+            pub const fn wrapping_neg(self) -> Self {
+                Self::wrapping_new(self.0.wrapping_neg())
+            }
+
+            /// left shift, where the shift amount is taken modulo the bit length of this type.
+            pub const fn wrapping_shl(self, rhs: u32) -> Self {
+                Self::wrapping_new(self.0 << (rhs % <Self as BitPiece>::BITS as u32))
+            }
+
+            /// right shift, where the shift amount is taken modulo the bit length of this type.
+            pub const fn wrapping_shr(self, rhs: u32) -> Self {
+                Self(self.0 >> (rhs % <Self as BitPiece>::BITS as u32))
+            }
+
+            /// addition, returning the wrapped result along with whether an overflow occurred.
+            pub const fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+                (self.wrapping_add(rhs), self.0 as u128 + rhs.0 as u128 > Self::MAX.0 as u128)
+            }
+
+            /// subtraction, returning the wrapped result along with whether an overflow occurred.
+            pub const fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+                (self.wrapping_sub(rhs), self.0 < rhs.0)
+            }
+
+            /// multiplication, returning the wrapped result along with whether an overflow occurred.
+            pub const fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+                (self.wrapping_mul(rhs), self.0 as u128 * rhs.0 as u128 > Self::MAX.0 as u128)
+            }
+
+            /// negation, returning the wrapped result along with whether an overflow occurred, which is the case for
+            /// any non-zero value.
+            pub const fn overflowing_neg(self) -> (Self, bool) {
+                (self.wrapping_neg(), self.0 != 0)
+            }
+
+            /// checked addition, returning `None` if the result does not fit within the bit length of this type.
+            pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+                match self.overflowing_add(rhs) {
+                    (result, false) => Some(result),
+                    (_, true) => None,
+                }
+            }
+
+            /// checked subtraction, returning `None` if the result does not fit within the bit length of this type.
+            pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+                match self.overflowing_sub(rhs) {
+                    (result, false) => Some(result),
+                    (_, true) => None,
+                }
+            }
+
+            /// checked multiplication, returning `None` if the result does not fit within the bit length of this
+            /// type.
+            pub const fn checked_mul(self, rhs: Self) -> Option<Self> {
+                match self.overflowing_mul(rhs) {
+                    (result, false) => Some(result),
+                    (_, true) => None,
+                }
+            }
+
+            /// checked negation, returning `None` unless the value is zero.
+            pub const fn checked_neg(self) -> Option<Self> {
+                match self.overflowing_neg() {
+                    (result, false) => Some(result),
+                    (_, true) => None,
+                }
+            }
+
+            /// checked left shift, returning `None` if the shift amount is not smaller than the bit length of this
+            /// type.
+            pub const fn checked_shl(self, rhs: u32) -> Option<Self> {
+                if rhs < $bit_len {
+                    Some(self.wrapping_shl(rhs))
+                } else {
+                    None
+                }
+            }
+
+            /// checked right shift, returning `None` if the shift amount is not smaller than the bit length of this
+            /// type.
+            pub const fn checked_shr(self, rhs: u32) -> Option<Self> {
+                if rhs < $bit_len {
+                    Some(self.wrapping_shr(rhs))
+                } else {
+                    None
+                }
+            }
+
+            /// saturating addition, clamping the result to the max value of this type.
+            pub const fn saturating_add(self, rhs: Self) -> Self {
+                match self.checked_add(rhs) {
+                    Some(result) => result,
+                    None => Self::MAX,
+                }
             }
-            fn to_fields(self) -> Self::Fields {
-                <Self as BitPiece>::Converter::to_fields(self)
+
+            /// saturating subtraction, clamping the result to zero.
+            pub const fn saturating_sub(self, rhs: Self) -> Self {
+                match self.checked_sub(rhs) {
+                    Some(result) => result,
+                    None => Self::ZEROES,
+                }
+            }
+
+            /// saturating multiplication, clamping the result to the max value of this type.
+            pub const fn saturating_mul(self, rhs: Self) -> Self {
+                match self.checked_mul(rhs) {
+                    Some(result) => result,
+                    None => Self::MAX,
+                }
             }
         }
+    }
+}
+
+macro_rules! define_b_type {
+    { $bit_len: literal, $ident: ident, $storage: ty, $mut_ref_ty_name: ident } => {
+        /// a type used to represent a field with a specific amount of bits.
+        pub type $ident = UBits<$bit_len>;
+
+        /// a mutable reference to a value of the corresponding type inside another bitpiece.
+        pub type $mut_ref_ty_name<'s> = UBitsMutRef<'s, $bit_len>;
+
+        b_type_impl_arith! { $bit_len, $ident, $storage }
+
         impl $ident {
             pub const fn from_fields(fields: $ident) -> $ident {
                 fields
@@ -115,7 +364,7 @@ macro_rules! define_b_type {
             /// creates a new instance of this bitfield type with the given value, without checking that the value
             /// fits within the bit length of this type.
             ///
-            /// # safety
+            /// # Safety
             /// the provided value must fit within the bit length of this type.
             pub const unsafe fn new_unchecked(value: $storage) -> Self {
                 Self(value)
@@ -125,23 +374,44 @@ macro_rules! define_b_type {
             pub const fn get(&self) -> $storage {
                 self.0
             }
-        }
-        impl core::fmt::Display for $ident {
-            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                core::fmt::Display::fmt(&self.0, f)
+
+            /// converts the given value to this type.
+            ///
+            /// if the value does not fit within the bit length of this type, returns `None`.
+            pub const fn cast(value: u64) -> Option<Self> {
+                if value <= Self::ONES.0 as u64 {
+                    Some(Self(value as $storage))
+                } else {
+                    None
+                }
+            }
+
+            /// converts the given value to this type, keeping only the low bits which fit within the bit length of
+            /// this type.
+            pub const fn truncate(value: u64) -> Self {
+                Self((value as $storage) & Self::ONES.0)
             }
         }
-        impl core::fmt::Debug for $ident {
-            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                core::fmt::Debug::fmt(&self.0, f)
+        impl<'s> $mut_ref_ty_name<'s> {
+            pub const fn get(&self) -> $ident {
+                let bits = self.0.get_bits(0, $bit_len) as $storage;
+                $ident::from_bits(bits)
+            }
+
+            pub const fn set(&mut self, new_value: $ident) {
+                self.0.set_bits(0, $bit_len, new_value.0 as u64);
             }
         }
         bitpiece_check_full_impl! { $ident, true }
-        bitpiece_define_mut_ref_type! { $ident, $mut_ref_ty_name, pub }
+        impl_bitpiece_for_wrapping! { $ident }
+        impl_bitpiece_for_bit_wrappers! { $ident }
     };
 }
 macro_rules! define_b_types {
     { $($bit_len: literal),+ $(,)? } => {
+        /// the names of the `B*` types, indexed by their bit length.
+        const B_TYPE_NAMES: [&str; 65] = ["", $(concat!("B", stringify!($bit_len))),+];
+
         $(
             paste!{
                 define_b_type! {
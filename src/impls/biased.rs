@@ -0,0 +1,291 @@
+use core::{fmt, marker::PhantomData};
+
+use crate::*;
+
+/// an integer which is stored with a bias, using the bits of the integer type `B`.
+///
+/// the value is stored as `value - OFFSET`, so for example, a divider which is stored as `value - 1` in 4 bits is a
+/// `Biased<B4, 1>`, which holds values from 1 to 16, and an exponent which is stored as `value + 127` in 8 bits is a
+/// `Biased<B8, { -127 }>`, which holds values from -127 to 128. if `B` is signed, the stored integer uses two's
+/// complement.
+///
+/// the range of values of this type must fit in an `i64`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Biased<B, const OFFSET: i64>(i64, PhantomData<B>);
+
+/// an integer in the inclusive range `MIN..=MAX`, which is stored as its offset from `MIN`, using the least amount of
+/// bits which can represent all values in the range.
+///
+/// for example, a `Ranged<1980, 2107>` holds a year since 1980 in 7 bits. raw bits which represent a value above `MAX`
+/// are invalid.
+///
+/// `MIN` must not be larger than `MAX`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Ranged<const MIN: i64, const MAX: i64>(i64);
+
+/// returns the least amount of bits which can represent all values in the inclusive range `min..=max`, as an offset
+/// from `min`.
+const fn ranged_bit_len(min: i64, max: i64) -> usize {
+    if min > max {
+        panic!("the min value of a ranged type must not be larger than its max value");
+    }
+    let span = (max as i128 - min as i128) as u64;
+    if span == 0 {
+        1
+    } else {
+        (u64::BITS - span.leading_zeros()) as usize
+    }
+}
+
+/// checks that the range of values of a biased type fits in an `i64`, and returns its bit length.
+const fn check_biased_bit_len(len: usize, value_min: i128, value_max: i128) -> usize {
+    if value_min < i64::MIN as i128 || value_max > i64::MAX as i128 {
+        panic!("the range of values of a biased type must fit in an i64");
+    }
+    len
+}
+
+/// returns the range of raw integers which can be stored in the given amount of bits.
+const fn raw_int_range(len: usize, is_signed: bool) -> (i128, i128) {
+    if is_signed {
+        (-(1i128 << (len - 1)), (1i128 << (len - 1)) - 1)
+    } else {
+        (0, (1i128 << len) - 1)
+    }
+}
+
+macro_rules! define_biased_int_type {
+    {
+        $ident: ident [$($params: tt)*] [$($args: tt)*], $mut_ref_ty_name: ident, $expecting: literal
+    } => {
+        impl<$($params)*> BitPiece for $ident<$($args)*> {
+            const BITS: usize = Self::LEN;
+            const ZEROES: Self = Self::from_bits(0);
+            // if the raw bits of all ones are above the max value, use the max value instead, like enums do.
+            const ONES: Self = match Self::try_from_bits(extract_bits(u64::MAX, 0, Self::LEN)) {
+                Some(ones) => ones,
+                None => Self::MAX,
+            };
+            const MIN: Self = Self::from_value_unchecked(Self::VALUE_MIN);
+            const MAX: Self = Self::from_value_unchecked(Self::VALUE_MAX);
+            type Bits = u64;
+            type Converter = Self;
+            fn try_from_bits(bits: Self::Bits) -> Option<Self> {
+                <Self as BitPiece>::Converter::try_from_bits(bits)
+            }
+            fn from_bits(bits: Self::Bits) -> Self {
+                <Self as BitPiece>::Converter::from_bits(bits)
+            }
+            fn to_bits(self) -> Self::Bits {
+                <Self as BitPiece>::Converter::to_bits(self)
+            }
+        }
+        impl<$($params)*> BitPieceHasLayout for $ident<$($args)*> {
+            const TYPE_NAME: &'static str = stringify!($ident);
+            // the signedness of the raw integer is an implementation detail of the encoding, so the type is signed if
+            // its range of values includes negative values.
+            const IS_SIGNED: bool = Self::VALUE_MIN < 0;
+        }
+        impl<$($params)*> BitPieceVisit for $ident<$($args)*> {
+            fn visit_as(&self, name: &str, visitor: &mut impl BitPieceVisitor) {
+                if <Self as BitPieceHasLayout>::IS_SIGNED {
+                    visitor.visit_signed(name, self.0, <Self as BitPiece>::BITS)
+                } else {
+                    visitor.visit_unsigned(name, self.0 as u64, <Self as BitPiece>::BITS)
+                }
+            }
+        }
+        impl<$($params)*> BitPieceHasFields for $ident<$($args)*> {
+            type Fields = Self;
+            fn from_fields(fields: Self::Fields) -> Self {
+                <Self as BitPiece>::Converter::from_fields(fields)
+            }
+            fn to_fields(self) -> Self::Fields {
+                <Self as BitPiece>::Converter::to_fields(self)
+            }
+        }
+        impl<$($params)*> BitPieceHasMutRef for $ident<$($args)*> {
+            type MutRef<'s> = $mut_ref_ty_name<'s, $($args)*>;
+        }
+
+        impl<$($params)*> $ident<$($args)*> {
+            /// the min value of this type.
+            const VALUE_MIN: i128 = Self::RAW_MIN + Self::BIAS;
+
+            /// the max value of this type.
+            const VALUE_MAX: i128 = Self::RAW_MAX + Self::BIAS;
+
+            /// converts the given raw integer, which is known to be in the raw range of this type, to its bits.
+            const fn raw_to_bits(raw: i128) -> u64 {
+                extract_bits(raw as u64, 0, <Self as BitPiece>::BITS)
+            }
+
+            pub const fn from_fields(fields: Self) -> Self {
+                fields
+            }
+            pub const fn to_fields(x: Self) -> Self {
+                x
+            }
+            pub const fn try_from_bits(bits: u64) -> Option<Self> {
+                let len = <Self as BitPiece>::BITS;
+                if extract_bits(bits, 0, len) != bits {
+                    return None;
+                }
+                let raw = if Self::IS_RAW_SIGNED {
+                    sign_extend_bits(bits, len) as i128
+                } else {
+                    bits as i128
+                };
+                if raw > Self::RAW_MAX {
+                    return None;
+                }
+                Some(Self::from_value_unchecked(raw + Self::BIAS))
+            }
+            pub const fn from_bits(bits: u64) -> Self {
+                Self::try_from_bits(bits).unwrap()
+            }
+            pub const fn to_bits(self) -> u64 {
+                Self::raw_to_bits(self.0 as i128 - Self::BIAS)
+            }
+            pub const fn const_eq(a: Self, b: Self) -> bool {
+                a.0 == b.0
+            }
+
+            /// creates a new instance of this type with the given value.
+            ///
+            /// this function panics if the value is not in the range of this type.
+            pub const fn new(value: i64) -> Self {
+                Self::try_new(value).unwrap()
+            }
+
+            /// creates a new instance of this type with the given value.
+            ///
+            /// if the value is not in the range of this type, returns `None`.
+            pub const fn try_new(value: i64) -> Option<Self> {
+                let value = value as i128;
+                if value >= Self::VALUE_MIN && value <= Self::VALUE_MAX {
+                    Some(Self::from_value_unchecked(value))
+                } else {
+                    None
+                }
+            }
+
+            /// returns the value, without the bias which is used to store it.
+            pub const fn get(&self) -> i64 {
+                self.0
+            }
+        }
+
+        impl<$($params)*> fmt::Display for $ident<$($args)*> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)
+            }
+        }
+        impl<$($params)*> fmt::Debug for $ident<$($args)*> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(&self.0, f)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<$($params)*> serde::Serialize for $ident<$($args)*> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_i64(self.0)
+            }
+        }
+        #[cfg(feature = "serde")]
+        impl<'de, $($params)*> serde::Deserialize<'de> for $ident<$($args)*> {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = <i64 as serde::Deserialize>::deserialize(deserializer)?;
+                Self::try_new(value).ok_or_else(|| {
+                    serde::de::Error::invalid_value(serde::de::Unexpected::Signed(value), &$expecting)
+                })
+            }
+        }
+
+        /// a mutable reference to a value of this type inside another bitpiece.
+        #[derive(Debug)]
+        pub struct $mut_ref_ty_name<'s, $($params)*>(pub BitsMut<'s>, PhantomData<$ident<$($args)*>>);
+        impl<'s, $($params)*> $mut_ref_ty_name<'s, $($args)*> {
+            pub const fn new(storage: BitPieceStorageMutRef<'s>, start_bit_index: usize) -> Self {
+                Self(BitsMut::new(storage, start_bit_index), PhantomData)
+            }
+
+            pub const fn get(&self) -> $ident<$($args)*> {
+                $ident::from_bits(self.0.get_bits(0, <$ident<$($args)*> as BitPiece>::BITS))
+            }
+
+            pub const fn set(&mut self, new_value: $ident<$($args)*>) {
+                self.0.set_bits(0, <$ident<$($args)*> as BitPiece>::BITS, new_value.to_bits());
+            }
+        }
+        impl<'s, $($params)*> BitPieceMutRef<'s> for $mut_ref_ty_name<'s, $($args)*> {
+            type BitPiece = $ident<$($args)*>;
+
+            fn new(storage: BitPieceStorageMutRef<'s>, start_bit_index: usize) -> Self {
+                Self::new(storage, start_bit_index)
+            }
+
+            fn get(&self) -> $ident<$($args)*> {
+                self.get()
+            }
+
+            fn set(&mut self, new_value: $ident<$($args)*>) {
+                self.set(new_value)
+            }
+        }
+    };
+}
+
+define_biased_int_type! {
+    Biased [B: BitInt, const OFFSET: i64] [B, OFFSET], BiasedMutRef, "an integer in the range of the biased type"
+}
+define_biased_int_type! {
+    Ranged [const MIN: i64, const MAX: i64] [MIN, MAX], RangedMutRef, "an integer in the range of the ranged type"
+}
+
+impl<B: BitInt, const OFFSET: i64> Biased<B, OFFSET> {
+    /// the bit length of this type.
+    const LEN: usize =
+        check_biased_bit_len(<B as BitPiece>::BITS, Self::VALUE_MIN, Self::VALUE_MAX);
+
+    /// whether the raw integer is stored in two's complement.
+    const IS_RAW_SIGNED: bool = <B as BitPieceHasLayout>::IS_SIGNED;
+
+    /// the min raw integer.
+    const RAW_MIN: i128 = raw_int_range(<B as BitPiece>::BITS, Self::IS_RAW_SIGNED).0;
+
+    /// the max raw integer.
+    const RAW_MAX: i128 = raw_int_range(<B as BitPiece>::BITS, Self::IS_RAW_SIGNED).1;
+
+    /// the bias which is added to the raw integer to get the value.
+    const BIAS: i128 = OFFSET as i128;
+
+    /// creates a value of this type without checking that it is in the range of this type.
+    const fn from_value_unchecked(value: i128) -> Self {
+        Self(value as i64, PhantomData)
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> Ranged<MIN, MAX> {
+    /// the bit length of this type.
+    const LEN: usize = ranged_bit_len(MIN, MAX);
+
+    /// whether the raw integer is stored in two's complement.
+    const IS_RAW_SIGNED: bool = false;
+
+    /// the min raw integer.
+    const RAW_MIN: i128 = 0;
+
+    /// the max raw integer, which is smaller than the max value of the raw bits if the span of the range is not a power
+    /// of two.
+    const RAW_MAX: i128 = MAX as i128 - MIN as i128;
+
+    /// the bias which is added to the raw integer to get the value.
+    const BIAS: i128 = MIN as i128;
+
+    /// creates a value of this type without checking that it is in the range of this type.
+    const fn from_value_unchecked(value: i128) -> Self {
+        Self(value as i64)
+    }
+}
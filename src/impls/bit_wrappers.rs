@@ -0,0 +1,157 @@
+use core::{fmt, marker::PhantomData};
+
+use crate::*;
+
+/// a value which is stored with all of its bits complemented, for example an active-low enable bit such as `nRESET`.
+///
+/// the inner value is the logical value, and the stored bits are its bits complemented within the bit length of `T`,
+/// so for example, an `Inverted<bool>` field which holds `true` is stored as a zero bit.
+///
+/// it implements [`BitPiece`] for any bitpiece type `T`. the const conversion functions, which the const accessors of
+/// struct fields call, are only implemented for `bool`, the `B*` and `SB*` types, and the native integer types, since
+/// const functions can't call trait methods, so only these can be wrapped in fields of structs.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Inverted<T>(pub T);
+
+/// a value which is stored with the order of its bits reversed, so that the most significant bit of the value is stored
+/// in the lowest bit.
+///
+/// the inner value is the logical value, and the stored bits are its bits reversed within the bit length of `T`, so for
+/// example, a `Reversed<B4>` field which holds `0b0001` is stored as `0b1000`.
+///
+/// like [`Inverted`], it implements [`BitPiece`] for any bitpiece type `T`, but can only be used in fields of structs
+/// when wrapping `bool`, the `B*` and `SB*` types, and the native integer types.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Reversed<T>(pub T);
+
+/// complements the given bits within the given bit length.
+pub(crate) const fn invert_bits(bits: u64, len: usize) -> u64 {
+    extract_bits(!bits, 0, len)
+}
+
+/// reverses the order of the given bits within the given bit length.
+pub(crate) const fn reverse_bits(bits: u64, len: usize) -> u64 {
+    extract_bits(bits, 0, len).reverse_bits() >> (u64::BITS as usize - len)
+}
+
+macro_rules! define_bit_wrapper_type {
+    { $ident: ident, $mut_ref_ty_name: ident, $transform: ident, $zeroes: ident, $ones: ident } => {
+        impl<T> $ident<T> {
+            /// creates a new instance of this type with the given logical value.
+            pub const fn new(value: T) -> Self {
+                Self(value)
+            }
+        }
+        impl<T: Copy> $ident<T> {
+            /// returns the logical value.
+            pub const fn get(&self) -> T {
+                self.0
+            }
+        }
+
+        impl<T: fmt::Display> fmt::Display for $ident<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)
+            }
+        }
+        impl<T: fmt::Debug> fmt::Debug for $ident<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(&self.0, f)
+            }
+        }
+
+        // the logical value is serialized, like the value of the field would be without this wrapper.
+        #[cfg(feature = "serde")]
+        impl<T: serde::Serialize> serde::Serialize for $ident<T> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.0.serialize(serializer)
+            }
+        }
+        #[cfg(feature = "serde")]
+        impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for $ident<T> {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                T::deserialize(deserializer).map(Self)
+            }
+        }
+
+        /// a mutable reference to a value of this type inside another bitpiece.
+        #[derive(Debug)]
+        pub struct $mut_ref_ty_name<'s, T>(pub BitsMut<'s>, PhantomData<T>);
+        impl<'s, T> $mut_ref_ty_name<'s, T> {
+            pub const fn new(storage: BitPieceStorageMutRef<'s>, start_bit_index: usize) -> Self {
+                Self(BitsMut::new(storage, start_bit_index), PhantomData)
+            }
+        }
+
+        impl<T: BitPiece> $ident<T> {
+            /// transforms the bits of the inner value to the stored bits, or the other way around, which is the same
+            /// operation since the transform is its own inverse.
+            fn transform_bits(bits: u64) -> T::Bits {
+                // the transformed bits are within the bit length of `T`, so they always fit in its storage type.
+                T::Bits::from_u64($transform(bits, T::BITS)).unwrap()
+            }
+        }
+        impl<T: BitPiece> BitPiece for $ident<T> {
+            const BITS: usize = T::BITS;
+            const ZEROES: Self = Self(T::$zeroes);
+            const ONES: Self = Self(T::$ones);
+            const MIN: Self = Self(T::MIN);
+            const MAX: Self = Self(T::MAX);
+            type Bits = T::Bits;
+            type Converter = Self;
+            fn try_from_bits(bits: Self::Bits) -> Option<Self> {
+                let bits = bits.to_u64();
+                if extract_bits(bits, 0, T::BITS) != bits {
+                    return None;
+                }
+                T::try_from_bits(Self::transform_bits(bits)).map(Self)
+            }
+            fn from_bits(bits: Self::Bits) -> Self {
+                <Self as BitPiece>::try_from_bits(bits).unwrap()
+            }
+            fn to_bits(self) -> Self::Bits {
+                Self::transform_bits(self.0.to_bits().to_u64())
+            }
+        }
+        impl<T: BitPiece> BitPieceHasMutRef for $ident<T> {
+            type MutRef<'s> = $mut_ref_ty_name<'s, T>;
+        }
+        impl<T: BitPieceHasLayout> BitPieceHasLayout for $ident<T> {
+            const TYPE_NAME: &'static str = stringify!($ident);
+            const IS_SIGNED: bool = T::IS_SIGNED;
+        }
+        impl<T: BitPieceVisit> BitPieceVisit for $ident<T> {
+            fn visit_as(&self, name: &str, visitor: &mut impl BitPieceVisitor) {
+                self.0.visit_as(name, visitor)
+            }
+        }
+        impl<T: BitPiece> BitPieceHasFields for $ident<T> {
+            type Fields = Self;
+            fn from_fields(fields: Self::Fields) -> Self {
+                fields
+            }
+            fn to_fields(self) -> Self::Fields {
+                self
+            }
+        }
+        impl<'s, T: BitPiece> BitPieceMutRef<'s> for $mut_ref_ty_name<'s, T> {
+            type BitPiece = $ident<T>;
+
+            fn new(storage: BitPieceStorageMutRef<'s>, start_bit_index: usize) -> Self {
+                Self::new(storage, start_bit_index)
+            }
+
+            fn get(&self) -> $ident<T> {
+                let bits = T::Bits::from_u64(self.0.get_bits(0, T::BITS)).unwrap();
+                <$ident<T> as BitPiece>::from_bits(bits)
+            }
+
+            fn set(&mut self, new_value: $ident<T>) {
+                self.0.set_bits(0, T::BITS, new_value.to_bits().to_u64());
+            }
+        }
+    };
+}
+
+define_bit_wrapper_type! { Inverted, InvertedMutRef, invert_bits, ONES, ZEROES }
+define_bit_wrapper_type! { Reversed, ReversedMutRef, reverse_bits, ZEROES, ONES }
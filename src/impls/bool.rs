@@ -47,6 +47,14 @@ impl BitPiece for bool {
 impl BitPieceHasMutRef for bool {
     type MutRef<'s> = BitPieceBoolMutRef<'s>;
 }
+impl BitPieceHasLayout for bool {
+    const TYPE_NAME: &'static str = "bool";
+}
+impl BitPieceVisit for bool {
+    fn visit_as(&self, name: &str, visitor: &mut impl BitPieceVisitor) {
+        visitor.visit_bool(name, *self)
+    }
+}
 impl BitPieceHasFields for bool {
     type Fields = bool;
     fn from_fields(fields: Self::Fields) -> Self {
@@ -58,3 +66,4 @@ impl BitPieceHasFields for bool {
 }
 bitpiece_check_full_impl! { bool, true }
 bitpiece_define_mut_ref_type! { bool, BitPieceBoolMutRef, pub }
+impl_bitpiece_for_bit_wrappers! { bool }
@@ -0,0 +1,67 @@
+use crate::*;
+
+pub struct BitPieceCharConverter;
+impl BitPieceCharConverter {
+    pub const fn from_fields(fields: char) -> char {
+        fields
+    }
+    pub const fn to_fields(x: char) -> char {
+        x
+    }
+    pub const fn try_from_bits(bits: u32) -> Option<char> {
+        char::from_u32(bits)
+    }
+    pub const fn from_bits(bits: u32) -> char {
+        Self::try_from_bits(bits).unwrap()
+    }
+    pub const fn to_bits(x: char) -> u32 {
+        x as u32
+    }
+    pub const fn const_eq(a: char, b: char) -> bool {
+        a == b
+    }
+}
+
+/// a `char` is stored as its unicode scalar value, in 21 bits. bit patterns which are not a unicode scalar value, such
+/// as surrogates, are invalid.
+impl BitPiece for char {
+    const BITS: usize = 21;
+    const ZEROES: Self = '\0';
+    // a value with all one bits is not a unicode scalar value, so like with enums, the max value is used instead.
+    const ONES: Self = char::MAX;
+    const MIN: Self = '\0';
+    const MAX: Self = char::MAX;
+    type Bits = u32;
+    type Converter = BitPieceCharConverter;
+    fn try_from_bits(bits: Self::Bits) -> Option<Self> {
+        <Self as BitPiece>::Converter::try_from_bits(bits)
+    }
+    fn from_bits(bits: Self::Bits) -> Self {
+        <Self as BitPiece>::Converter::from_bits(bits)
+    }
+    fn to_bits(self) -> Self::Bits {
+        <Self as BitPiece>::Converter::to_bits(self)
+    }
+}
+impl BitPieceHasMutRef for char {
+    type MutRef<'s> = BitPieceCharMutRef<'s>;
+}
+impl BitPieceHasLayout for char {
+    const TYPE_NAME: &'static str = "char";
+}
+impl BitPieceVisit for char {
+    fn visit_as(&self, name: &str, visitor: &mut impl BitPieceVisitor) {
+        visitor.visit_unsigned(name, *self as u64, 21)
+    }
+}
+impl BitPieceHasFields for char {
+    type Fields = char;
+    fn from_fields(fields: Self::Fields) -> Self {
+        <Self as BitPiece>::Converter::from_fields(fields)
+    }
+    fn to_fields(self) -> Self::Fields {
+        <Self as BitPiece>::Converter::to_fields(self)
+    }
+}
+bitpiece_check_full_impl! { char, false }
+bitpiece_define_mut_ref_type! { char, BitPieceCharMutRef, pub }
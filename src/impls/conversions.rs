@@ -0,0 +1,216 @@
+use crate::*;
+
+/// the error returned when a checked conversion between bitpiece integer types fails because the value does not fit
+/// in the target type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TryFromBitIntError(pub(crate) ());
+impl core::fmt::Display for TryFromBitIntError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("out of range integral type conversion attempted")
+    }
+}
+
+/// the error returned when converting the fields struct of a struct with a validation function to the struct fails
+/// because the fields break the invariants checked by the validation function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InvalidFieldsError;
+impl core::fmt::Display for InvalidFieldsError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("the fields break the invariants of the struct")
+    }
+}
+
+/// returns the raw integer value of a value of an integer type of the given kind.
+macro_rules! bit_int_raw_value {
+    { b, $value: expr } => { $value.get() };
+    { sb, $value: expr } => { $value.get() };
+    { u, $value: expr } => { $value };
+    { i, $value: expr } => { $value };
+}
+
+/// converts a raw integer value which is known to fit in the given type of the given kind to that type.
+macro_rules! bit_int_from_raw_value {
+    { b, $ty: ident, $raw: expr } => { $ty::new($raw as _) };
+    { sb, $ty: ident, $raw: expr } => { $ty::new($raw as _) };
+    { u, $ty: ident, $raw: expr } => { $raw as $ty };
+    { i, $ty: ident, $raw: expr } => { $raw as $ty };
+}
+
+/// converts an `i128` value to the given type of the given kind, returning `None` if it does not fit.
+macro_rules! bit_int_try_from_i128 {
+    { b, $ty: ident, $value: expr } => { u64::try_from($value).ok().and_then($ty::cast) };
+    { sb, $ty: ident, $value: expr } => { i64::try_from($value).ok().and_then($ty::cast) };
+    { u, $ty: ident, $value: expr } => { $ty::try_from($value).ok() };
+    { i, $ty: ident, $value: expr } => { $ty::try_from($value).ok() };
+}
+
+/// implements a lossless `From` conversion between two integer types.
+macro_rules! impl_from_conversion {
+    { ($src_kind: ident $src: ident) ($dst_kind: ident $dst: ident) } => {
+        impl From<$src> for $dst {
+            #[inline]
+            fn from(value: $src) -> Self {
+                bit_int_from_raw_value!($dst_kind, $dst, bit_int_raw_value!($src_kind, value))
+            }
+        }
+    };
+}
+
+/// implements a checked `TryFrom` conversion between two integer types.
+macro_rules! impl_try_from_conversion {
+    { ($src_kind: ident $src: ident) ($dst_kind: ident $dst: ident) } => {
+        impl TryFrom<$src> for $dst {
+            type Error = TryFromBitIntError;
+            #[inline]
+            fn try_from(value: $src) -> Result<Self, Self::Error> {
+                bit_int_try_from_i128!($dst_kind, $dst, bit_int_raw_value!($src_kind, value) as i128)
+                    .ok_or(TryFromBitIntError(()))
+            }
+        }
+    };
+}
+
+/// implements the conversions in both directions between a narrow integer type and a wider integer type.
+macro_rules! impl_widening_conversion {
+    // conversions between primitive integer types are already provided by the standard library.
+    { (u $narrow: ident) (u $wide: ident) } => {};
+    { (u $narrow: ident) (i $wide: ident) } => {};
+    { (i $narrow: ident) (u $wide: ident) } => {};
+    { (i $narrow: ident) (i $wide: ident) } => {};
+
+    // an unsigned value always fits in a wider type.
+    { (b $narrow: ident) $wide: tt } => {
+        impl_from_conversion! { (b $narrow) $wide }
+        impl_try_from_conversion! { $wide (b $narrow) }
+    };
+    { (u $narrow: ident) $wide: tt } => {
+        impl_from_conversion! { (u $narrow) $wide }
+        impl_try_from_conversion! { $wide (u $narrow) }
+    };
+
+    // a signed value may not fit in an unsigned type.
+    { $narrow: tt (b $wide: ident) } => {
+        impl_try_from_conversion! { $narrow (b $wide) }
+        impl_try_from_conversion! { (b $wide) $narrow }
+    };
+    { $narrow: tt (u $wide: ident) } => {
+        impl_try_from_conversion! { $narrow (u $wide) }
+        impl_try_from_conversion! { (u $wide) $narrow }
+    };
+
+    // a signed value always fits in a wider signed type.
+    { $narrow: tt $wide: tt } => {
+        impl_from_conversion! { $narrow $wide }
+        impl_try_from_conversion! { $wide $narrow }
+    };
+}
+
+/// implements the conversions between each of the given wide types and each of the given narrow types.
+macro_rules! impl_widening_conversions {
+    { [] [$($narrow: tt)*] } => {};
+    { [$wide: tt $($wide_rest: tt)*] [$($narrow: tt)*] } => {
+        $(
+            impl_widening_conversion! { $narrow $wide }
+        )*
+        impl_widening_conversions! { [$($wide_rest)*] [$($narrow)*] }
+    };
+}
+
+/// implements the conversions between the integer types of a single bit length.
+macro_rules! impl_same_width_conversions {
+    { (b $b: ident) (sb $sb: ident) } => {
+        impl_try_from_conversion! { (b $b) (sb $sb) }
+        impl_try_from_conversion! { (sb $sb) (b $b) }
+    };
+    { (b $b: ident) (sb $sb: ident) (u $u: ident) (i $i: ident) } => {
+        impl_same_width_conversions! { (b $b) (sb $sb) }
+        impl_from_conversion! { (b $b) (u $u) }
+        impl_from_conversion! { (u $u) (b $b) }
+        impl_from_conversion! { (sb $sb) (i $i) }
+        impl_from_conversion! { (i $i) (sb $sb) }
+        impl_try_from_conversion! { (b $b) (i $i) }
+        impl_try_from_conversion! { (i $i) (b $b) }
+        impl_try_from_conversion! { (sb $sb) (u $u) }
+        impl_try_from_conversion! { (u $u) (sb $sb) }
+    };
+}
+
+/// implements the conversions between all pairs of the given integer types, which are grouped by their bit length in
+/// ascending order.
+///
+/// a conversion is implemented using `From` if it is lossless, and using `TryFrom` otherwise.
+macro_rules! impl_conversions {
+    { [$($narrow: tt)*] } => {};
+    { [$($narrow: tt)*] [$($new: tt)*] $($rest: tt)* } => {
+        impl_widening_conversions! { [$($new)*] [$($narrow)*] }
+        impl_same_width_conversions! { $($new)* }
+        impl_conversions! { [$($narrow)* $($new)*] $($rest)* }
+    };
+}
+impl_conversions! {
+    []
+    [(b B1) (sb SB1)]
+    [(b B2) (sb SB2)]
+    [(b B3) (sb SB3)]
+    [(b B4) (sb SB4)]
+    [(b B5) (sb SB5)]
+    [(b B6) (sb SB6)]
+    [(b B7) (sb SB7)]
+    [(b B8) (sb SB8) (u u8) (i i8)]
+    [(b B9) (sb SB9)]
+    [(b B10) (sb SB10)]
+    [(b B11) (sb SB11)]
+    [(b B12) (sb SB12)]
+    [(b B13) (sb SB13)]
+    [(b B14) (sb SB14)]
+    [(b B15) (sb SB15)]
+    [(b B16) (sb SB16) (u u16) (i i16)]
+    [(b B17) (sb SB17)]
+    [(b B18) (sb SB18)]
+    [(b B19) (sb SB19)]
+    [(b B20) (sb SB20)]
+    [(b B21) (sb SB21)]
+    [(b B22) (sb SB22)]
+    [(b B23) (sb SB23)]
+    [(b B24) (sb SB24)]
+    [(b B25) (sb SB25)]
+    [(b B26) (sb SB26)]
+    [(b B27) (sb SB27)]
+    [(b B28) (sb SB28)]
+    [(b B29) (sb SB29)]
+    [(b B30) (sb SB30)]
+    [(b B31) (sb SB31)]
+    [(b B32) (sb SB32) (u u32) (i i32)]
+    [(b B33) (sb SB33)]
+    [(b B34) (sb SB34)]
+    [(b B35) (sb SB35)]
+    [(b B36) (sb SB36)]
+    [(b B37) (sb SB37)]
+    [(b B38) (sb SB38)]
+    [(b B39) (sb SB39)]
+    [(b B40) (sb SB40)]
+    [(b B41) (sb SB41)]
+    [(b B42) (sb SB42)]
+    [(b B43) (sb SB43)]
+    [(b B44) (sb SB44)]
+    [(b B45) (sb SB45)]
+    [(b B46) (sb SB46)]
+    [(b B47) (sb SB47)]
+    [(b B48) (sb SB48)]
+    [(b B49) (sb SB49)]
+    [(b B50) (sb SB50)]
+    [(b B51) (sb SB51)]
+    [(b B52) (sb SB52)]
+    [(b B53) (sb SB53)]
+    [(b B54) (sb SB54)]
+    [(b B55) (sb SB55)]
+    [(b B56) (sb SB56)]
+    [(b B57) (sb SB57)]
+    [(b B58) (sb SB58)]
+    [(b B59) (sb SB59)]
+    [(b B60) (sb SB60)]
+    [(b B61) (sb SB61)]
+    [(b B62) (sb SB62)]
+    [(b B63) (sb SB63)]
+    [(b B64) (sb SB64) (u u64) (i i64)]
+}
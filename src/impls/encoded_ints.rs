@@ -0,0 +1,260 @@
+use core::{fmt, marker::PhantomData};
+
+use crate::*;
+
+/// an unsigned decimal integer of `DIGITS` digits, encoded as binary-coded decimal (BCD).
+///
+/// each digit is stored in 4 bits, with the least significant digit in the lowest bits, so for example, a `Bcd<2>`
+/// holds values from 0 to 99, and the value 59 is stored as `0x59`. raw bits which contain a digit above 9 are invalid.
+///
+/// `DIGITS` must be between 1 and 16.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Bcd<const DIGITS: usize>(u64);
+
+/// an unsigned integer of `N` bits, encoded as a reflected binary Gray code, where consecutive values differ in a single
+/// bit.
+///
+/// the value is converted to and from its Gray code automatically, so for example, the value 2 of a `Gray<3>` is stored
+/// as `0b011`. all bit patterns are valid.
+///
+/// `N` must be between 1 and 64.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Gray<const N: usize>(u64);
+
+/// checks that the given amount of digits forms a valid BCD type, and returns its bit length.
+const fn check_bcd_bit_len(digits: usize) -> usize {
+    if digits == 0 || digits > 16 {
+        panic!("the amount of digits of a BCD type must be between 1 and 16");
+    }
+    digits * 4
+}
+
+/// checks that the given bit length forms a valid Gray code type, and returns it.
+const fn check_gray_bit_len(len: usize) -> usize {
+    if len == 0 || len > 64 {
+        panic!("the bit length of a Gray code type must be between 1 and 64");
+    }
+    len
+}
+
+macro_rules! define_encoded_int_type {
+    {
+        $ident: ident, $param: ident, $mut_ref_ty_name: ident, $expecting: literal
+    } => {
+        impl<const $param: usize> BitPiece for $ident<$param> {
+            const BITS: usize = Self::LEN;
+            const ZEROES: Self = Self::from_bits(0);
+            // if the raw bits of all ones are not a valid encoding, use the max value instead, like enums do.
+            const ONES: Self = match Self::try_from_bits(extract_bits(u64::MAX, 0, Self::LEN)) {
+                Some(ones) => ones,
+                None => Self::MAX,
+            };
+            const MIN: Self = Self(0);
+            const MAX: Self = Self(Self::VALUE_MAX);
+            type Bits = u64;
+            type Converter = Self;
+            fn try_from_bits(bits: Self::Bits) -> Option<Self> {
+                <Self as BitPiece>::Converter::try_from_bits(bits)
+            }
+            fn from_bits(bits: Self::Bits) -> Self {
+                <Self as BitPiece>::Converter::from_bits(bits)
+            }
+            fn to_bits(self) -> Self::Bits {
+                <Self as BitPiece>::Converter::to_bits(self)
+            }
+        }
+        impl<const $param: usize> BitPieceHasLayout for $ident<$param> {
+            const TYPE_NAME: &'static str = stringify!($ident);
+            const IS_SIGNED: bool = false;
+        }
+        impl<const $param: usize> BitPieceVisit for $ident<$param> {
+            fn visit_as(&self, name: &str, visitor: &mut impl BitPieceVisitor) {
+                visitor.visit_unsigned(name, self.0, <Self as BitPiece>::BITS)
+            }
+        }
+        impl<const $param: usize> BitPieceHasFields for $ident<$param> {
+            type Fields = Self;
+            fn from_fields(fields: Self::Fields) -> Self {
+                <Self as BitPiece>::Converter::from_fields(fields)
+            }
+            fn to_fields(self) -> Self::Fields {
+                <Self as BitPiece>::Converter::to_fields(self)
+            }
+        }
+        impl<const $param: usize> BitPieceHasMutRef for $ident<$param> {
+            type MutRef<'s> = $mut_ref_ty_name<'s, $param>;
+        }
+
+        impl<const $param: usize> $ident<$param> {
+            pub const fn from_fields(fields: Self) -> Self {
+                fields
+            }
+            pub const fn to_fields(x: Self) -> Self {
+                x
+            }
+            pub const fn try_from_bits(bits: u64) -> Option<Self> {
+                if extract_bits(bits, 0, <Self as BitPiece>::BITS) != bits {
+                    return None;
+                }
+                match Self::decode(bits) {
+                    Some(value) => Some(Self(value)),
+                    None => None,
+                }
+            }
+            pub const fn from_bits(bits: u64) -> Self {
+                Self::try_from_bits(bits).unwrap()
+            }
+            pub const fn to_bits(self) -> u64 {
+                Self::encode(self.0)
+            }
+            pub const fn const_eq(a: Self, b: Self) -> bool {
+                a.0 == b.0
+            }
+
+            /// creates a new instance of this type with the given value.
+            ///
+            /// this function panics if the value is not in the range of this type.
+            pub const fn new(value: u64) -> Self {
+                Self::try_new(value).unwrap()
+            }
+
+            /// creates a new instance of this type with the given value.
+            ///
+            /// if the value is not in the range of this type, returns `None`.
+            pub const fn try_new(value: u64) -> Option<Self> {
+                if value <= Self::VALUE_MAX {
+                    Some(Self(value))
+                } else {
+                    None
+                }
+            }
+
+            /// returns the decoded value.
+            pub const fn get(&self) -> u64 {
+                self.0
+            }
+        }
+
+        impl<const $param: usize> fmt::Display for $ident<$param> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)
+            }
+        }
+        impl<const $param: usize> fmt::Debug for $ident<$param> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(&self.0, f)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<const $param: usize> serde::Serialize for $ident<$param> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_u64(self.0)
+            }
+        }
+        #[cfg(feature = "serde")]
+        impl<'de, const $param: usize> serde::Deserialize<'de> for $ident<$param> {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = <u64 as serde::Deserialize>::deserialize(deserializer)?;
+                Self::try_new(value).ok_or_else(|| {
+                    serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(value), &$expecting)
+                })
+            }
+        }
+
+        /// a mutable reference to a value of this type inside another bitpiece.
+        #[derive(Debug)]
+        pub struct $mut_ref_ty_name<'s, const $param: usize>(pub BitsMut<'s>, PhantomData<$ident<$param>>);
+        impl<'s, const $param: usize> $mut_ref_ty_name<'s, $param> {
+            pub const fn new(storage: BitPieceStorageMutRef<'s>, start_bit_index: usize) -> Self {
+                Self(BitsMut::new(storage, start_bit_index), PhantomData)
+            }
+
+            pub const fn get(&self) -> $ident<$param> {
+                $ident::from_bits(self.0.get_bits(0, <$ident<$param> as BitPiece>::BITS))
+            }
+
+            pub const fn set(&mut self, new_value: $ident<$param>) {
+                self.0.set_bits(0, <$ident<$param> as BitPiece>::BITS, new_value.to_bits());
+            }
+        }
+        impl<'s, const $param: usize> BitPieceMutRef<'s> for $mut_ref_ty_name<'s, $param> {
+            type BitPiece = $ident<$param>;
+
+            fn new(storage: BitPieceStorageMutRef<'s>, start_bit_index: usize) -> Self {
+                Self::new(storage, start_bit_index)
+            }
+
+            fn get(&self) -> $ident<$param> {
+                self.get()
+            }
+
+            fn set(&mut self, new_value: $ident<$param>) {
+                self.set(new_value)
+            }
+        }
+    };
+}
+
+define_encoded_int_type! { Bcd, DIGITS, BcdMutRef, "an integer with at most the amount of digits of the BCD type" }
+define_encoded_int_type! { Gray, N, GrayMutRef, "an integer which fits in the bit length of the Gray code type" }
+
+impl<const DIGITS: usize> Bcd<DIGITS> {
+    /// the bit length of this type.
+    const LEN: usize = check_bcd_bit_len(DIGITS);
+
+    /// the max value of this type, which has all of its digits set to 9.
+    const VALUE_MAX: u64 = 10u64.pow(DIGITS as u32) - 1;
+
+    /// encodes the given value, which is known to be in the range of this type, as BCD.
+    const fn encode(mut value: u64) -> u64 {
+        let mut bits = 0;
+        let mut shift = 0;
+        while value != 0 {
+            bits |= (value % 10) << shift;
+            value /= 10;
+            shift += 4;
+        }
+        bits
+    }
+
+    /// decodes the given BCD bits, returning `None` if any of the digits is above 9.
+    const fn decode(bits: u64) -> Option<u64> {
+        let mut value = 0;
+        let mut digit_index = DIGITS;
+        while digit_index > 0 {
+            digit_index -= 1;
+            let digit = (bits >> (digit_index * 4)) & 0xf;
+            if digit > 9 {
+                return None;
+            }
+            value = value * 10 + digit;
+        }
+        Some(value)
+    }
+}
+
+impl<const N: usize> Gray<N> {
+    /// the bit length of this type.
+    const LEN: usize = check_gray_bit_len(N);
+
+    /// the max value of this type.
+    const VALUE_MAX: u64 = extract_bits(u64::MAX, 0, Self::LEN);
+
+    /// encodes the given value as a Gray code.
+    const fn encode(value: u64) -> u64 {
+        value ^ (value >> 1)
+    }
+
+    /// decodes the given Gray code, which is always valid.
+    const fn decode(bits: u64) -> Option<u64> {
+        // each bit of the value is the xor of all bits of the Gray code at or above it.
+        let mut value = bits;
+        let mut shift = 1;
+        while shift < u64::BITS {
+            value ^= value >> shift;
+            shift *= 2;
+        }
+        Some(value)
+    }
+}
@@ -0,0 +1,610 @@
+use core::fmt;
+
+use crate::*;
+
+/// an unsigned fixed-point number with `INT` integer bits and `FRAC` fractional bits, also known as the `UQ<INT>.<FRAC>`
+/// format.
+///
+/// the value is stored as a raw unsigned integer of `INT + FRAC` bits, which represents the value `raw / 2^FRAC`. for
+/// example, a `UFixed<4, 12>` can hold values from `0` to `15.999755859375`, in steps of `1 / 4096`.
+///
+/// the total bit length, `INT + FRAC`, must be between 1 and 64, and the raw integer is stored using the smallest
+/// storage type which can hold it, like the `B*` types.
+#[derive(Clone, Copy)]
+pub struct UFixed<const INT: usize, const FRAC: usize>(
+    <FixedBitLength<INT, FRAC> as AssociatedFixedStorage>::Storage,
+)
+where
+    FixedBitLength<INT, FRAC>: AssociatedFixedStorage;
+
+/// a signed fixed-point number with `INT` integer bits and `FRAC` fractional bits, using two's complement, also known as
+/// the `Q<INT>.<FRAC>` format.
+///
+/// the value is stored as a raw signed integer of `INT + FRAC` bits, which represents the value `raw / 2^FRAC`. the
+/// integer bits include the sign bit, so for example, a `SFixed<1, 15>` can hold values from `-1` to `0.999969482421875`,
+/// in steps of `1 / 32768`.
+///
+/// the total bit length, `INT + FRAC`, must be between 1 and 64, and `INT` must be at least 1. the raw integer is
+/// stored using the signed version of the smallest storage type which can hold it, like the `SB*` types.
+#[derive(Clone, Copy)]
+pub struct SFixed<const INT: usize, const FRAC: usize>(
+    <<FixedBitLength<INT, FRAC> as AssociatedFixedStorage>::Storage as BitStorage>::Signed,
+)
+where
+    FixedBitLength<INT, FRAC>: AssociatedFixedStorage;
+
+/// checks that the given amounts of integer and fractional bits form a valid fixed-point type, and returns its total
+/// bit length.
+const fn check_fixed_bit_len(int_bits: usize, frac_bits: usize, is_signed: bool) -> usize {
+    let len = int_bits + frac_bits;
+    if len == 0 || len > 64 {
+        panic!("the bit length of a fixed-point type must be between 1 and 64");
+    }
+    if is_signed && int_bits == 0 {
+        panic!(
+            "a signed fixed-point type must have at least one integer bit, which holds its sign"
+        );
+    }
+    len
+}
+
+/// returns `2^frac_bits` as a float, which is the scale between a fixed-point value and its raw integer.
+const fn fixed_scale(frac_bits: usize) -> f64 {
+    (1u128 << frac_bits) as f64
+}
+
+/// rounds the given float to the nearest integer, with ties rounded away from zero.
+///
+/// values outside the range of `i128` saturate, and `NaN` is converted to zero, like with an `as` cast.
+const fn round_to_i128(value: f64) -> i128 {
+    let truncated = value as i128;
+    // for values which are large enough to lose their fractional part when converted to `f64`, this is zero, and for
+    // `NaN` all comparisons are false.
+    let fraction = value - truncated as f64;
+    if fraction >= 0.5 {
+        truncated.saturating_add(1)
+    } else if fraction <= -0.5 {
+        truncated.saturating_sub(1)
+    } else {
+        truncated
+    }
+}
+
+/// the maximum amount of fractional digits printed for a fixed-point value. a value with `FRAC` fractional bits never
+/// has more than `FRAC` fractional digits in decimal, so this is always enough to print it exactly.
+const MAX_FRAC_DIGITS: usize = 64;
+
+/// formats a fixed-point value as a decimal number, given the magnitude of its raw integer.
+///
+/// without a precision, the value is printed exactly, without trailing zeros in its fractional part. with a precision,
+/// it is rounded to that amount of fractional digits, with ties rounded away from zero. precisions above
+/// [`MAX_FRAC_DIGITS`] are treated as [`MAX_FRAC_DIGITS`].
+fn fmt_fixed_decimal(
+    is_nonnegative: bool,
+    magnitude: u128,
+    frac_bits: usize,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    let frac_mask = (1u128 << frac_bits) - 1;
+    let mut int_part = magnitude >> frac_bits;
+    let mut frac = magnitude & frac_mask;
+
+    // each iteration moves the next decimal digit of the fraction into the integer bits of `frac`.
+    let mut frac_digits = [0u8; MAX_FRAC_DIGITS];
+    let mut frac_len = 0;
+    let max_frac_len = f
+        .precision()
+        .map_or(MAX_FRAC_DIGITS, |precision| precision.min(MAX_FRAC_DIGITS));
+    while frac_len < max_frac_len && (frac != 0 || f.precision().is_some()) {
+        frac *= 10;
+        frac_digits[frac_len] = b'0' + (frac >> frac_bits) as u8;
+        frac &= frac_mask;
+        frac_len += 1;
+    }
+
+    // round the printed digits using the remaining fraction, propagating the carry into the integer part.
+    if frac != 0 && frac * 2 > frac_mask {
+        let mut carry = true;
+        for digit in frac_digits[..frac_len].iter_mut().rev() {
+            if *digit == b'9' {
+                *digit = b'0';
+            } else {
+                *digit += 1;
+                carry = false;
+                break;
+            }
+        }
+        if carry {
+            int_part += 1;
+        }
+    }
+
+    // the integer part is at most `2^64`, which has 20 decimal digits.
+    let mut buf = [0u8; 20 + 1 + MAX_FRAC_DIGITS];
+    let mut int_digits = [0u8; 20];
+    let mut int_len = 0;
+    loop {
+        int_digits[int_len] = b'0' + (int_part % 10) as u8;
+        int_part /= 10;
+        int_len += 1;
+        if int_part == 0 {
+            break;
+        }
+    }
+    for (dst, src) in buf.iter_mut().zip(int_digits[..int_len].iter().rev()) {
+        *dst = *src;
+    }
+    let mut len = int_len;
+    if frac_len > 0 {
+        buf[len] = b'.';
+        buf[len + 1..len + 1 + frac_len].copy_from_slice(&frac_digits[..frac_len]);
+        len += 1 + frac_len;
+    }
+    let digits = core::str::from_utf8(&buf[..len]).unwrap_or_else(|_| unreachable!());
+    // a small negative value which is rounded to zero is printed without a sign, like an integer zero.
+    let is_zero = buf[..len].iter().all(|&digit| matches!(digit, b'0' | b'.'));
+    f.pad_integral(is_nonnegative || is_zero, "", digits)
+}
+
+macro_rules! define_fixed_type {
+    {
+        $ident: ident, $raw: ty, $is_signed: literal, $mut_ref_ty_name: ident,
+        $visit_method: ident, $serialize_method: ident, $unexpected_variant: ident, $expecting: literal
+    } => {
+        impl<const INT: usize, const FRAC: usize> BitPiece for $ident<INT, FRAC>
+        where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+        {
+            const BITS: usize = check_fixed_bit_len(INT, FRAC, $is_signed);
+            const ZEROES: Self = Self::from_wide_unchecked(0);
+            const ONES: Self = Self::from_bits_unchecked(Self::MASK);
+            const MIN: Self = Self::from_wide_unchecked(Self::MIN_RAW);
+            const MAX: Self = Self::from_wide_unchecked(Self::MAX_RAW);
+            type Bits = <FixedBitLength<INT, FRAC> as AssociatedFixedStorage>::Storage;
+            type Converter = Self;
+            fn try_from_bits(bits: Self::Bits) -> Option<Self> {
+                <Self as BitPiece>::Converter::try_from_bits(bits)
+            }
+            fn from_bits(bits: Self::Bits) -> Self {
+                <Self as BitPiece>::Converter::from_bits(bits)
+            }
+            fn to_bits(self) -> Self::Bits {
+                <Self as BitPiece>::Converter::to_bits(self)
+            }
+        }
+        impl<const INT: usize, const FRAC: usize> BitPieceHasLayout for $ident<INT, FRAC>
+        where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+        {
+            const TYPE_NAME: &'static str = stringify!($ident);
+            const IS_SIGNED: bool = $is_signed;
+        }
+        impl<const INT: usize, const FRAC: usize> BitPieceVisit for $ident<INT, FRAC>
+        where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+        {
+            fn visit_as(&self, name: &str, visitor: &mut impl BitPieceVisitor) {
+                visitor.$visit_method(name, self.raw(), <Self as BitPiece>::BITS)
+            }
+        }
+        impl<const INT: usize, const FRAC: usize> BitPieceHasFields for $ident<INT, FRAC>
+        where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+        {
+            type Fields = Self;
+            fn from_fields(fields: Self::Fields) -> Self {
+                <Self as BitPiece>::Converter::from_fields(fields)
+            }
+            fn to_fields(self) -> Self::Fields {
+                <Self as BitPiece>::Converter::to_fields(self)
+            }
+        }
+        impl<const INT: usize, const FRAC: usize> BitPieceHasMutRef for $ident<INT, FRAC>
+        where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+        {
+            type MutRef<'s> = $mut_ref_ty_name<'s, INT, FRAC>;
+        }
+
+        impl<const INT: usize, const FRAC: usize> $ident<INT, FRAC>
+        where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+        {
+            /// a mask of the bit length of this type.
+            const MASK: u64 = extract_bits(u64::MAX, 0, <Self as BitPiece>::BITS);
+
+            /// the raw integer of the min value of this type.
+            const MIN_RAW: i128 = if $is_signed { -(1i128 << (<Self as BitPiece>::BITS - 1)) } else { 0 };
+
+            /// the raw integer of the max value of this type.
+            const MAX_RAW: i128 = if $is_signed { (1i128 << (<Self as BitPiece>::BITS - 1)) - 1 } else { Self::MASK as i128 };
+
+            pub const fn from_fields(fields: Self) -> Self {
+                fields
+            }
+            pub const fn to_fields(x: Self) -> Self {
+                x
+            }
+            pub const fn try_from_bits(bits: <Self as BitPiece>::Bits) -> Option<Self> {
+                // SAFETY: the bits are of the storage type of this type.
+                let bits = unsafe { storage_to_u64(bits) };
+                if bits & !Self::MASK != 0 {
+                    return None;
+                }
+                Some(Self::from_bits_unchecked(bits))
+            }
+            pub const fn from_bits(bits: <Self as BitPiece>::Bits) -> Self {
+                Self::try_from_bits(bits).unwrap()
+            }
+            pub const fn to_bits(self) -> <Self as BitPiece>::Bits {
+                // SAFETY: the bits are converted to the storage type of this type.
+                unsafe { storage_from_u64(self.to_u64_bits()) }
+            }
+            pub const fn const_eq(a: Self, b: Self) -> bool {
+                a.wide() == b.wide()
+            }
+
+            /// creates a value of this type from its raw integer, which represents the value `raw / 2^FRAC`.
+            ///
+            /// this function panics if the raw integer does not fit within the bit length of this type.
+            pub const fn from_raw(raw: $raw) -> Self {
+                Self::try_from_raw(raw).unwrap()
+            }
+
+            /// creates a value of this type from its raw integer, which represents the value `raw / 2^FRAC`.
+            ///
+            /// if the raw integer does not fit within the bit length of this type, returns `None`.
+            pub const fn try_from_raw(raw: $raw) -> Option<Self> {
+                Self::try_from_wide(raw as i128)
+            }
+
+            /// returns the raw integer of this value, which represents the value `raw / 2^FRAC`.
+            pub const fn raw(self) -> $raw {
+                self.wide() as $raw
+            }
+
+            /// returns the bits of this value, as a `u64`.
+            const fn to_u64_bits(self) -> u64 {
+                (self.wide() as u64) & Self::MASK
+            }
+
+            /// converts the given raw integer, which may be outside the range of this type, to this type.
+            const fn try_from_wide(raw: i128) -> Option<Self> {
+                if raw >= Self::MIN_RAW && raw <= Self::MAX_RAW {
+                    Some(Self::from_wide_unchecked(raw))
+                } else {
+                    None
+                }
+            }
+
+            /// converts the given raw integer to this type, wrapping around at the bit length of this type.
+            const fn wrapping_from_wide(raw: i128) -> Self {
+                Self::from_bits_unchecked((raw as u64) & Self::MASK)
+            }
+
+            /// converts the given raw integer to this type, clamping it to the range of this type.
+            const fn saturating_from_wide(raw: i128) -> Self {
+                if raw < Self::MIN_RAW {
+                    Self::MIN
+                } else if raw > Self::MAX_RAW {
+                    Self::MAX
+                } else {
+                    Self::from_wide_unchecked(raw)
+                }
+            }
+
+            /// converts the given float to this type, rounding it to the nearest representable value, with ties
+            /// rounded away from zero.
+            ///
+            /// values outside the range of this type are clamped to its min and max values, and `NaN` is converted to
+            /// zero, like with an `as` cast.
+            pub const fn from_f64(value: f64) -> Self {
+                Self::saturating_from_wide(round_to_i128(value * fixed_scale(FRAC)))
+            }
+
+            /// converts the given float to this type, rounding it to the nearest representable value, with ties
+            /// rounded away from zero.
+            ///
+            /// values outside the range of this type are clamped to its min and max values, and `NaN` is converted to
+            /// zero, like with an `as` cast.
+            pub const fn from_f32(value: f32) -> Self {
+                Self::from_f64(value as f64)
+            }
+
+            /// converts this value to a float, rounding it to the nearest representable float.
+            pub const fn to_f64(self) -> f64 {
+                self.wide() as f64 / fixed_scale(FRAC)
+            }
+
+            /// converts this value to a float, rounding it to the nearest representable float.
+            pub const fn to_f32(self) -> f32 {
+                self.to_f64() as f32
+            }
+
+            /// wrapping addition, wrapping around at the bit length of this type.
+            pub const fn wrapping_add(self, rhs: Self) -> Self {
+                Self::wrapping_from_wide(self.wide() + rhs.wide())
+            }
+
+            /// wrapping subtraction, wrapping around at the bit length of this type.
+            pub const fn wrapping_sub(self, rhs: Self) -> Self {
+                Self::wrapping_from_wide(self.wide() - rhs.wide())
+            }
+
+            /// wrapping multiplication, wrapping around at the bit length of this type.
+            ///
+            /// the product is rounded to the nearest representable value, with ties rounded up.
+            pub const fn wrapping_mul(self, rhs: Self) -> Self {
+                Self::wrapping_from_wide(self.wide_mul(rhs))
+            }
+
+            /// checked addition, returning `None` if the result does not fit within the range of this type.
+            pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+                Self::try_from_wide(self.wide() + rhs.wide())
+            }
+
+            /// checked subtraction, returning `None` if the result does not fit within the range of this type.
+            pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+                Self::try_from_wide(self.wide() - rhs.wide())
+            }
+
+            /// checked multiplication, returning `None` if the result does not fit within the range of this type.
+            ///
+            /// the product is rounded to the nearest representable value, with ties rounded up.
+            pub const fn checked_mul(self, rhs: Self) -> Option<Self> {
+                Self::try_from_wide(self.wide_mul(rhs))
+            }
+
+            /// saturating addition, clamping the result to the range of this type.
+            pub const fn saturating_add(self, rhs: Self) -> Self {
+                Self::saturating_from_wide(self.wide() + rhs.wide())
+            }
+
+            /// saturating subtraction, clamping the result to the range of this type.
+            pub const fn saturating_sub(self, rhs: Self) -> Self {
+                Self::saturating_from_wide(self.wide() - rhs.wide())
+            }
+
+            /// saturating multiplication, clamping the result to the range of this type.
+            ///
+            /// the product is rounded to the nearest representable value, with ties rounded up.
+            pub const fn saturating_mul(self, rhs: Self) -> Self {
+                Self::saturating_from_wide(self.wide_mul(rhs))
+            }
+
+            /// the value `2^(FRAC - 1)`, which is half of the last fractional bit. it is added to a product before
+            /// shifting it to round it to the nearest representable value.
+            const HALF_ULP: i128 = if FRAC == 0 { 0 } else { 1i128 << (FRAC - 1) };
+        }
+
+        impl<const INT: usize, const FRAC: usize> fmt::Display for $ident<INT, FRAC>
+        where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let raw = self.wide();
+                fmt_fixed_decimal(raw >= 0, raw.unsigned_abs(), FRAC, f)
+            }
+        }
+        impl<const INT: usize, const FRAC: usize> fmt::Debug for $ident<INT, FRAC>
+        where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(self, f)
+            }
+        }
+        impl<const INT: usize, const FRAC: usize> Default for $ident<INT, FRAC>
+        where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+        {
+            fn default() -> Self {
+                <Self as BitPiece>::ZEROES
+            }
+        }
+        impl<const INT: usize, const FRAC: usize> PartialEq for $ident<INT, FRAC>
+        where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+        {
+            fn eq(&self, other: &Self) -> bool {
+                self.wide() == other.wide()
+            }
+        }
+        impl<const INT: usize, const FRAC: usize> Eq for $ident<INT, FRAC> where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage
+        {
+        }
+        impl<const INT: usize, const FRAC: usize> PartialOrd for $ident<INT, FRAC>
+        where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+        {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl<const INT: usize, const FRAC: usize> Ord for $ident<INT, FRAC>
+        where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+        {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.wide().cmp(&other.wide())
+            }
+        }
+        impl<const INT: usize, const FRAC: usize> core::hash::Hash for $ident<INT, FRAC>
+        where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+        {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                self.wide().hash(state)
+            }
+        }
+        impl<const INT: usize, const FRAC: usize> fmt::Binary for $ident<INT, FRAC>
+        where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt_binary(self.to_u64_bits(), <Self as BitPiece>::BITS, f)
+            }
+        }
+        impl<const INT: usize, const FRAC: usize> fmt::Octal for $ident<INT, FRAC>
+        where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt_octal(self.to_u64_bits(), <Self as BitPiece>::BITS, f)
+            }
+        }
+        impl<const INT: usize, const FRAC: usize> fmt::LowerHex for $ident<INT, FRAC>
+        where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt_lower_hex(self.to_u64_bits(), <Self as BitPiece>::BITS, f)
+            }
+        }
+        impl<const INT: usize, const FRAC: usize> fmt::UpperHex for $ident<INT, FRAC>
+        where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt_upper_hex(self.to_u64_bits(), <Self as BitPiece>::BITS, f)
+            }
+        }
+
+        // fixed-point values are serialized as their raw integer, so that they round trip exactly.
+        #[cfg(feature = "serde")]
+        impl<const INT: usize, const FRAC: usize> serde::Serialize for $ident<INT, FRAC>
+        where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+        {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.$serialize_method(self.raw())
+            }
+        }
+        #[cfg(feature = "serde")]
+        impl<'de, const INT: usize, const FRAC: usize> serde::Deserialize<'de> for $ident<INT, FRAC>
+        where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+        {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let raw = <$raw as serde::Deserialize>::deserialize(deserializer)?;
+                Self::try_from_raw(raw).ok_or_else(|| {
+                    serde::de::Error::invalid_value(serde::de::Unexpected::$unexpected_variant(raw), &$expecting)
+                })
+            }
+        }
+
+        /// a mutable reference to a fixed-point value inside another bitpiece.
+        #[derive(Debug)]
+        pub struct $mut_ref_ty_name<'s, const INT: usize, const FRAC: usize>(pub BitsMut<'s>);
+        impl<'s, const INT: usize, const FRAC: usize> $mut_ref_ty_name<'s, INT, FRAC>
+        where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+        {
+            pub const fn new(storage: BitPieceStorageMutRef<'s>, start_bit_index: usize) -> Self {
+                Self(BitsMut::new(storage, start_bit_index))
+            }
+
+            pub const fn get(&self) -> $ident<INT, FRAC> {
+                $ident::from_bits_unchecked(self.0.get_bits(0, <$ident<INT, FRAC> as BitPiece>::BITS))
+            }
+
+            pub const fn set(&mut self, new_value: $ident<INT, FRAC>) {
+                self.0.set_bits(0, <$ident<INT, FRAC> as BitPiece>::BITS, new_value.to_u64_bits());
+            }
+        }
+        impl<'s, const INT: usize, const FRAC: usize> BitPieceMutRef<'s> for $mut_ref_ty_name<'s, INT, FRAC>
+        where
+            FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+        {
+            type BitPiece = $ident<INT, FRAC>;
+
+            fn new(storage: BitPieceStorageMutRef<'s>, start_bit_index: usize) -> Self {
+                Self::new(storage, start_bit_index)
+            }
+
+            fn get(&self) -> $ident<INT, FRAC> {
+                self.get()
+            }
+
+            fn set(&mut self, new_value: $ident<INT, FRAC>) {
+                self.set(new_value)
+            }
+        }
+    };
+}
+
+define_fixed_type! {
+    UFixed, u64, false, UFixedMutRef,
+    visit_unsigned, serialize_u64, Unsigned, "an unsigned raw fixed-point integer which fits in the bit length of the type"
+}
+define_fixed_type! {
+    SFixed, i64, true, SFixedMutRef,
+    visit_signed, serialize_i64, Signed, "a signed raw fixed-point integer which fits in the bit length of the type"
+}
+
+impl<const INT: usize, const FRAC: usize> UFixed<INT, FRAC>
+where
+    FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+{
+    /// converts the given raw bits, which are known to fit within the bit length of this type, to this type.
+    const fn from_bits_unchecked(bits: u64) -> Self {
+        Self::from_wide_unchecked(bits as i128)
+    }
+
+    /// creates a value of this type from its raw integer, which is known to be in the range of this type.
+    const fn from_wide_unchecked(raw: i128) -> Self {
+        // SAFETY: the raw integer is converted to the storage type of this type.
+        Self(unsafe { storage_from_u64(raw as u64) })
+    }
+
+    /// returns the raw integer of this value.
+    const fn wide(self) -> i128 {
+        // SAFETY: the value is of the storage type of this type.
+        unsafe { storage_to_u64(self.0) as i128 }
+    }
+
+    /// returns the raw integer of the product of the given values, rounded to `FRAC` fractional bits.
+    const fn wide_mul(self, rhs: Self) -> i128 {
+        let product = ((self.wide() as u128 * rhs.wide() as u128) + Self::HALF_ULP as u128) >> FRAC;
+        if product > i128::MAX as u128 {
+            // this can only happen for 64-bit types. keep the low 64 bits of the product for wrapping, while keeping
+            // it out of the range of this type.
+            (1i128 << 64) | (product as u64 as i128)
+        } else {
+            product as i128
+        }
+    }
+}
+
+impl<const INT: usize, const FRAC: usize> SFixed<INT, FRAC>
+where
+    FixedBitLength<INT, FRAC>: AssociatedFixedStorage,
+{
+    /// converts the given raw bits, which are known to fit within the bit length of this type, to this type.
+    const fn from_bits_unchecked(bits: u64) -> Self {
+        Self::from_wide_unchecked(sign_extend_bits(bits, <Self as BitPiece>::BITS) as i128)
+    }
+
+    /// creates a value of this type from its raw integer, which is known to be in the range of this type.
+    const fn from_wide_unchecked(raw: i128) -> Self {
+        // SAFETY: the raw integer is converted to the signed version of the storage type of this type, which truncates
+        // it to the two's complement of the storage type.
+        Self(unsafe { storage_from_u64(raw as u64) })
+    }
+
+    /// returns the raw integer of this value.
+    const fn wide(self) -> i128 {
+        // SAFETY: the value is of the signed version of the storage type of this type.
+        let bits = unsafe { storage_to_u64(self.0) };
+        sign_extend_bits(
+            extract_bits(bits, 0, <Self as BitPiece>::BITS),
+            <Self as BitPiece>::BITS,
+        ) as i128
+    }
+
+    /// returns the raw integer of the product of the given values, rounded to `FRAC` fractional bits.
+    const fn wide_mul(self, rhs: Self) -> i128 {
+        (self.wide() * rhs.wide() + Self::HALF_ULP) >> FRAC
+    }
+}
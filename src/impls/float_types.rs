@@ -0,0 +1,75 @@
+use crate::*;
+
+macro_rules! impl_bitpiece_for_float_types {
+    { $($bit_len: literal),+ $(,)? } => {
+        $(
+            paste::paste! {
+                pub struct [<BitPieceF $bit_len Converter>];
+                impl [<BitPieceF $bit_len Converter>] {
+                    pub const fn from_fields(fields: [<f $bit_len>]) -> [<f $bit_len>] {
+                        fields
+                    }
+                    pub const fn to_fields(x: [<f $bit_len>]) -> [<f $bit_len>] {
+                        x
+                    }
+                    pub const fn try_from_bits(bits: [<u $bit_len>]) -> Option<[<f $bit_len>]> {
+                        Some([<f $bit_len>]::from_bits(bits))
+                    }
+                    pub const fn from_bits(bits: [<u $bit_len>]) -> [<f $bit_len>] {
+                        [<f $bit_len>]::from_bits(bits)
+                    }
+                    pub const fn to_bits(x: [<f $bit_len>]) -> [<u $bit_len>] {
+                        x.to_bits()
+                    }
+                    /// compares the bit patterns of the given values, so unlike `==`, a `NaN` is equal to itself, and
+                    /// `0.0` is not equal to `-0.0`.
+                    pub const fn const_eq(a: [<f $bit_len>], b: [<f $bit_len>]) -> bool {
+                        a.to_bits() == b.to_bits()
+                    }
+                }
+                /// floats are stored by their IEEE 754 bit pattern.
+                impl BitPiece for [<f $bit_len>] {
+                    const BITS: usize = $bit_len;
+                    const ZEROES: Self = 0.0;
+                    const ONES: Self = [<f $bit_len>]::from_bits(!0);
+                    const MIN: Self = [<f $bit_len>]::MIN;
+                    const MAX: Self = [<f $bit_len>]::MAX;
+                    type Bits = [<u $bit_len>];
+                    type Converter = [<BitPieceF $bit_len Converter>];
+                    fn try_from_bits(bits: Self::Bits) -> Option<Self> {
+                        <Self as BitPiece>::Converter::try_from_bits(bits)
+                    }
+                    fn from_bits(bits: Self::Bits) -> Self {
+                        <Self as BitPiece>::Converter::from_bits(bits)
+                    }
+                    fn to_bits(self) -> Self::Bits {
+                        <Self as BitPiece>::Converter::to_bits(self)
+                    }
+                }
+                impl BitPieceHasMutRef for [<f $bit_len>] {
+                    type MutRef<'s> = [<BitPieceF $bit_len MutRef>]<'s>;
+                }
+                impl BitPieceHasLayout for [<f $bit_len>] {
+                    const TYPE_NAME: &'static str = stringify!([<f $bit_len>]);
+                }
+                impl BitPieceVisit for [<f $bit_len>] {
+                    fn visit_as(&self, name: &str, visitor: &mut impl BitPieceVisitor) {
+                        visitor.visit_float(name, *self as f64, $bit_len)
+                    }
+                }
+                impl BitPieceHasFields for [<f $bit_len>] {
+                    type Fields = Self;
+                    fn from_fields(fields: Self::Fields) -> Self {
+                        <Self as BitPiece>::Converter::from_fields(fields)
+                    }
+                    fn to_fields(self) -> Self::Fields {
+                        <Self as BitPiece>::Converter::to_fields(self)
+                    }
+                }
+                bitpiece_check_full_impl! { [<f $bit_len>], true }
+                bitpiece_define_mut_ref_type! { [<f $bit_len>], [<BitPieceF $bit_len MutRef>], pub }
+            }
+        )+
+    };
+}
+impl_bitpiece_for_float_types! { 32, 64 }
@@ -49,6 +49,31 @@ macro_rules! impl_bitpiece_for_unsigned_int_types {
                 impl BitPieceHasMutRef for [<u $bit_len>] {
                     type MutRef<'s> = [<BitPieceU $bit_len MutRef>]<'s>;
                 }
+                impl BitPieceHasLayout for [<u $bit_len>] {
+                    const TYPE_NAME: &'static str = stringify!([<u $bit_len>]);
+                }
+                impl BitInt for [<u $bit_len>] {
+                    type Primitive = Self;
+                    const MIN_VALUE: Self = Self::MIN;
+                    const MAX_VALUE: Self = Self::MAX;
+                    fn to_i128(self) -> i128 {
+                        self as i128
+                    }
+                    fn as_u64_bits(self) -> u64 {
+                        self as u64
+                    }
+                    fn try_from_i128(value: i128) -> Option<Self> {
+                        Self::try_from(value).ok()
+                    }
+                    fn get(&self) -> Self {
+                        *self
+                    }
+                }
+                impl BitPieceVisit for [<u $bit_len>] {
+                    fn visit_as(&self, name: &str, visitor: &mut impl BitPieceVisitor) {
+                        visitor.visit_unsigned(name, *self as u64, $bit_len)
+                    }
+                }
                 impl BitPieceHasFields for [<u $bit_len>] {
                     type Fields = Self;
                     fn from_fields(fields: Self::Fields) -> Self {
@@ -60,6 +85,8 @@ macro_rules! impl_bitpiece_for_unsigned_int_types {
                 }
                 bitpiece_check_full_impl! { [<u $bit_len>], true }
                 bitpiece_define_mut_ref_type! { [<u $bit_len>], [<BitPieceU $bit_len MutRef>], pub }
+                impl_bitpiece_for_wrapping! { [<u $bit_len>] }
+                impl_bitpiece_for_bit_wrappers! { [<u $bit_len>] }
             }
         )+
     };
@@ -112,6 +139,32 @@ macro_rules! impl_bitpiece_for_signed_int_types {
                 impl BitPieceHasMutRef for [<i $bit_len>] {
                     type MutRef<'s> = [<BitPieceI $bit_len MutRef>]<'s>;
                 }
+                impl BitPieceHasLayout for [<i $bit_len>] {
+                    const TYPE_NAME: &'static str = stringify!([<i $bit_len>]);
+                    const IS_SIGNED: bool = true;
+                }
+                impl BitInt for [<i $bit_len>] {
+                    type Primitive = Self;
+                    const MIN_VALUE: Self = Self::MIN;
+                    const MAX_VALUE: Self = Self::MAX;
+                    fn to_i128(self) -> i128 {
+                        self as i128
+                    }
+                    fn as_u64_bits(self) -> u64 {
+                        self as u64
+                    }
+                    fn try_from_i128(value: i128) -> Option<Self> {
+                        Self::try_from(value).ok()
+                    }
+                    fn get(&self) -> Self {
+                        *self
+                    }
+                }
+                impl BitPieceVisit for [<i $bit_len>] {
+                    fn visit_as(&self, name: &str, visitor: &mut impl BitPieceVisitor) {
+                        visitor.visit_signed(name, *self as i64, $bit_len)
+                    }
+                }
                 impl BitPieceHasFields for [<i $bit_len>] {
                     type Fields = Self;
                     fn from_fields(fields: Self::Fields) -> Self {
@@ -123,6 +176,8 @@ macro_rules! impl_bitpiece_for_signed_int_types {
                 }
                 bitpiece_check_full_impl! { [<i $bit_len>], true }
                 bitpiece_define_mut_ref_type! { [<i $bit_len>], [<BitPieceI $bit_len MutRef>], pub }
+                impl_bitpiece_for_wrapping! { [<i $bit_len>] }
+                impl_bitpiece_for_bit_wrappers! { [<i $bit_len>] }
             }
         )+
     };
@@ -1,8 +1,345 @@
+/// implements the standard comparison, hashing and formatting traits for a generic bitpiece integer type, such as
+/// `UBits<N>`, according to the value represented by it.
+macro_rules! impl_bit_int_std_traits {
+    { $ty: ident } => {
+        impl<const N: usize> Default for $ty<N>
+        where
+            BitLength<N>: AssociatedStorage,
+        {
+            fn default() -> Self {
+                <Self as BitPiece>::ZEROES
+            }
+        }
+        impl<const N: usize> PartialEq for $ty<N>
+        where
+            BitLength<N>: AssociatedStorage,
+        {
+            fn eq(&self, other: &Self) -> bool {
+                self.to_i128() == other.to_i128()
+            }
+        }
+        impl<const N: usize> Eq for $ty<N> where BitLength<N>: AssociatedStorage {}
+        impl<const N: usize> PartialOrd for $ty<N>
+        where
+            BitLength<N>: AssociatedStorage,
+        {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl<const N: usize> Ord for $ty<N>
+        where
+            BitLength<N>: AssociatedStorage,
+        {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.to_i128().cmp(&other.to_i128())
+            }
+        }
+        impl<const N: usize> core::hash::Hash for $ty<N>
+        where
+            BitLength<N>: AssociatedStorage,
+        {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                self.to_i128().hash(state)
+            }
+        }
+        impl<const N: usize> core::fmt::Display for $ty<N>
+        where
+            BitLength<N>: AssociatedStorage,
+        {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Display::fmt(&self.to_i128(), f)
+            }
+        }
+        impl<const N: usize> core::fmt::Debug for $ty<N>
+        where
+            BitLength<N>: AssociatedStorage,
+        {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Debug::fmt(&self.to_i128(), f)
+            }
+        }
+    };
+}
+
+/// implements the binary, octal and hex formatting traits for a generic bitpiece integer type, such as `UBits<N>`,
+/// padded to its bit length.
+macro_rules! impl_bitpiece_fmt_traits {
+    { $ty: ident } => {
+        impl_bitpiece_fmt_traits! { $ty, Binary, fmt_binary }
+        impl_bitpiece_fmt_traits! { $ty, Octal, fmt_octal }
+        impl_bitpiece_fmt_traits! { $ty, LowerHex, fmt_lower_hex }
+        impl_bitpiece_fmt_traits! { $ty, UpperHex, fmt_upper_hex }
+    };
+    { $ty: ident, $trait: ident, $fmt_fn: ident } => {
+        impl<const N: usize> core::fmt::$trait for $ty<N>
+        where
+            BitLength<N>: AssociatedStorage,
+        {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                $fmt_fn(BitPiece::to_bits(*self).to_u64(), N, f)
+            }
+        }
+    };
+}
+
+/// implements an arithmetic operator trait and its assigning variant for a generic bitpiece integer type, such as
+/// `UBits<N>`.
+///
+/// like the operators of the primitive integer types, this panics on overflow when debug assertions are enabled,
+/// and wraps around otherwise.
+macro_rules! impl_bitpiece_arith_op {
+    {
+        $ty: ident, $rhs: ty, $trait: ident, $method: ident, $assign_trait: ident, $assign_method: ident,
+        $checked_method: ident, $wrapping_method: ident, $overflow_msg: literal
+    } => {
+        impl<const N: usize> core::ops::$trait<$rhs> for $ty<N>
+        where
+            BitLength<N>: AssociatedStorage,
+        {
+            type Output = Self;
+            #[track_caller]
+            fn $method(self, rhs: $rhs) -> Self {
+                if cfg!(debug_assertions) {
+                    match self.$checked_method(rhs) {
+                        Some(result) => result,
+                        None => panic!($overflow_msg),
+                    }
+                } else {
+                    self.$wrapping_method(rhs)
+                }
+            }
+        }
+        impl<const N: usize> core::ops::$assign_trait<$rhs> for $ty<N>
+        where
+            BitLength<N>: AssociatedStorage,
+        {
+            #[track_caller]
+            fn $assign_method(&mut self, rhs: $rhs) {
+                *self = core::ops::$trait::$method(*self, rhs);
+            }
+        }
+    };
+}
+
+/// implements the arithmetic and shift operator traits for a generic bitpiece integer type, such as `UBits<N>`.
+macro_rules! impl_bitpiece_arith_ops {
+    { $ty: ident } => {
+        impl_bitpiece_arith_op! {
+            $ty, Self, Add, add, AddAssign, add_assign, checked_add, wrapping_add, "attempt to add with overflow"
+        }
+        impl_bitpiece_arith_op! {
+            $ty, Self, Sub, sub, SubAssign, sub_assign, checked_sub, wrapping_sub, "attempt to subtract with overflow"
+        }
+        impl_bitpiece_arith_op! {
+            $ty, Self, Mul, mul, MulAssign, mul_assign, checked_mul, wrapping_mul, "attempt to multiply with overflow"
+        }
+        impl_bitpiece_arith_op! {
+            $ty, u32, Shl, shl, ShlAssign, shl_assign, checked_shl, wrapping_shl, "attempt to shift left with overflow"
+        }
+        impl_bitpiece_arith_op! {
+            $ty, u32, Shr, shr, ShrAssign, shr_assign, checked_shr, wrapping_shr, "attempt to shift right with overflow"
+        }
+    };
+}
+
+/// the description of the values expected when deserializing a bitpiece integer type, used in error messages.
+#[cfg(feature = "serde")]
+struct ExpectedBitInt<T>(core::marker::PhantomData<T>);
+#[cfg(feature = "serde")]
+impl<T: crate::BitInt> ExpectedBitInt<T> {
+    const fn new() -> Self {
+        Self(core::marker::PhantomData)
+    }
+}
+#[cfg(feature = "serde")]
+impl<T: crate::BitInt> serde::de::Expected for ExpectedBitInt<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let signedness = if T::IS_SIGNED {
+            "a signed"
+        } else {
+            "an unsigned"
+        };
+        write!(f, "{} integer which fits in {} bits", signedness, T::BITS)
+    }
+}
+
+/// implements `BitPiece` and the related traits for `Wrapping<T>` of the given bitpiece type, forwarding all
+/// conversions to the converter of the inner type.
+macro_rules! impl_bitpiece_for_wrapping {
+    { $ty: ty } => {
+        impl BitPieceWrappingConverter<$ty> {
+            pub const fn from_fields(fields: core::num::Wrapping<$ty>) -> core::num::Wrapping<$ty> {
+                fields
+            }
+            pub const fn to_fields(x: core::num::Wrapping<$ty>) -> core::num::Wrapping<$ty> {
+                x
+            }
+            pub const fn try_from_bits(bits: <$ty as BitPiece>::Bits) -> Option<core::num::Wrapping<$ty>> {
+                match <$ty as BitPiece>::Converter::try_from_bits(bits) {
+                    Some(value) => Some(core::num::Wrapping(value)),
+                    None => None,
+                }
+            }
+            pub const fn from_bits(bits: <$ty as BitPiece>::Bits) -> core::num::Wrapping<$ty> {
+                core::num::Wrapping(<$ty as BitPiece>::Converter::from_bits(bits))
+            }
+            pub const fn to_bits(x: core::num::Wrapping<$ty>) -> <$ty as BitPiece>::Bits {
+                <$ty as BitPiece>::Converter::to_bits(x.0)
+            }
+            pub const fn const_eq(a: core::num::Wrapping<$ty>, b: core::num::Wrapping<$ty>) -> bool {
+                <$ty as BitPiece>::Converter::const_eq(a.0, b.0)
+            }
+        }
+        impl BitPiece for core::num::Wrapping<$ty> {
+            const BITS: usize = <$ty as BitPiece>::BITS;
+            const ZEROES: Self = core::num::Wrapping(<$ty as BitPiece>::ZEROES);
+            const ONES: Self = core::num::Wrapping(<$ty as BitPiece>::ONES);
+            const MIN: Self = core::num::Wrapping(<$ty as BitPiece>::MIN);
+            const MAX: Self = core::num::Wrapping(<$ty as BitPiece>::MAX);
+            type Bits = <$ty as BitPiece>::Bits;
+            type Converter = BitPieceWrappingConverter<$ty>;
+            fn try_from_bits(bits: Self::Bits) -> Option<Self> {
+                <Self as BitPiece>::Converter::try_from_bits(bits)
+            }
+            fn from_bits(bits: Self::Bits) -> Self {
+                <Self as BitPiece>::Converter::from_bits(bits)
+            }
+            fn to_bits(self) -> Self::Bits {
+                <Self as BitPiece>::Converter::to_bits(self)
+            }
+        }
+        impl BitPieceHasMutRef for core::num::Wrapping<$ty> {
+            type MutRef<'s> = BitPieceWrappingMutRef<'s, $ty>;
+        }
+        impl BitPieceHasLayout for core::num::Wrapping<$ty> {
+            const TYPE_NAME: &'static str = concat!("Wrapping<", stringify!($ty), ">");
+            const IS_SIGNED: bool = <$ty as BitPieceHasLayout>::IS_SIGNED;
+        }
+        impl BitPieceVisit for core::num::Wrapping<$ty> {
+            fn visit_as(&self, name: &str, visitor: &mut impl BitPieceVisitor) {
+                self.0.visit_as(name, visitor)
+            }
+        }
+        impl BitPieceHasFields for core::num::Wrapping<$ty> {
+            type Fields = Self;
+            fn from_fields(fields: Self::Fields) -> Self {
+                <Self as BitPiece>::Converter::from_fields(fields)
+            }
+            fn to_fields(self) -> Self::Fields {
+                <Self as BitPiece>::Converter::to_fields(self)
+            }
+        }
+        impl<'s> BitPieceWrappingMutRef<'s, $ty> {
+            pub const fn get(&self) -> core::num::Wrapping<$ty> {
+                let bits = self.0.get_bits(0, <$ty as BitPiece>::BITS) as <$ty as BitPiece>::Bits;
+                BitPieceWrappingConverter::<$ty>::from_bits(bits)
+            }
+
+            pub const fn set(&mut self, new_value: core::num::Wrapping<$ty>) {
+                let bits = BitPieceWrappingConverter::<$ty>::to_bits(new_value);
+                self.0.set_bits(0, <$ty as BitPiece>::BITS, bits as u64);
+            }
+        }
+        impl<'s> BitPieceMutRef<'s> for BitPieceWrappingMutRef<'s, $ty> {
+            type BitPiece = core::num::Wrapping<$ty>;
+
+            fn new(storage: BitPieceStorageMutRef<'s>, start_bit_index: usize) -> Self {
+                Self::new(storage, start_bit_index)
+            }
+
+            fn get(&self) -> core::num::Wrapping<$ty> {
+                self.get()
+            }
+
+            fn set(&mut self, new_value: core::num::Wrapping<$ty>) {
+                self.set(new_value)
+            }
+        }
+        bitpiece_check_full_impl! { core::num::Wrapping<$ty>, true }
+    };
+}
+
+/// implements the const conversion functions of a bit wrapper type, such as `Inverted<T>`, for the given bitpiece type,
+/// which the const accessors of struct fields call. the traits are implemented generically, in `bit_wrappers.rs`. the
+/// stored bits are the bits of the inner value, transformed by the given function, which must be its own inverse.
+macro_rules! impl_bitpiece_for_bit_wrapper {
+    { $wrapper: ident, $mut_ref_ty_name: ident, $transform: ident, $ty: ty } => {
+        impl $wrapper<$ty> {
+            pub const fn from_fields(fields: Self) -> Self {
+                fields
+            }
+            pub const fn to_fields(x: Self) -> Self {
+                x
+            }
+            pub const fn try_from_bits(bits: <$ty as BitPiece>::Bits) -> Option<Self> {
+                let bits = bits as u64;
+                if extract_bits(bits, 0, <$ty as BitPiece>::BITS) != bits {
+                    return None;
+                }
+                let inner_bits = $transform(bits, <$ty as BitPiece>::BITS) as <$ty as BitPiece>::Bits;
+                match <$ty as BitPiece>::Converter::try_from_bits(inner_bits) {
+                    Some(value) => Some(Self(value)),
+                    None => None,
+                }
+            }
+            pub const fn from_bits(bits: <$ty as BitPiece>::Bits) -> Self {
+                Self::try_from_bits(bits).unwrap()
+            }
+            pub const fn to_bits(x: Self) -> <$ty as BitPiece>::Bits {
+                let inner_bits = <$ty as BitPiece>::Converter::to_bits(x.0) as u64;
+                $transform(inner_bits, <$ty as BitPiece>::BITS) as <$ty as BitPiece>::Bits
+            }
+            pub const fn const_eq(a: Self, b: Self) -> bool {
+                <$ty as BitPiece>::Converter::const_eq(a.0, b.0)
+            }
+        }
+        impl<'s> $mut_ref_ty_name<'s, $ty> {
+            pub const fn get(&self) -> $wrapper<$ty> {
+                let bits = self.0.get_bits(0, <$ty as BitPiece>::BITS) as <$ty as BitPiece>::Bits;
+                $wrapper::<$ty>::from_bits(bits)
+            }
+
+            pub const fn set(&mut self, new_value: $wrapper<$ty>) {
+                let bits = $wrapper::<$ty>::to_bits(new_value);
+                self.0.set_bits(0, <$ty as BitPiece>::BITS, bits as u64);
+            }
+        }
+        bitpiece_check_full_impl! { $wrapper<$ty>, true }
+    };
+}
+
+/// implements the const conversion functions of `Inverted<T>` and `Reversed<T>` for the given bitpiece type.
+macro_rules! impl_bitpiece_for_bit_wrappers {
+    { $ty: ty } => {
+        impl_bitpiece_for_bit_wrapper! { Inverted, InvertedMutRef, invert_bits, $ty }
+        impl_bitpiece_for_bit_wrapper! { Reversed, ReversedMutRef, reverse_bits, $ty }
+    };
+}
+
 mod b_types;
+mod biased;
+mod bit_wrappers;
 mod bool;
+mod char;
+mod conversions;
+mod encoded_ints;
+mod fixed;
+mod float_types;
 mod int_types;
+mod non_zero_types;
 mod sb_types;
+mod wrapping;
 pub use b_types::*;
+pub use biased::*;
+pub use bit_wrappers::*;
 pub use bool::*;
+pub use char::*;
+pub use conversions::*;
+pub use encoded_ints::*;
+pub use fixed::*;
+pub use float_types::*;
 pub use int_types::*;
+pub use non_zero_types::*;
 pub use sb_types::*;
+pub use wrapping::*;
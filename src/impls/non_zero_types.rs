@@ -0,0 +1,90 @@
+use core::num::*;
+
+use crate::*;
+
+macro_rules! impl_bitpiece_for_non_zero_types {
+    { $($bit_len: literal),+ $(,)? } => {
+        $(
+            paste::paste! {
+                impl_bitpiece_for_non_zero_type! {
+                    $bit_len, [<NonZeroU $bit_len>], [<u $bit_len>], false, visit_unsigned, u64
+                }
+                impl_bitpiece_for_non_zero_type! {
+                    $bit_len, [<NonZeroI $bit_len>], [<i $bit_len>], true, visit_signed, i64
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_bitpiece_for_non_zero_type {
+    { $bit_len: literal, $ty: tt, $int_ty: tt, $is_signed: literal, $visit_method: ident, $visit_ty: ty } => {
+        paste::paste! {
+            pub struct [<BitPiece $ty Converter>];
+            impl [<BitPiece $ty Converter>] {
+                pub const fn from_fields(fields: $ty) -> $ty {
+                    fields
+                }
+                pub const fn to_fields(x: $ty) -> $ty {
+                    x
+                }
+                pub const fn try_from_bits(bits: [<u $bit_len>]) -> Option<$ty> {
+                    $ty::new(bits as $int_ty)
+                }
+                pub const fn from_bits(bits: [<u $bit_len>]) -> $ty {
+                    Self::try_from_bits(bits).unwrap()
+                }
+                pub const fn to_bits(x: $ty) -> [<u $bit_len>] {
+                    x.get() as [<u $bit_len>]
+                }
+                pub const fn const_eq(a: $ty, b: $ty) -> bool {
+                    a.get() == b.get()
+                }
+            }
+            impl BitPiece for $ty {
+                const BITS: usize = $bit_len;
+                // a value with all zero bits is not valid, so like with enums, the min value is used instead.
+                const ZEROES: Self = Self::MIN;
+                const ONES: Self = <Self as BitPiece>::Converter::from_bits(!0);
+                const MIN: Self = $ty::MIN;
+                const MAX: Self = $ty::MAX;
+                type Bits = [<u $bit_len>];
+                type Converter = [<BitPiece $ty Converter>];
+                fn try_from_bits(bits: Self::Bits) -> Option<Self> {
+                    <Self as BitPiece>::Converter::try_from_bits(bits)
+                }
+                fn from_bits(bits: Self::Bits) -> Self {
+                    <Self as BitPiece>::Converter::from_bits(bits)
+                }
+                fn to_bits(self) -> Self::Bits {
+                    <Self as BitPiece>::Converter::to_bits(self)
+                }
+            }
+            impl BitPieceHasMutRef for $ty {
+                type MutRef<'s> = [<BitPiece $ty MutRef>]<'s>;
+            }
+            impl BitPieceHasLayout for $ty {
+                const TYPE_NAME: &'static str = stringify!($ty);
+                const IS_SIGNED: bool = $is_signed;
+            }
+            impl BitPieceVisit for $ty {
+                fn visit_as(&self, name: &str, visitor: &mut impl BitPieceVisitor) {
+                    visitor.$visit_method(name, self.get() as $visit_ty, $bit_len)
+                }
+            }
+            impl BitPieceHasFields for $ty {
+                type Fields = Self;
+                fn from_fields(fields: Self::Fields) -> Self {
+                    <Self as BitPiece>::Converter::from_fields(fields)
+                }
+                fn to_fields(self) -> Self::Fields {
+                    <Self as BitPiece>::Converter::to_fields(self)
+                }
+            }
+            bitpiece_check_full_impl! { $ty, false }
+            bitpiece_define_mut_ref_type! { $ty, [<BitPiece $ty MutRef>], pub }
+        }
+    };
+}
+
+impl_bitpiece_for_non_zero_types! { 8, 16, 32, 64 }
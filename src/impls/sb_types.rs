@@ -1,67 +1,368 @@
 use crate::*;
 
-macro_rules! sb_type_impl_from_int_type {
-    { $sb_type_ident: ident, $sb_type_storage_signed: ty, $uint_type: ty } => {
-        impl From<$uint_type> for $sb_type_ident {
-            fn from(x: $uint_type) -> Self {
-                Self::new(x as $sb_type_storage_signed)
+#[cfg(feature = "serde")]
+use super::ExpectedBitInt;
+
+/// a signed integer type with a bit length of `N`, using two's complement.
+///
+/// this is the generic form of the `SB1`..`SB64` types, which allows code to be generic over the bit length. generic
+/// code can use the [`BitPiece`] and [`BitInt`] traits, which provide the same constructors and arithmetic methods as
+/// the inherent const functions of the concrete types.
+#[derive(Clone, Copy)]
+pub struct SBits<const N: usize>(
+    <<BitLength<N> as AssociatedStorage>::Storage as BitStorage>::Signed,
+)
+where
+    BitLength<N>: AssociatedStorage;
+
+/// a mutable reference to a [`SBits`] value inside another bitpiece.
+#[derive(Debug)]
+pub struct SBitsMutRef<'s, const N: usize>(pub BitsMut<'s>);
+impl<'s, const N: usize> SBitsMutRef<'s, N> {
+    pub const fn new(storage: BitPieceStorageMutRef<'s>, start_bit_index: usize) -> Self {
+        Self(BitsMut::new(storage, start_bit_index))
+    }
+}
+
+impl<const N: usize> BitPiece for SBits<N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    const BITS: usize = N;
+    const ZEROES: Self = Self(
+        <<<BitLength<N> as AssociatedStorage>::Storage as BitStorage>::Signed as BitPiece>::ZEROES,
+    );
+    const ONES: Self = Self(
+        <<<BitLength<N> as AssociatedStorage>::Storage as BitStorage>::Signed as BitPiece>::ONES,
+    );
+    const MIN: Self = Self(<BitLength<N> as AssociatedStorage>::SIGNED_MIN);
+    const MAX: Self = Self(<BitLength<N> as AssociatedStorage>::SIGNED_MAX);
+    type Bits = <BitLength<N> as AssociatedStorage>::Storage;
+    type Converter = Self;
+    fn try_from_bits(bits: Self::Bits) -> Option<Self> {
+        let bits = bits.to_u64();
+        let mask = <BitLength<N> as AssociatedStorage>::MASK.to_u64();
+        // sign extend according to the sign bit of this type.
+        let value = if (bits >> (N - 1)) & 1 != 0 {
+            (bits | !mask) as i64
+        } else {
+            bits as i64
+        };
+        Self::try_from_i128(value.into())
+    }
+    fn from_bits(bits: Self::Bits) -> Self {
+        <Self as BitPiece>::try_from_bits(bits).unwrap()
+    }
+    fn to_bits(self) -> Self::Bits {
+        let bits = self.0.as_u64_bits() & <BitLength<N> as AssociatedStorage>::MASK.to_u64();
+        BitStorage::from_u64(bits).unwrap()
+    }
+}
+impl<const N: usize> BitPieceHasMutRef for SBits<N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    type MutRef<'s> = SBitsMutRef<'s, N>;
+}
+impl<'s, const N: usize> BitPieceMutRef<'s> for SBitsMutRef<'s, N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    type BitPiece = SBits<N>;
+
+    fn new(storage: BitPieceStorageMutRef<'s>, start_bit_index: usize) -> Self {
+        Self::new(storage, start_bit_index)
+    }
+
+    fn get(&self) -> SBits<N> {
+        let bits = self.0.get_bits(0, N);
+        <SBits<N> as BitPiece>::from_bits(BitStorage::from_u64(bits).unwrap())
+    }
+
+    fn set(&mut self, new_value: SBits<N>) {
+        self.0.set_bits(0, N, BitPiece::to_bits(new_value).to_u64())
+    }
+}
+impl<const N: usize> BitPieceHasLayout for SBits<N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    const TYPE_NAME: &'static str = SB_TYPE_NAMES[N];
+    const IS_SIGNED: bool = true;
+}
+impl<const N: usize> BitInt for SBits<N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    type Primitive = <<BitLength<N> as AssociatedStorage>::Storage as BitStorage>::Signed;
+    const MIN_VALUE: Self::Primitive = <Self as BitPiece>::MIN.0;
+    const MAX_VALUE: Self::Primitive = <Self as BitPiece>::MAX.0;
+    fn to_i128(self) -> i128 {
+        self.0.into()
+    }
+    fn as_u64_bits(self) -> u64 {
+        self.0.as_u64_bits()
+    }
+    fn try_from_i128(value: i128) -> Option<Self> {
+        if value < Self::MIN_VALUE.into() || value > Self::MAX_VALUE.into() {
+            return None;
+        }
+        BitInt::try_from_i128(value).map(Self)
+    }
+    fn get(&self) -> Self::Primitive {
+        self.0
+    }
+}
+impl<const N: usize> BitPieceVisit for SBits<N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    fn visit_as(&self, name: &str, visitor: &mut impl BitPieceVisitor) {
+        visitor.visit_signed(name, self.to_i128() as i64, N)
+    }
+}
+impl<const N: usize> BitPieceHasFields for SBits<N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    type Fields = Self;
+    fn from_fields(fields: Self::Fields) -> Self {
+        fields
+    }
+    fn to_fields(self) -> Self::Fields {
+        self
+    }
+}
+impl<const N: usize> core::str::FromStr for SBits<N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    type Err = ParseBitIntError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_i128(parse_signed(s)?.into()).ok_or(ParseBitIntError::OutOfRange)
+    }
+}
+impl_bit_int_std_traits! { SBits }
+impl_bitpiece_fmt_traits! { SBits }
+impl_bitpiece_arith_ops! { SBits }
+impl<const N: usize> core::ops::Neg for SBits<N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    type Output = Self;
+    #[track_caller]
+    fn neg(self) -> Self {
+        if cfg!(debug_assertions) {
+            match self.checked_neg() {
+                Some(result) => result,
+                None => panic!("attempt to negate with overflow"),
             }
+        } else {
+            self.wrapping_neg()
         }
     }
 }
 
-macro_rules! sb_type_impl_from_int_types {
-    { $sb_type_ident: ident, $sb_type_storage_signed: ty } => {
-        sb_type_impl_from_int_type! { $sb_type_ident, $sb_type_storage_signed, u8 }
-        sb_type_impl_from_int_type! { $sb_type_ident, $sb_type_storage_signed, u16 }
-        sb_type_impl_from_int_type! { $sb_type_ident, $sb_type_storage_signed, u32 }
-        sb_type_impl_from_int_type! { $sb_type_ident, $sb_type_storage_signed, u64 }
-        sb_type_impl_from_int_type! { $sb_type_ident, $sb_type_storage_signed, i8 }
-        sb_type_impl_from_int_type! { $sb_type_ident, $sb_type_storage_signed, i16 }
-        sb_type_impl_from_int_type! { $sb_type_ident, $sb_type_storage_signed, i32 }
-        sb_type_impl_from_int_type! { $sb_type_ident, $sb_type_storage_signed, i64 }
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for SBits<N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(self.to_i128() as i64)
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for SBits<N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = <i64 as serde::Deserialize>::deserialize(deserializer)?;
+        Self::try_from_i128(value.into()).ok_or_else(|| {
+            serde::de::Error::invalid_value(
+                serde::de::Unexpected::Signed(value),
+                &ExpectedBitInt::<Self>::new(),
+            )
+        })
     }
 }
 
-macro_rules! define_sb_type {
-    { $bit_len: literal, $ident: ident, $storage: ty, $storage_signed: ty, $mut_ref_ty_name: ident } => {
-        /// a type used to represent a field with a specific amount of bits.
-        #[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
-        pub struct $ident($storage_signed);
-        impl BitPiece for $ident {
-            const BITS: usize = $bit_len;
-            const ZEROES: Self = Self(0);
-            const ONES: Self = Self::from_bits(Self::STORAGE_MASK);
-            const MIN: Self = Self(((1 as $storage) << ($bit_len - 1)).wrapping_neg() as $storage_signed);
-            const MAX: Self = Self(((1 as $storage) << ($bit_len - 1)).wrapping_sub(1) as $storage_signed);
-            type Bits = $storage;
-            type Converter = Self;
-            fn try_from_bits(bits: Self::Bits) -> Option<Self> {
-                <Self as BitPiece>::Converter::try_from_bits(bits)
-            }
-            fn from_bits(bits: Self::Bits) -> Self {
-                <Self as BitPiece>::Converter::from_bits(bits)
-            }
-            fn to_bits(self) -> Self::Bits {
-                <Self as BitPiece>::Converter::to_bits(self)
+macro_rules! sb_type_impl_arith {
+    { $bit_len: literal, $sb_type_ident: ident, $sb_type_storage: ty, $sb_type_storage_signed: ty } => {
+        impl $sb_type_ident {
+            /// creates a new instance of this bitfield type with the given value, truncated to the bit length of this
+            /// type and sign extended.
+            pub const fn wrapping_new(value: $sb_type_storage_signed) -> Self {
+                Self::from_bits((value as $sb_type_storage) & Self::STORAGE_MASK)
             }
-        }
 
-        sb_type_impl_from_int_types! { $ident, $storage_signed }
+            /// creates a new instance of this bitfield type with the given value, clamped to the range of this type.
+            pub const fn saturating_new(value: $sb_type_storage_signed) -> Self {
+                if value > Self::MAX.0 {
+                    Self::MAX
+                } else if value < Self::MIN.0 {
+                    Self::MIN
+                } else {
+                    Self(value)
+                }
+            }
 
-        impl BitPieceHasFields for $ident {
-            type Fields = Self;
-            fn from_fields(fields: Self::Fields) -> Self {
-                <Self as BitPiece>::Converter::from_fields(fields)
+            /// returns whether the given value is outside the range of this type.
+            const fn is_out_of_range(value: i128) -> bool {
+                value > Self::MAX.0 as i128 || value < Self::MIN.0 as i128
             }
-            fn to_fields(self) -> Self::Fields {
-                <Self as BitPiece>::Converter::to_fields(self)
+
+            /// wrapping addition, wrapping around at the bit length of this type.
+            pub const fn wrapping_add(self, rhs: Self) -> Self {
+                Self::wrapping_new(self.0.wrapping_add(rhs.0))
+            }
+
+            /// wrapping subtraction, wrapping around at the bit length of this type.
+            pub const fn wrapping_sub(self, rhs: Self) -> Self {
+                Self::wrapping_new(self.0.wrapping_sub(rhs.0))
+            }
+
+            /// wrapping multiplication, wrapping around at the bit length of this type.
+            pub const fn wrapping_mul(self, rhs: Self) -> Self {
+                Self::wrapping_new(self.0.wrapping_mul(rhs.0))
+            }
+
+            /// wrapping negation, wrapping around at the bit length of this type.
+            pub const fn wrapping_neg(self) -> Self {
+                Self::wrapping_new(self.0.wrapping_neg())
+            }
+
+            /// left shift, where the shift amount is taken modulo the bit length of this type.
+            pub const fn wrapping_shl(self, rhs: u32) -> Self {
+                Self::wrapping_new(self.0 << (rhs % <Self as BitPiece>::BITS as u32))
+            }
+
+            /// arithmetic right shift, where the shift amount is taken modulo the bit length of this type.
+            pub const fn wrapping_shr(self, rhs: u32) -> Self {
+                Self(self.0 >> (rhs % <Self as BitPiece>::BITS as u32))
+            }
+
+            /// addition, returning the wrapped result along with whether an overflow occurred.
+            pub const fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+                (self.wrapping_add(rhs), Self::is_out_of_range(self.0 as i128 + rhs.0 as i128))
+            }
+
+            /// subtraction, returning the wrapped result along with whether an overflow occurred.
+            pub const fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+                (self.wrapping_sub(rhs), Self::is_out_of_range(self.0 as i128 - rhs.0 as i128))
             }
-        }
 
-        impl BitPieceHasMutRef for $ident {
-            type MutRef<'s> = $mut_ref_ty_name<'s>;
+            /// multiplication, returning the wrapped result along with whether an overflow occurred.
+            pub const fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+                (self.wrapping_mul(rhs), Self::is_out_of_range(self.0 as i128 * rhs.0 as i128))
+            }
+
+            /// negation, returning the wrapped result along with whether an overflow occurred, which is only the case
+            /// for the min value of this type.
+            pub const fn overflowing_neg(self) -> (Self, bool) {
+                (self.wrapping_neg(), self.0 == Self::MIN.0)
+            }
+
+            /// checked addition, returning `None` if the result does not fit within the bit length of this type.
+            pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+                match self.overflowing_add(rhs) {
+                    (result, false) => Some(result),
+                    (_, true) => None,
+                }
+            }
+
+            /// checked subtraction, returning `None` if the result does not fit within the bit length of this type.
+            pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+                match self.overflowing_sub(rhs) {
+                    (result, false) => Some(result),
+                    (_, true) => None,
+                }
+            }
+
+            /// checked multiplication, returning `None` if the result does not fit within the bit length of this
+            /// type.
+            pub const fn checked_mul(self, rhs: Self) -> Option<Self> {
+                match self.overflowing_mul(rhs) {
+                    (result, false) => Some(result),
+                    (_, true) => None,
+                }
+            }
+
+            /// checked negation, returning `None` for the min value of this type.
+            pub const fn checked_neg(self) -> Option<Self> {
+                match self.overflowing_neg() {
+                    (result, false) => Some(result),
+                    (_, true) => None,
+                }
+            }
+
+            /// checked left shift, returning `None` if the shift amount is not smaller than the bit length of this
+            /// type.
+            pub const fn checked_shl(self, rhs: u32) -> Option<Self> {
+                if rhs < $bit_len {
+                    Some(self.wrapping_shl(rhs))
+                } else {
+                    None
+                }
+            }
+
+            /// checked arithmetic right shift, returning `None` if the shift amount is not smaller than the bit
+            /// length of this type.
+            pub const fn checked_shr(self, rhs: u32) -> Option<Self> {
+                if rhs < $bit_len {
+                    Some(self.wrapping_shr(rhs))
+                } else {
+                    None
+                }
+            }
+
+            /// saturating addition, clamping the result to the range of this type.
+            pub const fn saturating_add(self, rhs: Self) -> Self {
+                match self.checked_add(rhs) {
+                    Some(result) => result,
+                    None if rhs.0 < 0 => Self::MIN,
+                    None => Self::MAX,
+                }
+            }
+
+            /// saturating subtraction, clamping the result to the range of this type.
+            pub const fn saturating_sub(self, rhs: Self) -> Self {
+                match self.checked_sub(rhs) {
+                    Some(result) => result,
+                    None if rhs.0 < 0 => Self::MAX,
+                    None => Self::MIN,
+                }
+            }
+
+            /// saturating multiplication, clamping the result to the range of this type.
+            pub const fn saturating_mul(self, rhs: Self) -> Self {
+                match self.checked_mul(rhs) {
+                    Some(result) => result,
+                    None if (self.0 < 0) != (rhs.0 < 0) => Self::MIN,
+                    None => Self::MAX,
+                }
+            }
+
+            /// saturating negation, clamping the result to the max value of this type.
+            pub const fn saturating_neg(self) -> Self {
+                match self.checked_neg() {
+                    Some(result) => result,
+                    None => Self::MAX,
+                }
+            }
         }
+    }
+}
+
+macro_rules! define_sb_type {
+    { $bit_len: literal, $ident: ident, $storage: ty, $storage_signed: ty, $mut_ref_ty_name: ident } => {
+        /// a type used to represent a signed field with a specific amount of bits.
+        pub type $ident = SBits<$bit_len>;
+        /// a mutable reference to a value of the corresponding type inside another bitpiece.
+        pub type $mut_ref_ty_name<'s> = SBitsMutRef<'s, $bit_len>;
+
+        sb_type_impl_arith! { $bit_len, $ident, $storage, $storage_signed }
 
         impl $ident {
             pub const fn from_fields(fields: Self) -> Self {
@@ -127,7 +428,7 @@ macro_rules! define_sb_type {
             /// creates a new instance of this bitfield type with the given value, without checking that the value
             /// fits within the bit length of this type.
             ///
-            /// # safety
+            /// # Safety
             /// the provided value must fit within the bit length of this type.
             pub const unsafe fn new_unchecked(value: $storage_signed) -> Self {
                 Self(value)
@@ -137,23 +438,44 @@ macro_rules! define_sb_type {
             pub const fn get(&self) -> $storage_signed {
                 self.0
             }
-        }
-        impl core::fmt::Display for $ident {
-            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                core::fmt::Display::fmt(&self.0, f)
+
+            /// converts the given value to this type.
+            ///
+            /// if the value does not fit within the bit length of this type, returns `None`.
+            pub const fn cast(value: i64) -> Option<Self> {
+                if value <= Self::MAX.0 as i64 && value >= Self::MIN.0 as i64 {
+                    Some(Self(value as $storage_signed))
+                } else {
+                    None
+                }
+            }
+
+            /// converts the given value to this type, keeping only the low bits which fit within the bit length of
+            /// this type, and sign extending them.
+            pub const fn truncate(value: i64) -> Self {
+                Self::from_bits((value as $storage) & Self::STORAGE_MASK)
             }
         }
-        impl core::fmt::Debug for $ident {
-            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                core::fmt::Debug::fmt(&self.0, f)
+        impl<'s> $mut_ref_ty_name<'s> {
+            pub const fn get(&self) -> $ident {
+                let bits = self.0.get_bits(0, $bit_len) as $storage;
+                $ident::from_bits(bits)
+            }
+
+            pub const fn set(&mut self, new_value: $ident) {
+                self.0.set_bits(0, $bit_len, new_value.to_bits() as u64);
             }
         }
         bitpiece_check_full_impl! { $ident, true }
-        bitpiece_define_mut_ref_type! { $ident, $mut_ref_ty_name, pub }
+        impl_bitpiece_for_wrapping! { $ident }
+        impl_bitpiece_for_bit_wrappers! { $ident }
     };
 }
 macro_rules! define_sb_types {
     { $($bit_len: literal),+ $(,)? } => {
+        /// the names of the `SB*` types, indexed by their bit length.
+        const SB_TYPE_NAMES: [&str; 65] = ["", $(concat!("SB", stringify!($bit_len))),+];
+
         $(
             paste!{
                 define_sb_type! {
@@ -0,0 +1,17 @@
+use core::marker::PhantomData;
+
+use crate::*;
+
+/// the converter type of `Wrapping<T>`, which forwards all conversions to the converter of `T`.
+///
+/// it is implemented for the integer bitpiece types, which are the types for which wrapping arithmetic is meaningful.
+pub struct BitPieceWrappingConverter<T>(PhantomData<T>);
+
+/// a mutable reference to a `Wrapping<T>` inside another bitpiece.
+#[derive(Debug)]
+pub struct BitPieceWrappingMutRef<'s, T>(pub BitsMut<'s>, PhantomData<T>);
+impl<'s, T> BitPieceWrappingMutRef<'s, T> {
+    pub const fn new(storage: BitPieceStorageMutRef<'s>, start_bit_index: usize) -> Self {
+        Self(BitsMut::new(storage, start_bit_index), PhantomData)
+    }
+}
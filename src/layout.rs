@@ -0,0 +1,159 @@
+use crate::*;
+
+/// runtime information about a field of a bitpiece struct.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldInfo {
+    /// the name of the field.
+    pub name: &'static str,
+
+    /// the offset of the field, in bits, from the least significant bit of the containing type.
+    pub offset: usize,
+
+    /// the length of the field, in bits.
+    pub len: usize,
+
+    /// the name of the type of the field, for example `B5` or `u8`.
+    pub type_name: &'static str,
+
+    /// whether the type of the field is a signed integer type.
+    pub is_signed: bool,
+
+    /// the variants of the type of the field, if it is an enum. otherwise, this is empty.
+    pub variants: &'static [VariantInfo],
+
+    /// the fields of the type of the field, if it is a struct. otherwise, this is empty.
+    pub fields: &'static [FieldInfo],
+
+    /// checks whether the given raw bits are a valid value of the type of the field, see [`is_valid_bits`].
+    pub is_valid_bits: fn(u64) -> bool,
+}
+impl FieldInfo {
+    /// returns the mask of the bits of this field inside the containing type.
+    pub const fn mask(&self) -> u64 {
+        crate::extract_bits_noshift(u64::MAX, self.offset, self.len)
+    }
+
+    /// returns the variant of the type of this field with the given value, if the type of this field is an enum.
+    pub fn variant_by_value(&self, value: u64) -> Option<&'static VariantInfo> {
+        self.variants.iter().find(|variant| variant.value == value)
+    }
+}
+
+// function pointers can't be compared reliably, so the comparison ignores `is_valid_bits`. it is derived from the type
+// of the field anyway, which is already described by the other fields.
+impl PartialEq for FieldInfo {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.offset == other.offset
+            && self.len == other.len
+            && self.type_name == other.type_name
+            && self.is_signed == other.is_signed
+            && self.variants == other.variants
+            && self.fields == other.fields
+    }
+}
+impl Eq for FieldInfo {}
+impl core::hash::Hash for FieldInfo {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.offset.hash(state);
+        self.len.hash(state);
+        self.type_name.hash(state);
+        self.is_signed.hash(state);
+        self.variants.hash(state);
+        self.fields.hash(state);
+    }
+}
+
+/// runtime information about a variant of a bitpiece enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VariantInfo {
+    /// the name of the variant.
+    pub name: &'static str,
+
+    /// the value of the variant.
+    pub value: u64,
+}
+
+/// returns whether the given raw bits are a valid value of `T`, according to `T::try_from_bits`.
+///
+/// bits above `T::BITS` are not allowed to be set.
+pub fn is_valid_bits<T: BitPiece>(bits: u64) -> bool {
+    extract_bits(bits, 0, T::BITS) == bits
+        && T::Bits::from_u64(bits)
+            .ok()
+            .and_then(T::try_from_bits)
+            .is_some()
+}
+
+/// finds a field by its path, which is a sequence of field names separated by `.`, for example `"control.mode"`. each
+/// name but the last one refers to a field whose type is a struct.
+///
+/// returns the offset of the field relative to the start of the outermost type, and the information about the field.
+pub fn find_field_by_path(
+    fields: &'static [FieldInfo],
+    path: &str,
+) -> Option<(usize, &'static FieldInfo)> {
+    let (name, rest) = match path.split_once('.') {
+        Some((name, rest)) => (name, Some(rest)),
+        None => (path, None),
+    };
+    let field = fields.iter().find(|field| field.name == name)?;
+    match rest {
+        Some(rest) => {
+            let (inner_offset, inner_field) = find_field_by_path(field.fields, rest)?;
+            Some((field.offset + inner_offset, inner_field))
+        }
+        None => Some((field.offset, field)),
+    }
+}
+
+/// an error which occurred while modifying a field of a bitpiece by its name or index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FieldAccessError {
+    /// there is no field with the given name or index.
+    UnknownField,
+
+    /// the value does not fit within the bit length of the field.
+    ValueTooLarge,
+
+    /// the value is not a valid value of the type of the field, for example it is not a valid enum variant.
+    InvalidValue,
+}
+impl core::fmt::Display for FieldAccessError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FieldAccessError::UnknownField => write!(f, "unknown field"),
+            FieldAccessError::ValueTooLarge => write!(f, "value does not fit in field"),
+            FieldAccessError::InvalidValue => write!(f, "invalid value for field"),
+        }
+    }
+}
+
+/// returns the raw bits of a field of the given value, given the offset of the field.
+pub(crate) fn get_field_bits<T: BitPiece>(value: T, offset: usize, field: &FieldInfo) -> u64 {
+    extract_bits(value.to_bits().to_u64(), offset, field.len)
+}
+
+/// modifies the raw bits of a field of the given value, given the offset of the field. the new bits are validated using
+/// the type of the field.
+pub(crate) fn set_field_bits<T: BitPiece>(
+    value: &mut T,
+    offset: usize,
+    field: &FieldInfo,
+    bits: u64,
+) -> Result<(), FieldAccessError> {
+    if extract_bits(bits, 0, field.len) != bits {
+        return Err(FieldAccessError::ValueTooLarge);
+    }
+    if !(field.is_valid_bits)(bits) {
+        return Err(FieldAccessError::InvalidValue);
+    }
+    let new_bits = modify_bits(value.to_bits().to_u64(), offset, field.len, bits);
+    // revalidate the whole value, since the containing types may impose their own restrictions.
+    *value = T::Bits::from_u64(new_bits)
+        .ok()
+        .and_then(T::try_from_bits)
+        .ok_or(FieldAccessError::InvalidValue)?;
+    Ok(())
+}
@@ -1,24 +1,63 @@
 #![no_std]
 
+mod bit_int;
+mod bit_stream;
 mod check;
+mod codings;
+mod flags;
+mod formatting;
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+mod fuzzing;
 mod impls;
+mod layout;
 mod mut_ref;
+mod parse;
 mod storage;
+pub mod testing;
 mod utils;
+mod visit;
+pub use bit_int::*;
+pub use bit_stream::*;
+pub use codings::*;
+pub use flags::*;
+pub use formatting::*;
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+pub use fuzzing::*;
 pub use impls::*;
+pub use layout::*;
 pub use mut_ref::*;
+pub use parse::*;
 pub use storage::*;
 pub use utils::*;
+pub use visit::*;
 
+#[cfg(feature = "arbitrary")]
+pub use arbitrary;
 pub use bitpiece_macros::bitpiece;
 pub use const_for::const_for;
 pub use paste::paste;
+#[cfg(feature = "proptest")]
+pub use proptest;
+#[cfg(feature = "serde")]
+pub use serde;
 
+/// a type which can be stored in a bitfield, using a fixed amount of bits.
+///
+/// bitpieces are limited to 64 bits, so this is not implemented for `u128` and `i128`, and a bitpiece can't have a bit
+/// length above 64.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be used as a bitpiece",
+    note = "bitpieces are limited to 64 bits, so `u128` and `i128` are not supported"
+)]
 pub trait BitPiece: Copy {
     /// the length in bits of this type.
     const BITS: usize;
 
     /// a value with all zero bits.
+    ///
+    /// the constants of structs are built from the constants of their fields without calling the validation function
+    /// of the struct, if it has one, so they may break its invariants. generic code which needs a valid value should
+    /// not rely on them.
     const ZEROES: Self;
 
     /// a value with all one bits.
@@ -58,4 +97,77 @@ pub trait BitPieceHasFields: BitPiece {
     type Fields;
     fn from_fields(fields: Self::Fields) -> Self;
     fn to_fields(self) -> Self::Fields;
+
+    /// calls every generated setter of this type, which can't be called generically, so that [`testing::check`] can
+    /// verify them.
+    ///
+    /// each field of a copy of `value` is set to the value of the field in `source`, once using each setter of the
+    /// field, and the result is passed to `f`. the default implementation doesn't call anything, which is correct for
+    /// types without setters. the `bitpiece` macro implements it for structs.
+    fn call_setters(value: Self, source: Self, f: &mut dyn FnMut(testing::SetterCall<Self>)) {
+        let _ = (value, source, f);
+    }
+}
+
+/// runtime metadata about the layout of a bitpiece, which allows generic code to walk its fields.
+pub trait BitPieceHasLayout: BitPiece {
+    /// the name of this type, for example `B5` or `u8`.
+    const TYPE_NAME: &'static str;
+
+    /// whether this type is a signed integer type.
+    const IS_SIGNED: bool = false;
+
+    /// the variants of this type, if it is an enum. otherwise, this is empty.
+    const VARIANTS: &'static [VariantInfo] = &[];
+
+    /// the fields of this type, if it is a struct. otherwise, this is empty.
+    const FIELDS: &'static [FieldInfo] = &[];
+
+    /// returns the raw bits of the field with the given path, for example `"irq_en"` or `"control.mode"` for a field of
+    /// a nested struct. returns `None` if there is no such field.
+    ///
+    /// the bits are not sign extended, so the value of a signed field is returned in two's complement using the bit
+    /// length of the field. for example, an `SB4` field with a value of `-2` returns `0b1110`. use
+    /// [`FieldInfo::is_signed`] and [`sign_extend_bits`] to get the signed value.
+    fn get_field(&self, path: &str) -> Option<u64> {
+        let (offset, field) = find_field_by_path(Self::FIELDS, path)?;
+        Some(layout::get_field_bits(*self, offset, field))
+    }
+
+    /// sets the raw bits of the field with the given path, for example `"irq_en"` or `"control.mode"` for a field of a
+    /// nested struct.
+    ///
+    /// like [`Self::get_field`], this takes raw bits, so the value of a signed field must be given in two's complement
+    /// using the bit length of the field. for example, to set an `SB4` field to `-2`, pass `0b1110`.
+    ///
+    /// the bits are validated using the `try_from_bits` function of the type of the field, and the value is left
+    /// unchanged on failure.
+    fn set_field(&mut self, path: &str, bits: u64) -> Result<(), FieldAccessError> {
+        let (offset, field) =
+            find_field_by_path(Self::FIELDS, path).ok_or(FieldAccessError::UnknownField)?;
+        layout::set_field_bits(self, offset, field, bits)
+    }
+
+    /// returns the raw bits of the field at the given index in [`Self::FIELDS`]. returns `None` if the index is out of
+    /// bounds.
+    ///
+    /// the bits are not sign extended, see [`Self::get_field`].
+    fn get_field_by_index(&self, index: usize) -> Option<u64> {
+        let field = Self::FIELDS.get(index)?;
+        Some(layout::get_field_bits(*self, field.offset, field))
+    }
+
+    /// sets the raw bits of the field at the given index in [`Self::FIELDS`].
+    ///
+    /// the value of a signed field must be given in two's complement using the bit length of the field, see
+    /// [`Self::set_field`].
+    ///
+    /// the bits are validated using the `try_from_bits` function of the type of the field, and the value is left
+    /// unchanged on failure.
+    fn set_field_by_index(&mut self, index: usize, bits: u64) -> Result<(), FieldAccessError> {
+        let field = Self::FIELDS
+            .get(index)
+            .ok_or(FieldAccessError::UnknownField)?;
+        layout::set_field_bits(self, field.offset, field, bits)
+    }
 }
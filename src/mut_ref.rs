@@ -23,7 +23,7 @@ impl<'a> BitPieceStorageMutRef<'a> {
     #[inline(always)]
     pub const fn get(&self) -> u64 {
         match self {
-            BitPieceStorageMutRef::U64(x) => **x as u64,
+            BitPieceStorageMutRef::U64(x) => **x,
             BitPieceStorageMutRef::U32(x) => **x as u64,
             BitPieceStorageMutRef::U16(x) => **x as u64,
             BitPieceStorageMutRef::U8(x) => **x as u64,
@@ -33,7 +33,7 @@ impl<'a> BitPieceStorageMutRef<'a> {
     #[inline(always)]
     pub const fn set(&mut self, new_value: u64) {
         match self {
-            BitPieceStorageMutRef::U64(x) => **x = new_value as u64,
+            BitPieceStorageMutRef::U64(x) => **x = new_value,
             BitPieceStorageMutRef::U32(x) => **x = new_value as u32,
             BitPieceStorageMutRef::U16(x) => **x = new_value as u16,
             BitPieceStorageMutRef::U8(x) => **x = new_value as u8,
@@ -99,6 +99,63 @@ impl<'s> BitsMut<'s> {
     }
 }
 
+/// a mutable reference to a field which is stored using a custom field converter (`#[bitpiece(with = path, bits = N)]`),
+/// which is returned by the `*_mut` methods of such fields.
+///
+/// the value of such a field is not a bitpiece, so it has no mutable reference type of its own. instead, the reference
+/// holds the `encode` and `decode` functions of the converter module, where `B` is the type of the raw bits of the field.
+pub struct CodecMutRef<'s, T, B> {
+    bits: BitsMut<'s>,
+    len: usize,
+    encode: fn(T) -> B,
+    decode: fn(B) -> T,
+}
+impl<'s, T, B: BitStorage> CodecMutRef<'s, T, B> {
+    #[inline(always)]
+    pub const fn new(
+        storage: BitPieceStorageMutRef<'s>,
+        start_bit_index: usize,
+        len: usize,
+        encode: fn(T) -> B,
+        decode: fn(B) -> T,
+    ) -> Self {
+        Self {
+            bits: BitsMut::new(storage, start_bit_index),
+            len,
+            encode,
+            decode,
+        }
+    }
+
+    /// decodes the value of the field.
+    pub fn get(&self) -> T {
+        let bits = B::from_u64(self.bits.get_bits(0, self.len)).unwrap();
+        (self.decode)(bits)
+    }
+
+    /// encodes the given value and stores it in the field, panicking if the encoded value doesn't fit in the field.
+    pub fn set(&mut self, new_value: T) {
+        let bits = check_encoded_bits((self.encode)(new_value).to_u64(), self.len);
+        self.bits.set_bits(0, self.len, bits);
+    }
+
+    /// encodes the given value and stores it in the field, or returns an error if the encoded value doesn't fit in the
+    /// field, in which case the field is left unchanged.
+    pub fn try_set(&mut self, new_value: T) -> Result<(), TryFromBitIntError> {
+        let bits = try_narrow_unsigned((self.encode)(new_value).to_u64(), self.len)?;
+        self.bits.set_bits(0, self.len, bits);
+        Ok(())
+    }
+}
+impl<T, B> core::fmt::Debug for CodecMutRef<'_, T, B> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CodecMutRef")
+            .field("bits", &self.bits)
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
 /// a mutable reference to a bitpiece inside another bitpiece.
 pub trait BitPieceMutRef<'s> {
     type BitPiece: BitPiece;
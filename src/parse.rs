@@ -0,0 +1,101 @@
+use core::num::IntErrorKind;
+
+use crate::*;
+
+/// an error which occurred while parsing an integer bitpiece type from a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseBitIntError {
+    /// the string contains no digits.
+    Empty,
+
+    /// the string contains a character which is not a valid digit.
+    InvalidDigit,
+
+    /// the value does not fit within the bit length of the type.
+    OutOfRange,
+}
+impl core::fmt::Display for ParseBitIntError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseBitIntError::Empty => write!(f, "cannot parse integer from empty string"),
+            ParseBitIntError::InvalidDigit => write!(f, "invalid digit found in string"),
+            ParseBitIntError::OutOfRange => write!(f, "number out of range of the type"),
+        }
+    }
+}
+
+/// an error which occurred while parsing an enum from a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseEnumError {
+    /// the string is neither the name of a variant nor a number.
+    UnknownVariant,
+
+    /// the string is a number which is not the value of any variant.
+    InvalidValue,
+}
+impl core::fmt::Display for ParseEnumError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseEnumError::UnknownVariant => write!(f, "unknown variant name"),
+            ParseEnumError::InvalidValue => write!(f, "value does not match any variant"),
+        }
+    }
+}
+
+/// parses an unsigned integer, which is decimal by default, or hex or binary if prefixed with `0x` or `0b`.
+pub fn parse_unsigned(s: &str) -> Result<u64, ParseBitIntError> {
+    let (radix, digits) =
+        if let Some(digits) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            (16, digits)
+        } else if let Some(digits) = s.strip_prefix("0b").or_else(|| s.strip_prefix("0B")) {
+            (2, digits)
+        } else {
+            (10, s)
+        };
+    // `from_str_radix` accepts a leading sign, which we don't want to allow.
+    if digits.starts_with(['+', '-']) {
+        return Err(ParseBitIntError::InvalidDigit);
+    }
+    u64::from_str_radix(digits, radix).map_err(|err| match err.kind() {
+        IntErrorKind::Empty => ParseBitIntError::Empty,
+        IntErrorKind::PosOverflow => ParseBitIntError::OutOfRange,
+        _ => ParseBitIntError::InvalidDigit,
+    })
+}
+
+/// parses a signed integer, which may start with a `-` sign, followed by the format accepted by [`parse_unsigned`].
+pub fn parse_signed(s: &str) -> Result<i64, ParseBitIntError> {
+    match s.strip_prefix('-') {
+        Some(magnitude) => {
+            let magnitude = parse_unsigned(magnitude)?;
+            if magnitude > i64::MIN.unsigned_abs() {
+                return Err(ParseBitIntError::OutOfRange);
+            }
+            Ok((magnitude as i64).wrapping_neg())
+        }
+        None => i64::try_from(parse_unsigned(s)?).map_err(|_| ParseBitIntError::OutOfRange),
+    }
+}
+
+/// parses an enum value from either the name of one of its variants, ignoring case, or from its numeric value in the
+/// format accepted by [`parse_unsigned`].
+pub fn parse_enum<T: BitPiece>(
+    s: &str,
+    variants: &[T],
+    variant_name: fn(T) -> &'static str,
+) -> Result<T, ParseEnumError> {
+    if let Some(&variant) = variants
+        .iter()
+        .find(|&&variant| variant_name(variant).eq_ignore_ascii_case(s))
+    {
+        return Ok(variant);
+    }
+    let value = parse_unsigned(s).map_err(|err| match err {
+        ParseBitIntError::OutOfRange => ParseEnumError::InvalidValue,
+        _ => ParseEnumError::UnknownVariant,
+    })?;
+    T::Bits::from_u64(value)
+        .ok()
+        .and_then(T::try_from_bits)
+        .ok_or(ParseEnumError::InvalidValue)
+}
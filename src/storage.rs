@@ -65,7 +65,17 @@ pub trait ExactAssociatedStorage {
 /// storing that amount of bits. for example for bit lengths `0..8` this will be [`u8`].
 pub trait AssociatedStorage {
     /// the storage type required for storing that amount of bits. for example for bit lengths `0..8` this will be [`u8`].
-    type Storage: BitStorage;
+    type Storage: BitStorage<Signed: BitInt + Into<i128>> + Into<i128>;
+
+    /// a mask of the bits of the storage type which are within that amount of bits, which is also the max value of an
+    /// unsigned integer of that bit length.
+    const MASK: Self::Storage;
+
+    /// the min value of a signed integer of that bit length, represented using the signed version of the storage type.
+    const SIGNED_MIN: <Self::Storage as BitStorage>::Signed;
+
+    /// the max value of a signed integer of that bit length, represented using the signed version of the storage type.
+    const SIGNED_MAX: <Self::Storage as BitStorage>::Signed;
 }
 
 macro_rules! impl_exact_associated_storage {
@@ -99,6 +109,11 @@ macro_rules! impl_associated_storage {
         $(
             impl AssociatedStorage for BitLength<$bit_length> {
                 type Storage = <BitLength< { exact_associated_storage_bit_length($bit_length) } > as ExactAssociatedStorage>::Storage;
+                const MASK: Self::Storage = ((1u128 << $bit_length) - 1) as Self::Storage;
+                const SIGNED_MIN: <Self::Storage as BitStorage>::Signed =
+                    (-(1i128 << ($bit_length - 1))) as <Self::Storage as BitStorage>::Signed;
+                const SIGNED_MAX: <Self::Storage as BitStorage>::Signed =
+                    ((1i128 << ($bit_length - 1)) - 1) as <Self::Storage as BitStorage>::Signed;
             }
         )+
     };
@@ -107,3 +122,83 @@ impl_associated_storage! {
     1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33,
     34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64,
 }
+
+/// an empty struct used to represent the bit length of a fixed-point type with `INT` integer bits and `FRAC` fractional
+/// bits, which is `INT + FRAC`.
+///
+/// the sum can't be computed in a type on stable rust, so this is combined with [`AssociatedFixedStorage`], which is
+/// implemented for each pair whose sum is a valid bit length.
+pub struct FixedBitLength<const INT: usize, const FRAC: usize>;
+
+/// a trait implemented for all [`FixedBitLength`] types whose total bit length is between 1 and 64, which provides the
+/// minimal storage type required for storing that amount of bits, like [`AssociatedStorage`].
+pub trait AssociatedFixedStorage {
+    /// the storage type required for storing `INT + FRAC` bits. for example for `UFixed<4, 12>` this will be [`u16`].
+    type Storage: BitStorage<Signed: Copy>;
+}
+
+macro_rules! impl_associated_fixed_storage {
+    { [$($frac: literal)*] $bit_length: literal $($rest: literal)* } => {
+        $(
+            impl AssociatedFixedStorage for FixedBitLength<{ $bit_length - $frac }, $frac> {
+                type Storage = <BitLength<$bit_length> as AssociatedStorage>::Storage;
+            }
+        )*
+        impl AssociatedFixedStorage for FixedBitLength<0, $bit_length> {
+            type Storage = <BitLength<$bit_length> as AssociatedStorage>::Storage;
+        }
+        impl_associated_fixed_storage! { [$($frac)* $bit_length] $($rest)* }
+    };
+    { [$($frac: literal)*] } => {};
+}
+impl_associated_fixed_storage! {
+    [0] 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32 33 34 35 36 37 38 39 40 41
+    42 43 44 45 46 47 48 49 50 51 52 53 54 55 56 57 58 59 60 61 62 63 64
+}
+
+/// converts a value of a storage type, or of its signed version, to a `u64`, without sign extending it.
+///
+/// this is the const counterpart of [`BitStorage::to_u64`], for code which is generic over the storage type, since trait
+/// functions can't be called in const contexts.
+///
+/// # Safety
+///
+/// `S` must be a primitive integer type, which is always the case for the storage types of [`AssociatedStorage`] and
+/// [`AssociatedFixedStorage`] and their signed versions, since these traits can't be implemented outside of this crate.
+#[inline(always)]
+pub(crate) const unsafe fn storage_to_u64<S: Copy>(value: S) -> u64 {
+    let ptr = &value as *const S;
+    // SAFETY: `S` is a primitive integer type of the given size, so it has the same size and alignment as the unsigned
+    // integer type which it is read as.
+    unsafe {
+        match core::mem::size_of::<S>() {
+            1 => *(ptr as *const u8) as u64,
+            2 => *(ptr as *const u16) as u64,
+            4 => *(ptr as *const u32) as u64,
+            8 => *(ptr as *const u64),
+            _ => panic!("storage types must be primitive integer types"),
+        }
+    }
+}
+
+/// converts a `u64` to a storage type, or to its signed version, truncating it to the size of the storage type.
+///
+/// this is the const counterpart of [`BitStorage::from_u64`], see [`storage_to_u64`].
+///
+/// # Safety
+///
+/// `S` must be a primitive integer type, see [`storage_to_u64`].
+#[inline(always)]
+pub(crate) const unsafe fn storage_from_u64<S: Copy>(value: u64) -> S {
+    // SAFETY: `S` is a primitive integer type of the given size, so any unsigned integer of the same size is a valid
+    // value of it, and has the same alignment.
+    unsafe {
+        match core::mem::size_of::<S>() {
+            1 => *(&(value as u8) as *const u8 as *const S),
+            2 => *(&(value as u16) as *const u16 as *const S),
+            4 => *(&(value as u32) as *const u32 as *const S),
+            8 => *(&value as *const u64 as *const S),
+            _ => panic!("storage types must be primitive integer types"),
+        }
+    }
+}
@@ -0,0 +1,375 @@
+//! a runtime harness for verifying the generated code of bitpiece types, see [`check`] and [`check_layout`].
+
+use crate::*;
+
+/// types with a bit length of up to this many bits are checked exhaustively by [`check`] and [`check_layout`].
+pub const EXHAUSTIVE_MAX_BITS: usize = 20;
+
+/// the amount of random bit patterns checked by [`check`] and [`check_layout`] for types which are too large to be
+/// checked exhaustively.
+pub const RANDOM_SAMPLES: usize = 1 << 14;
+
+/// the result of calling one of the generated setters of a field, as reported by [`BitPieceHasFields::call_setters`].
+#[derive(Debug, Clone, Copy)]
+pub struct SetterCall<T> {
+    /// the name of the field.
+    pub field: &'static str,
+
+    /// the name of the setter, for example `set_len` or `len_mut().set`.
+    pub setter: &'static str,
+
+    /// the offset of the field, in bits.
+    pub offset: usize,
+
+    /// the length of the field, in bits.
+    pub len: usize,
+
+    /// the modified value, or `None` if the setter rejected the new value of the field.
+    pub result: Option<T>,
+}
+
+/// verifies that the conversions, mutable references and setters of the given bitpiece type are consistent with each
+/// other, panicking with a descriptive message if they are not.
+///
+/// if `T::BITS` is at most [`EXHAUSTIVE_MAX_BITS`], every bit pattern is checked. otherwise, [`RANDOM_SAMPLES`] random
+/// bit patterns are checked. the random patterns are generated deterministically, so a failure is always reproducible.
+///
+/// for each valid bit pattern, this checks that:
+/// - `from_bits` and `to_bits` round trip, and `try_from_bits` accepts the pattern.
+/// - `to_fields` and `from_fields` round trip.
+/// - a mutable reference placed at any offset inside a larger storage reads back the value after setting it, and never
+///   modifies the bits outside of the value.
+/// - each generated setter of a field (`set_*`, `with_*`, their `try_*` and `checked_*` variants, and the setters of the
+///   mutable reference types), called through [`BitPieceHasFields::call_setters`], stores the new value of the field
+///   and never modifies the bits of the other fields. the new values are taken from other valid values of the type.
+///
+/// the field layout of types which implement [`BitPieceHasLayout`] can be checked using [`check_layout`].
+///
+/// ```
+/// # use bitpiece::*;
+/// #[bitpiece(8, all)]
+/// struct Header {
+///     kind: B3,
+///     len: B5,
+/// }
+///
+/// bitpiece::testing::check::<Header>();
+/// ```
+pub fn check<T>()
+where
+    T: BitPieceHasFields + BitPieceHasMutRef,
+{
+    let mut recent_values = RecentValues::new();
+    for_each_bit_pattern::<T>(|bits, rng| check_bits::<T>(bits, rng, &mut recent_values));
+}
+
+/// verifies that the field layout of the given bitpiece type, as described by its [`BitPieceHasLayout`] implementation,
+/// is consistent with its values, panicking with a descriptive message if it is not.
+///
+/// the fields must fit inside the type and must not overlap. the bit patterns are chosen like in [`check`], and for
+/// each valid bit pattern, this checks that:
+/// - each field holds a valid value of its type.
+/// - setting a field using [`BitPieceHasLayout::set_field_by_index`] only modifies the bits of that field.
+///
+/// for types which are too large to be checked exhaustively, random values built from valid values of each of the
+/// fields are also checked.
+///
+/// ```
+/// # use bitpiece::*;
+/// #[bitpiece(8, all, layout)]
+/// struct Header {
+///     kind: B3,
+///     len: B5,
+/// }
+///
+/// bitpiece::testing::check_layout::<Header>();
+/// ```
+pub fn check_layout<T: BitPieceHasLayout>() {
+    check_field_bounds::<T>();
+    for_each_bit_pattern::<T>(|bits, rng| {
+        check_fields::<T>(bits, rng);
+        if T::BITS > EXHAUSTIVE_MAX_BITS {
+            let bits = random_valid_field_bits::<T>(rng);
+            check_fields::<T>(bits, rng);
+        }
+    });
+}
+
+/// calls the given function with every bit pattern of the given type, if it is small enough to be checked
+/// exhaustively, or with a deterministic sample of bit patterns otherwise.
+fn for_each_bit_pattern<T: BitPiece>(mut f: impl FnMut(u64, &mut Rng)) {
+    let mut rng = Rng::new(T::BITS as u64);
+    if T::BITS <= EXHAUSTIVE_MAX_BITS {
+        for bits in 0..(1u64 << T::BITS) {
+            f(bits, &mut rng);
+        }
+    } else {
+        let mask = extract_bits_noshift(u64::MAX, 0, T::BITS);
+        // the constants of the type are not used here, since they may break the invariants of a validated struct.
+        for special in [0, mask] {
+            f(special, &mut rng);
+        }
+        for _ in 0..RANDOM_SAMPLES {
+            f(rng.next() & mask, &mut rng);
+        }
+    }
+}
+
+/// converts the given bits to a value of the given type, if they are a valid bit pattern of it.
+fn try_value<T: BitPiece>(bits: u64) -> Option<T> {
+    T::Bits::from_u64(bits).ok().and_then(T::try_from_bits)
+}
+
+/// panics with a message which describes a failed check of the given bit pattern of the given type.
+fn fail_check(type_name: &str, bits: u64, what: core::fmt::Arguments<'_>) -> ! {
+    panic!(
+        "check of `{}` failed for bits {:#x}: {}",
+        type_name, bits, what
+    )
+}
+
+/// runs all checks of [`check`] on the given bit pattern, which may or may not be a valid value of `T`.
+fn check_bits<T>(bits: u64, rng: &mut Rng, recent_values: &mut RecentValues<T>)
+where
+    T: BitPieceHasFields + BitPieceHasMutRef,
+{
+    let Some(value) = try_value::<T>(bits) else {
+        return;
+    };
+    let type_name = core::any::type_name::<T>();
+    if value.to_bits().to_u64() != bits {
+        fail_check(
+            type_name,
+            bits,
+            format_args!("`to_bits` does not return the bits passed to `try_from_bits`"),
+        );
+    }
+    check_value(value, rng)
+        .unwrap_or_else(|what| fail_check(type_name, bits, format_args!("{}", what)));
+
+    let source = recent_values.pick(rng).unwrap_or(value);
+    T::call_setters(value, source, &mut |call| {
+        check_setter_call(bits, source.to_bits().to_u64(), call, rng).unwrap_or_else(|what| {
+            fail_check(
+                type_name,
+                bits,
+                format_args!("`{}` of field `{}` {}", call.setter, call.field, what),
+            )
+        })
+    });
+    recent_values.push(value);
+}
+
+/// checks that the conversions and the mutable reference of the given valid value are consistent with each other.
+fn check_value<T>(value: T, rng: &mut Rng) -> Result<(), &'static str>
+where
+    T: BitPieceHasFields + BitPieceHasMutRef,
+{
+    let bits = value.to_bits().to_u64();
+    if T::from_bits(value.to_bits()).to_bits().to_u64() != bits {
+        return Err("`from_bits` and `to_bits` do not round trip");
+    }
+    if T::from_fields(value.to_fields()).to_bits().to_u64() != bits {
+        return Err("`from_fields` and `to_fields` do not round trip");
+    }
+    check_mut_ref::<T>(bits, rng)
+}
+
+/// checks that a generated setter, which was called on the value with the given bits to set a field to its value in the
+/// source bits, only modified the bits of that field.
+///
+/// if the modified value is valid, it is also checked like any other value, which covers combinations of fields which
+/// are rare among random bit patterns.
+fn check_setter_call<T>(
+    bits: u64,
+    source_bits: u64,
+    call: SetterCall<T>,
+    rng: &mut Rng,
+) -> Result<(), &'static str>
+where
+    T: BitPieceHasFields + BitPieceHasMutRef,
+{
+    if call.offset + call.len > T::BITS {
+        return Err("sets a field which is out of bounds");
+    }
+    let field_mask = extract_bits_noshift(u64::MAX, call.offset, call.len);
+    let expected_bits = (bits & !field_mask) | (source_bits & field_mask);
+    let Some(modified) = call.result else {
+        // a setter may only reject the new value if the containing type rejects the new combination of fields.
+        if try_value::<T>(expected_bits).is_some() {
+            return Err("rejects a valid value");
+        }
+        return Ok(());
+    };
+    let modified_bits = modified.to_bits().to_u64();
+    if modified_bits & !field_mask != bits & !field_mask {
+        return Err("modifies the bits of other fields");
+    }
+    if modified_bits != expected_bits {
+        return Err("does not store the bits of the new value");
+    }
+    match try_value::<T>(modified_bits) {
+        Some(modified) => check_value(modified, rng),
+        None => Ok(()),
+    }
+}
+
+/// a few of the most recently checked valid values of a type, which are used as the sources of the new values of the
+/// fields when checking the setters.
+struct RecentValues<T> {
+    values: [Option<T>; 8],
+    next_index: usize,
+}
+impl<T: Copy> RecentValues<T> {
+    fn new() -> Self {
+        Self {
+            values: [None; 8],
+            next_index: 0,
+        }
+    }
+
+    fn push(&mut self, value: T) {
+        self.values[self.next_index] = Some(value);
+        self.next_index = (self.next_index + 1) % self.values.len();
+    }
+
+    fn pick(&self, rng: &mut Rng) -> Option<T> {
+        self.values[(rng.next() % self.values.len() as u64) as usize]
+    }
+}
+
+/// checks that the fields of the type fit inside of it and don't overlap.
+fn check_field_bounds<T: BitPieceHasLayout>() {
+    let mut used_bits = 0u64;
+    for field in T::FIELDS {
+        assert!(
+            field.offset + field.len <= T::BITS,
+            "field `{}` of `{}` is out of bounds",
+            field.name,
+            T::TYPE_NAME,
+        );
+        assert!(
+            used_bits & field.mask() == 0,
+            "field `{}` of `{}` overlaps another field",
+            field.name,
+            T::TYPE_NAME,
+        );
+        used_bits |= field.mask();
+    }
+}
+
+/// runs all checks of [`check_layout`] on the given bit pattern, which may or may not be a valid value of `T`.
+fn check_fields<T: BitPieceHasLayout>(bits: u64, rng: &mut Rng) {
+    let Some(value) = try_value::<T>(bits) else {
+        return;
+    };
+    let fail = |what: &str| -> ! { fail_check(T::TYPE_NAME, bits, format_args!("{}", what)) };
+    for field in T::FIELDS {
+        if !(field.is_valid_bits)(extract_bits(bits, field.offset, field.len)) {
+            fail("a field holds an invalid value");
+        }
+    }
+    check_field_setters::<T>(value, rng).unwrap_or_else(|what| fail(what));
+}
+
+/// checks that a mutable reference to the given bits, placed at a random offset inside random surrounding bits, reads
+/// back what was set and leaves the surrounding bits untouched.
+fn check_mut_ref<T: BitPieceHasMutRef>(bits: u64, rng: &mut Rng) -> Result<(), &'static str> {
+    let offset = (rng.next() % (64 - T::BITS as u64 + 1)) as usize;
+    let outside_mask = !extract_bits_noshift(u64::MAX, offset, T::BITS);
+    let mut storage = rng.next();
+    let original_storage = storage;
+
+    let read_back = {
+        let mut mut_ref = T::MutRef::new(BitPieceStorageMutRef::U64(&mut storage), offset);
+        mut_ref.set(mut_ref_value::<T>(bits));
+        mut_ref.get().to_bits().to_u64()
+    };
+    if read_back != bits {
+        return Err("a mutable reference does not read back the value that was set");
+    }
+    if storage & outside_mask != original_storage & outside_mask {
+        return Err("setting a mutable reference modifies bits outside of the value");
+    }
+    if extract_bits(storage, offset, T::BITS) != bits {
+        return Err("setting a mutable reference does not store the bits of the value");
+    }
+    Ok(())
+}
+
+/// builds the value referenced by the mutable reference type of `T` from the given bits.
+fn mut_ref_value<'s, T: BitPieceHasMutRef>(
+    bits: u64,
+) -> <T::MutRef<'s> as BitPieceMutRef<'s>>::BitPiece {
+    let bits = <<T::MutRef<'s> as BitPieceMutRef<'s>>::BitPiece as BitPiece>::Bits::from_u64(bits)
+        .unwrap();
+    BitPiece::from_bits(bits)
+}
+
+/// checks that setting each field of the given value to some other valid value only modifies the bits of that field.
+fn check_field_setters<T: BitPieceHasLayout>(value: T, rng: &mut Rng) -> Result<(), &'static str> {
+    let bits = value.to_bits().to_u64();
+    for (index, field) in T::FIELDS.iter().enumerate() {
+        let Some(field_bits) = random_valid_bits(field, rng) else {
+            continue;
+        };
+        let mut modified = value;
+        // the containing type may reject the new combination of fields, in which case it must be left unchanged.
+        let Ok(()) = modified.set_field_by_index(index, field_bits) else {
+            if modified.to_bits().to_u64() != bits {
+                return Err("a rejected field modification modifies the value");
+            }
+            continue;
+        };
+        let modified_bits = modified.to_bits().to_u64();
+        if modified_bits & !field.mask() != bits & !field.mask() {
+            return Err("setting a field modifies the bits of other fields");
+        }
+        if extract_bits(modified_bits, field.offset, field.len) != field_bits {
+            return Err("setting a field does not store the bits of the new value");
+        }
+    }
+    Ok(())
+}
+
+/// builds random bits for `T` by combining random valid values of each of its fields. the resulting bits are not
+/// guaranteed to be valid, since the containing type may impose its own restrictions.
+fn random_valid_field_bits<T: BitPieceHasLayout>(rng: &mut Rng) -> u64 {
+    let mut bits = 0;
+    for field in T::FIELDS {
+        if let Some(field_bits) = random_valid_bits(field, rng) {
+            bits = modify_bits(bits, field.offset, field.len, field_bits);
+        }
+    }
+    bits
+}
+
+/// returns random valid bits for the type of the given field, giving up after a few attempts for types where valid bit
+/// patterns are rare.
+fn random_valid_bits(field: &FieldInfo, rng: &mut Rng) -> Option<u64> {
+    if let Some(variant) = field
+        .variants
+        .get((rng.next() % field.variants.len().max(1) as u64) as usize)
+    {
+        return Some(variant.value);
+    }
+    let mask = extract_bits_noshift(u64::MAX, 0, field.len);
+    (0..16)
+        .map(|_| rng.next() & mask)
+        .find(|&bits| (field.is_valid_bits)(bits))
+}
+
+/// a small deterministic xorshift random number generator.
+struct Rng(u64);
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(0x9e37_79b9_7f4a_7c15 ^ seed)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
@@ -1,3 +1,5 @@
+use crate::TryFromBitIntError;
+
 #[inline(always)]
 const fn extract_bits_mask(len: usize) -> u64 {
     debug_assert!(len <= 64);
@@ -31,6 +33,72 @@ pub const fn modify_bits(value: u64, offset: usize, len: usize, new_value: u64)
     without_original_bits | shifted_new_value
 }
 
+/// checks that the bits returned by the `encode` function of a custom field converter fit within the bit length of the
+/// field, and returns them.
+#[inline(always)]
+pub const fn check_encoded_bits(bits: u64, len: usize) -> u64 {
+    if extract_bits(bits, 0, len) != bits {
+        panic!("encoded value does not fit in the bit length of the field");
+    }
+    bits
+}
+
+/// checks that the bit length of a native integer field is non-zero and not larger than the bit length of its type,
+/// and returns it.
+pub const fn check_narrowed_bit_len(len: usize, type_len: usize) -> usize {
+    if len == 0 || len > type_len {
+        panic!("the bit length of a native integer field must be between 1 and the bit length of its type");
+    }
+    len
+}
+
+/// converts the value of a native unsigned integer field, widened to a `u64`, to the bits of a field with the given bit
+/// length, returning an error if the value does not fit in the field.
+pub const fn try_narrow_unsigned(value: u64, len: usize) -> Result<u64, TryFromBitIntError> {
+    if extract_bits(value, 0, len) == value {
+        Ok(value)
+    } else {
+        Err(TryFromBitIntError(()))
+    }
+}
+
+/// converts the value of a native signed integer field, widened to an `i64`, to the bits of a field with the given bit
+/// length, returning an error if the value does not fit in the field.
+pub const fn try_narrow_signed(value: i64, len: usize) -> Result<u64, TryFromBitIntError> {
+    let bits = extract_bits(value as u64, 0, len);
+    if sign_extend_bits(bits, len) == value {
+        Ok(bits)
+    } else {
+        Err(TryFromBitIntError(()))
+    }
+}
+
+/// like [`try_narrow_unsigned`], but panics if the value does not fit in the field.
+#[track_caller]
+pub const fn narrow_unsigned(value: u64, len: usize) -> u64 {
+    match try_narrow_unsigned(value, len) {
+        Ok(bits) => bits,
+        Err(_) => panic!("value does not fit in the bit length of the field"),
+    }
+}
+
+/// like [`try_narrow_signed`], but panics if the value does not fit in the field.
+#[track_caller]
+pub const fn narrow_signed(value: i64, len: usize) -> u64 {
+    match try_narrow_signed(value, len) {
+        Ok(bits) => bits,
+        Err(_) => panic!("value does not fit in the bit length of the field"),
+    }
+}
+
+/// sign extends the `len` lowest bits of a value, treating bit `len - 1` as the sign bit. the bits above `len` are ignored.
+#[inline(always)]
+pub const fn sign_extend_bits(value: u64, len: usize) -> i64 {
+    debug_assert!(len > 0 && len <= 64);
+    let shift = 64 - len;
+    ((value << shift) as i64) >> shift
+}
+
 pub const fn const_array_max_u64(array: &[u64]) -> u64 {
     let mut maybe_max = None;
     use const_for::const_for;
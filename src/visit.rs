@@ -0,0 +1,54 @@
+use crate::*;
+
+/// a visitor which receives the typed values of the fields of a bitpiece, see [`BitPieceVisit`].
+///
+/// all methods do nothing by default, so a visitor only needs to implement the methods it is interested in. the `name`
+/// passed to each method is the name of the visited field, or an empty string for the outermost visited value.
+pub trait BitPieceVisitor {
+    /// called for a `bool` value.
+    fn visit_bool(&mut self, name: &str, value: bool) {
+        let _ = (name, value);
+    }
+
+    /// called for an unsigned integer value with the given bit length, for example a `B5` or a `u8`.
+    fn visit_unsigned(&mut self, name: &str, value: u64, bits: usize) {
+        let _ = (name, value, bits);
+    }
+
+    /// called for a signed integer value with the given bit length, for example an `SB5` or an `i8`.
+    fn visit_signed(&mut self, name: &str, value: i64, bits: usize) {
+        let _ = (name, value, bits);
+    }
+
+    /// called for a floating point value with the given bit length, for example an `f32`.
+    fn visit_float(&mut self, name: &str, value: f64, bits: usize) {
+        let _ = (name, value, bits);
+    }
+
+    /// called for an enum value, with the name of its variant and its raw value.
+    fn visit_enum(&mut self, name: &str, variant_name: &str, raw: u64) {
+        let _ = (name, variant_name, raw);
+    }
+
+    /// called before visiting the fields of a struct.
+    fn enter_struct(&mut self, name: &str, type_name: &str) {
+        let _ = (name, type_name);
+    }
+
+    /// called after visiting the fields of a struct.
+    fn exit_struct(&mut self, name: &str, type_name: &str) {
+        let _ = (name, type_name);
+    }
+}
+
+/// a bitpiece whose value can be walked using a [`BitPieceVisitor`].
+pub trait BitPieceVisit: BitPiece {
+    /// passes this value to the given visitor, as a field with the given name. structs pass each of their fields to
+    /// the visitor, recursively.
+    fn visit_as(&self, name: &str, visitor: &mut impl BitPieceVisitor);
+
+    /// passes this value to the given visitor. structs pass each of their fields to the visitor, recursively.
+    fn visit(&self, visitor: &mut impl BitPieceVisitor) {
+        self.visit_as("", visitor)
+    }
+}
@@ -0,0 +1,179 @@
+//! Tests for the `arbitrary` implementations of bitpiece types.
+#![cfg(feature = "arbitrary")]
+
+use bitpiece::arbitrary::{Arbitrary, Unstructured};
+use bitpiece::*;
+
+#[bitpiece(2, all, arbitrary)]
+#[derive(Debug, PartialEq, Eq)]
+enum Kind {
+    Data = 0,
+    Ack = 1,
+    Reset = 3,
+}
+
+#[bitpiece(20, all, arbitrary)]
+#[derive(Debug, PartialEq, Eq)]
+struct Header {
+    kind: Kind,
+    len: B7,
+    offset: SB3,
+    seq: u8,
+}
+
+/// a percentage, where values above 100 are invalid.
+mod percent {
+    pub const fn encode(value: u8) -> u8 {
+        value
+    }
+    pub const fn decode(bits: u8) -> u8 {
+        bits
+    }
+    pub const fn is_valid(bits: u8) -> bool {
+        bits <= 100
+    }
+}
+
+#[bitpiece(8, all, arbitrary)]
+#[derive(Debug, PartialEq, Eq)]
+struct Duty {
+    #[bitpiece(with = percent, bits = 7, validate = percent::is_valid)]
+    percent: u8,
+    enabled: bool,
+}
+
+const fn is_valid_range(range: Range) -> bool {
+    range.start().get() <= range.end().get()
+}
+
+#[bitpiece(8, all, arbitrary, validate = is_valid_range)]
+#[derive(Debug, PartialEq, Eq)]
+struct Range {
+    start: B4,
+    end: B4,
+}
+
+/// returns deterministic pseudo random bytes to feed to `Unstructured`.
+fn bytes(len: usize) -> Vec<u8> {
+    let mut state = 0x2545f491u32;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect()
+}
+
+#[test]
+fn b_and_sb_types_stay_in_range() {
+    let data = bytes(4096);
+    let mut u = Unstructured::new(&data);
+    let mut seen_max = false;
+    let mut seen_min = false;
+    for _ in 0..500 {
+        let b = B5::arbitrary(&mut u).unwrap();
+        let sb = SB5::arbitrary(&mut u).unwrap();
+        assert!(b <= B5::MAX);
+        assert!(sb >= SB5::MIN && sb <= SB5::MAX);
+        seen_max |= b == B5::MAX;
+        seen_min |= sb == SB5::MIN;
+    }
+    // the edges of the range are preferred
+    assert!(seen_max && seen_min);
+}
+
+#[test]
+fn fixed_types_stay_in_range() {
+    let data = bytes(4096);
+    let mut u = Unstructured::new(&data);
+    let mut seen_min = false;
+    for _ in 0..500 {
+        let value = SFixed::<2, 3>::arbitrary(&mut u).unwrap();
+        assert!(value.raw() >= -16 && value.raw() <= 15);
+        seen_min |= value == SFixed::<2, 3>::MIN;
+    }
+    assert!(seen_min);
+}
+
+#[test]
+fn enums_only_generate_valid_variants() {
+    let data = bytes(1024);
+    let mut u = Unstructured::new(&data);
+    let mut seen = Vec::new();
+    for _ in 0..100 {
+        let kind = Kind::arbitrary(&mut u).unwrap();
+        if !seen.contains(&kind) {
+            seen.push(kind);
+        }
+    }
+    assert_eq!(seen.len(), Kind::VARIANT_COUNT);
+}
+
+#[test]
+fn structs_are_built_from_fields() {
+    let data = bytes(4096);
+    let mut u = Unstructured::new(&data);
+    for _ in 0..200 {
+        let header = Header::arbitrary(&mut u).unwrap();
+        assert_eq!(Header::try_from_bits(header.to_bits()), Some(header));
+    }
+}
+
+#[test]
+fn raw_bits_near_the_edges() {
+    let data = bytes(4096);
+    let mut u = Unstructured::new(&data);
+    let mut seen_invalid = false;
+    for _ in 0..200 {
+        let bits = arbitrary_raw_bits::<Kind>(&mut u).unwrap();
+        assert!(bits <= 0b11);
+        seen_invalid |= Kind::try_from_bits(bits).is_none();
+    }
+    assert!(seen_invalid);
+}
+
+#[test]
+fn raw_bits_of_single_bit_signed_type() {
+    // the bits of the min value of `SB1` are above the bits of its max value
+    let data = bytes(1024);
+    let mut u = Unstructured::new(&data);
+    for _ in 0..100 {
+        let bits = arbitrary_raw_bits::<SB1>(&mut u).unwrap();
+        assert!(bits <= 1);
+        let value = SB1::arbitrary(&mut u).unwrap();
+        assert!(value == SB1::MIN || value == SB1::MAX);
+    }
+}
+
+#[test]
+fn structs_breaking_invariants_are_rejected() {
+    let data = bytes(4096);
+    let mut u = Unstructured::new(&data);
+    let mut seen_rejected = false;
+    for _ in 0..200 {
+        match Range::arbitrary(&mut u) {
+            Ok(range) => assert!(range.start() <= range.end()),
+            Err(err) => {
+                assert_eq!(err, bitpiece::arbitrary::Error::IncorrectFormat);
+                seen_rejected = true;
+            }
+        }
+    }
+    assert!(seen_rejected);
+}
+
+#[test]
+fn converted_fields_are_decoded_from_valid_bits() {
+    let data = bytes(4096);
+    let mut u = Unstructured::new(&data);
+    let mut seen_rejected = false;
+    for _ in 0..200 {
+        match Duty::arbitrary(&mut u) {
+            Ok(duty) => assert!(duty.percent() <= 100),
+            Err(_) => seen_rejected = true,
+        }
+    }
+    assert!(seen_rejected);
+}
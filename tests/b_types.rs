@@ -1,4 +1,5 @@
 //! Tests for unsigned arbitrary-width types (B1-B64).
+#![allow(clippy::clone_on_copy)]
 
 mod common;
 
@@ -440,3 +441,190 @@ fn all_b_types_exist() {
     assert_eq!(B63::BITS, 63);
     assert_eq!(B64::BITS, 64);
 }
+
+// =============================================================================
+// Arithmetic tests
+// =============================================================================
+
+#[test]
+fn b_type_wrapping_arithmetic() {
+    assert_eq!(B5::new(30).wrapping_add(B5::new(5)), B5::new(3));
+    assert_eq!(B5::new(2).wrapping_sub(B5::new(5)), B5::new(29));
+    assert_eq!(B5::new(7).wrapping_mul(B5::new(5)), B5::new(3));
+    assert_eq!(B5::new(1).wrapping_neg(), B5::new(31));
+    assert_eq!(B5::new(0).wrapping_neg(), B5::new(0));
+    assert_eq!(B64::MAX.wrapping_add(B64::new(1)), B64::new(0));
+}
+
+#[test]
+fn b_type_checked_arithmetic() {
+    assert_eq!(B5::new(30).checked_add(B5::new(1)), Some(B5::new(31)));
+    assert_eq!(B5::new(30).checked_add(B5::new(2)), None);
+    assert_eq!(B5::new(2).checked_sub(B5::new(3)), None);
+    assert_eq!(B5::new(6).checked_mul(B5::new(5)), Some(B5::new(30)));
+    assert_eq!(B5::new(8).checked_mul(B5::new(4)), None);
+    assert_eq!(B5::new(0).checked_neg(), Some(B5::new(0)));
+    assert_eq!(B5::new(1).checked_neg(), None);
+    assert_eq!(B64::MAX.checked_mul(B64::new(2)), None);
+}
+
+#[test]
+fn b_type_saturating_and_overflowing_arithmetic() {
+    assert_eq!(B5::new(30).saturating_add(B5::new(5)), B5::MAX);
+    assert_eq!(B5::new(2).saturating_sub(B5::new(5)), B5::new(0));
+    assert_eq!(B5::new(8).saturating_mul(B5::new(8)), B5::MAX);
+    assert_eq!(B5::new(30).overflowing_add(B5::new(5)), (B5::new(3), true));
+    assert_eq!(
+        B5::new(30).overflowing_add(B5::new(1)),
+        (B5::new(31), false)
+    );
+    assert_eq!(B5::new(2).overflowing_sub(B5::new(5)), (B5::new(29), true));
+    assert_eq!(B5::new(8).overflowing_mul(B5::new(4)), (B5::new(0), true));
+}
+
+#[test]
+fn b_type_wrapping_and_saturating_new() {
+    assert_eq!(B5::wrapping_new(35), B5::new(3));
+    assert_eq!(B5::wrapping_new(31), B5::new(31));
+    assert_eq!(B5::saturating_new(35), B5::MAX);
+    assert_eq!(B5::saturating_new(17), B5::new(17));
+}
+
+#[test]
+fn b_type_shifts() {
+    assert_eq!(B5::new(0b10011).wrapping_shl(1), B5::new(0b00110));
+    assert_eq!(B5::new(0b10011).wrapping_shr(1), B5::new(0b01001));
+    assert_eq!(B5::new(1).wrapping_shl(6), B5::new(2));
+    assert_eq!(B5::new(1).checked_shl(4), Some(B5::new(16)));
+    assert_eq!(B5::new(1).checked_shl(5), None);
+    assert_eq!(B5::new(16).checked_shr(5), None);
+}
+
+#[test]
+fn b_type_operators() {
+    assert_eq!(B5::new(3) + B5::new(4), B5::new(7));
+    assert_eq!(B5::new(7) - B5::new(4), B5::new(3));
+    assert_eq!(B5::new(3) * B5::new(4), B5::new(12));
+    assert_eq!(B5::new(0b10011) << 1, B5::new(0b00110));
+    assert_eq!(B5::new(0b10011) >> 2, B5::new(0b00100));
+
+    let mut counter = B5::new(30);
+    counter += B5::new(1);
+    assert_eq!(counter, B5::MAX);
+    counter -= B5::new(11);
+    counter *= B5::new(1);
+    counter <<= 1;
+    counter >>= 2;
+    assert_eq!(counter, B5::new(2));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn b_type_operators_panic_on_overflow() {
+    expect_panic(|| {
+        let _ = B5::MAX + B5::new(1);
+    });
+    expect_panic(|| {
+        let _ = B5::new(0) - B5::new(1);
+    });
+    expect_panic(|| {
+        let _ = B5::new(8) * B5::new(4);
+    });
+    expect_panic(|| {
+        let _ = B5::new(1) << 5;
+    });
+    expect_panic(|| {
+        let _ = B5::new(1) >> 5;
+    });
+}
+
+#[test]
+fn b_type_arithmetic_in_const_context() {
+    const SEQ: B3 = B3::MAX.wrapping_add(B3::new(2));
+    const SUM: Option<B3> = B3::new(3).checked_add(B3::new(4));
+    assert_eq!(SEQ, B3::new(1));
+    assert_eq!(SUM, Some(B3::new(7)));
+}
+
+// =============================================================================
+// Generic width tests
+// =============================================================================
+
+/// sums values of any bit length, using only the `BitPiece` trait.
+fn sum_bits<const N: usize>(values: &[UBits<N>]) -> u64
+where
+    BitLength<N>: AssociatedStorage,
+{
+    values.iter().map(|value| value.to_bits().to_u64()).sum()
+}
+
+/// increments a value of any bit length, saturating at its max value, using only the `BitInt` trait.
+fn saturating_increment<const N: usize>(value: UBits<N>) -> UBits<N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    UBits::<N>::saturating_from_i128(value.to_i128() + 1)
+}
+
+/// advances a sequence number of any bit length, using the arithmetic methods provided by the `BitInt` trait.
+fn next_sequence_number<const N: usize>(value: UBits<N>) -> (UBits<N>, Option<UBits<N>>)
+where
+    BitLength<N>: AssociatedStorage,
+{
+    let one = UBits::<N>::try_from_i128(1).unwrap();
+    (value.wrapping_add(one), value.checked_add(one))
+}
+
+#[test]
+fn b_types_are_aliases_of_ubits() {
+    let value: UBits<5> = B5::new(17);
+    assert_eq!(value, UBits::<5>::new(17));
+    assert_eq!(<UBits<12> as BitPiece>::BITS, 12);
+    assert_eq!(UBits::<3>::MAX, B3::MAX);
+}
+
+#[test]
+fn generic_over_width() {
+    assert_eq!(sum_bits(&[B5::new(17), B5::new(3)]), 20);
+    assert_eq!(sum_bits(&[B40::MAX]), (1 << 40) - 1);
+}
+
+#[test]
+fn generic_arithmetic_using_bit_int() {
+    assert_eq!(saturating_increment(B5::new(17)), B5::new(18));
+    assert_eq!(saturating_increment(B5::MAX), B5::MAX);
+    assert_eq!(saturating_increment(B64::new(7)), B64::new(8));
+    assert_eq!(UBits::<12>::try_from_i128(4096), None);
+}
+
+#[test]
+fn generic_constructors_and_arithmetic() {
+    assert_eq!(
+        next_sequence_number(B5::new(17)),
+        (B5::new(18), Some(B5::new(18)))
+    );
+    assert_eq!(next_sequence_number(B5::MAX), (B5::new(0), None));
+    assert_eq!(next_sequence_number(B40::MAX), (B40::new(0), None));
+
+    fn round_trip<const N: usize>(value: UBits<N>) -> Option<UBits<N>>
+    where
+        BitLength<N>: AssociatedStorage,
+    {
+        UBits::<N>::try_new(value.get())
+    }
+    assert_eq!(round_trip(B12::new(4095)), Some(B12::new(4095)));
+}
+
+#[test]
+fn generic_operators() {
+    fn double<const N: usize>(value: UBits<N>) -> UBits<N>
+    where
+        BitLength<N>: AssociatedStorage,
+    {
+        value * UBits::<N>::try_from_i128(2).unwrap() + UBits::<N>::ZEROES
+    }
+    assert_eq!(double(B5::new(7)), B5::new(14));
+    assert_eq!(UBits::<5>::try_new(32), None);
+    assert_eq!(UBits::<5>::wrapping_new(33), B5::new(1));
+    assert_eq!(UBits::<5>::saturating_new(33), B5::MAX);
+}
@@ -0,0 +1,158 @@
+//! Tests for the biased and ranged integer types.
+
+use bitpiece::*;
+
+type Divider = Biased<B4, 1>;
+type Exponent = Biased<B8, { -127 }>;
+type Year = Ranged<1980, 2107>;
+type Level = Ranged<1, 10>;
+
+#[bitpiece(27, all, debug, layout, visit)]
+#[derive(PartialEq, Eq)]
+struct Config {
+    divider: Divider,
+    exponent: Exponent,
+    year: Year,
+    level: Level,
+    delta: Biased<SB4, 10>,
+}
+
+#[test]
+fn bit_lens() {
+    assert_eq!(Divider::BITS, 4);
+    assert_eq!(Exponent::BITS, 8);
+    assert_eq!(Year::BITS, 7);
+    assert_eq!(Level::BITS, 4);
+    assert_eq!(Ranged::<5, 5>::BITS, 1);
+    assert_eq!(Ranged::<{ i64::MIN }, { i64::MAX }>::BITS, 64);
+    assert_eq!(Biased::<u8, 0>::BITS, 8);
+}
+
+#[test]
+fn get_and_new_hide_the_bias() {
+    assert_eq!(Divider::new(1).to_bits(), 0);
+    assert_eq!(Divider::new(16).to_bits(), 15);
+    assert_eq!(Divider::from_bits(3).get(), 4);
+    assert_eq!(Exponent::from_bits(0).get(), -127);
+    assert_eq!(Exponent::new(0).to_bits(), 127);
+    assert_eq!(Year::new(2024).to_bits(), 44);
+    assert_eq!(Year::from_bits(127).get(), 2107);
+    assert_eq!(Biased::<SB4, 10>::from_bits(0b1000).get(), 2);
+    assert_eq!(Biased::<SB4, 10>::new(9).to_bits(), 0b1111);
+}
+
+#[test]
+fn range_limits() {
+    assert_eq!(Divider::MIN.get(), 1);
+    assert_eq!(Divider::MAX.get(), 16);
+    assert_eq!(Exponent::MAX.get(), 128);
+    assert_eq!(Biased::<SB4, 10>::MIN.get(), 2);
+    assert_eq!(Biased::<SB4, 10>::MAX.get(), 17);
+    assert_eq!(Level::MIN.get(), 1);
+    assert_eq!(Level::MAX.get(), 10);
+    assert_eq!(Level::ZEROES, Level::MIN);
+    assert_eq!(Level::ONES, Level::MAX);
+    assert_eq!(Divider::ONES, Divider::MAX);
+
+    assert_eq!(Divider::try_new(0), None);
+    assert_eq!(Divider::try_new(17), None);
+    assert_eq!(Year::try_new(1979), None);
+    assert_eq!(Level::try_new(10).map(|level| level.get()), Some(10));
+    assert_eq!(Level::try_new(11), None);
+}
+
+#[test]
+fn try_from_bits_rejects_out_of_range_raw_bits() {
+    assert!(Level::try_from_bits(9).is_some());
+    assert_eq!(Level::try_from_bits(10), None);
+    assert_eq!(Level::try_from_bits(15), None);
+    assert_eq!(Divider::try_from_bits(16), None);
+    assert!(Year::try_from_bits(127).is_some());
+    assert_eq!(Year::try_from_bits(128), None);
+    assert_eq!(Config::try_from_bits(10 << 19), None);
+    assert!(Config::try_from_bits(9 << 19).is_some());
+}
+
+#[test]
+fn struct_fields() {
+    let mut config = Config::from_bits(0);
+    config.set_divider(Divider::new(8));
+    config.set_exponent(Exponent::new(-1));
+    config.set_year(Year::new(1990));
+    assert_eq!(config.divider().get(), 7 + 1);
+    assert_eq!(config.storage & 0xfff, 0x7e7);
+    assert_eq!(config.year().get(), 1990);
+    assert_eq!(config.level(), Level::MIN);
+    assert_eq!(
+        format!("{config:?}"),
+        "Config { divider: 8, exponent: -1, year: 1990, level: 1, delta: 10 }"
+    );
+}
+
+#[test]
+fn layout_and_visit() {
+    let year = &Config::FIELDS[2];
+    assert_eq!(year.type_name, "Ranged");
+    assert!(!year.is_signed);
+    assert_eq!((year.offset, year.len), (12, 7));
+    // the signedness follows the range of values, not the raw integer type
+    assert!(Config::FIELDS[1].is_signed);
+    assert!(!Config::FIELDS[4].is_signed);
+
+    let mut config = Config::from_bits(0);
+    assert_eq!(
+        config.set_field("level", 12),
+        Err(FieldAccessError::InvalidValue)
+    );
+    assert_eq!(config.set_field("level", 2), Ok(()));
+    assert_eq!(config.level().get(), 3);
+
+    struct Collect(Vec<String>);
+    impl BitPieceVisitor for Collect {
+        fn visit_unsigned(&mut self, name: &str, value: u64, bits: usize) {
+            self.0.push(format!("{name}: u{bits} = {value}"));
+        }
+        fn visit_signed(&mut self, name: &str, value: i64, bits: usize) {
+            self.0.push(format!("{name}: i{bits} = {value}"));
+        }
+    }
+    let mut collect = Collect(Vec::new());
+    config.visit(&mut collect);
+    assert_eq!(
+        collect.0,
+        [
+            "divider: u4 = 1",
+            "exponent: i8 = -127",
+            "year: u7 = 1980",
+            "level: u4 = 3",
+            "delta: u4 = 10"
+        ]
+    );
+}
+
+#[test]
+fn mut_struct() {
+    let mut config = Config::from_bits(0);
+    let mut year = config.year_mut();
+    year.set(Year::new(2000));
+    assert_eq!(year.get().get(), 2000);
+    assert_eq!(config.year().get(), 2000);
+}
+
+#[test]
+fn const_context() {
+    const CONFIG: Config = Config::from_bits(0)
+        .with_divider(Divider::new(4))
+        .with_level(Level::new(5));
+    const DIVIDER: i64 = CONFIG.divider().get();
+    assert_eq!(DIVIDER, 4);
+    assert_eq!(CONFIG.level().get(), 5);
+}
+
+#[test]
+fn passes_testing_harness() {
+    testing::check::<Divider>();
+    testing::check::<Level>();
+    testing::check::<Biased<SB4, 10>>();
+    testing::check::<Config>();
+}
@@ -0,0 +1,96 @@
+//! Tests for the `BitInt` trait, which unifies the integer bitpiece types.
+#![allow(clippy::assertions_on_constants)]
+
+use bitpiece::*;
+
+#[bitpiece(20, all)]
+#[derive(Debug, PartialEq, Eq)]
+struct Sample {
+    level: B5,
+    offset: SB7,
+    count: u8,
+}
+
+/// sums any integer values as an `i64`.
+fn sum_as_i64<T: BitInt>(values: &[T]) -> i64 {
+    values.iter().map(|value| value.to_i128() as i64).sum()
+}
+
+/// clamps the given value into the range of the given field type.
+fn clamp_into<T: BitInt>(value: i64) -> T {
+    T::saturating_from_i128(value as i128)
+}
+
+/// returns whether an integer type is signed, which is given by the `BitPieceHasLayout` supertrait of `BitInt`.
+fn is_signed<T: BitInt>() -> bool {
+    T::IS_SIGNED
+}
+
+#[test]
+fn constants() {
+    assert_eq!(<B5 as BitInt>::MIN_VALUE, 0u8);
+    assert_eq!(<B5 as BitInt>::MAX_VALUE, 31u8);
+    assert_eq!(<SB7 as BitInt>::MIN_VALUE, -64i8);
+    assert_eq!(<SB7 as BitInt>::MAX_VALUE, 63i8);
+    assert_eq!(<B33 as BitInt>::MAX_VALUE, (1u64 << 33) - 1);
+    assert_eq!(<u16 as BitInt>::MAX_VALUE, u16::MAX);
+    assert_eq!(<i32 as BitInt>::MIN_VALUE, i32::MIN);
+    assert!(is_signed::<SB7>());
+    assert!(!is_signed::<B5>());
+    assert!(is_signed::<i16>());
+}
+
+#[test]
+fn to_i128_and_as_u64_bits() {
+    assert_eq!(B5::new(17).to_i128(), 17);
+    assert_eq!(SB7::new(-5).to_i128(), -5);
+    assert_eq!(BitInt::to_i128(u64::MAX), u64::MAX as i128);
+    assert_eq!(BitInt::to_i128(i64::MIN), i64::MIN as i128);
+    assert_eq!(BitInt::as_u64_bits(B5::new(17)), 17);
+    assert_eq!(BitInt::as_u64_bits(SB7::new(-1)), u64::MAX);
+    assert_eq!(BitInt::as_u64_bits(-2i8), (-2i64) as u64);
+    // the storage of a value can still be converted using `BitStorage::to_u64` without disambiguation.
+    assert_eq!(B5::new(3).to_bits().to_u64(), 3);
+    assert_eq!(200u8.to_u64(), 200);
+}
+
+#[test]
+fn try_from_i128() {
+    assert_eq!(B5::try_from_i128(31), Some(B5::MAX));
+    assert_eq!(B5::try_from_i128(32), None);
+    assert_eq!(B5::try_from_i128(-1), None);
+    assert_eq!(SB7::try_from_i128(-64), Some(SB7::MIN));
+    assert_eq!(SB7::try_from_i128(-65), None);
+    assert_eq!(u8::try_from_i128(255), Some(255));
+    assert_eq!(i8::try_from_i128(128), None);
+    assert_eq!(B64::try_from_i128(u64::MAX as i128), Some(B64::MAX));
+    assert_eq!(B64::try_from_i128(u64::MAX as i128 + 1), None);
+}
+
+#[test]
+fn saturating_from_i128() {
+    assert_eq!(B5::saturating_from_i128(100), B5::MAX);
+    assert_eq!(B5::saturating_from_i128(-100), B5::new(0));
+    assert_eq!(SB7::saturating_from_i128(-100), SB7::MIN);
+    assert_eq!(i16::saturating_from_i128(i128::MAX), i16::MAX);
+}
+
+#[test]
+fn generic_helpers_over_fields() {
+    let sample = Sample::from_fields(SampleFields {
+        level: B5::new(20),
+        offset: SB7::new(-30),
+        count: 200,
+    });
+    assert_eq!(sum_as_i64(&[sample.level(), B5::new(3)]), 23);
+    assert_eq!(sum_as_i64(&[sample.offset(), SB7::new(-4)]), -34);
+    assert_eq!(sum_as_i64(&[sample.count()]), 200);
+
+    let mut sample = sample;
+    sample.set_level(clamp_into(1000));
+    sample.set_offset(clamp_into(-1000));
+    sample.set_count(clamp_into(-3));
+    assert_eq!(sample.level(), B5::MAX);
+    assert_eq!(sample.offset(), SB7::MIN);
+    assert_eq!(sample.count(), 0);
+}
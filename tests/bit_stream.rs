@@ -0,0 +1,248 @@
+//! Tests for the bit stream reader and writer.
+#![allow(clippy::unusual_byte_groupings)]
+
+mod common;
+
+use bitpiece::*;
+use common::expect_panic;
+
+#[bitpiece(2, all)]
+#[derive(Debug, PartialEq, Eq)]
+enum Kind {
+    A = 0,
+    B = 1,
+    C = 2,
+}
+
+#[bitpiece(7, all)]
+#[derive(Debug, PartialEq, Eq)]
+struct Header {
+    kind: Kind,
+    flag: bool,
+    len: B4,
+}
+
+// =============================================================================
+// Reading
+// =============================================================================
+
+#[test]
+fn read_bits_msb_first() {
+    let data = [0b1011_0010, 0b1100_0000];
+    let mut reader = BitReader::new(&data, BitOrder::MsbFirst);
+    assert_eq!(reader.read_bits(1), Ok(0b1));
+    assert_eq!(reader.read_bits(3), Ok(0b011));
+    assert_eq!(reader.read_bits(6), Ok(0b0010_11));
+    assert_eq!(reader.position(), 10);
+    assert_eq!(reader.remaining(), 6);
+}
+
+#[test]
+fn read_bits_lsb_first() {
+    let data = [0b1011_0010, 0b1100_0011];
+    let mut reader = BitReader::new(&data, BitOrder::LsbFirst);
+    assert_eq!(reader.read_bits(1), Ok(0b0));
+    assert_eq!(reader.read_bits(3), Ok(0b001));
+    assert_eq!(reader.read_bits(6), Ok(0b11_1011));
+    assert_eq!(reader.position(), 10);
+    assert_eq!(reader.remaining(), 6);
+}
+
+#[test]
+fn read_bits_full_width() {
+    let data = 0x0123_4567_89ab_cdefu64.to_be_bytes();
+    let mut reader = BitReader::new(&data, BitOrder::MsbFirst);
+    assert_eq!(reader.read_bits(64), Ok(0x0123_4567_89ab_cdef));
+
+    let data = 0x0123_4567_89ab_cdefu64.to_le_bytes();
+    let mut reader = BitReader::new(&data, BitOrder::LsbFirst);
+    assert_eq!(reader.read_bits(64), Ok(0x0123_4567_89ab_cdef));
+}
+
+#[test]
+fn read_bits_past_end_fails_without_consuming() {
+    let data = [0xff];
+    let mut reader = BitReader::new(&data, BitOrder::MsbFirst);
+    assert_eq!(reader.read_bits(5), Ok(0b11111));
+    assert_eq!(reader.read_bits(4), Err(BitStreamError::EndOfStream));
+    assert_eq!(reader.position(), 5);
+    assert_eq!(reader.read_bits(3), Ok(0b111));
+    assert_eq!(reader.read_bit(), Err(BitStreamError::EndOfStream));
+}
+
+#[test]
+fn read_bits_more_than_64_panics() {
+    expect_panic(|| {
+        let data = [0u8; 16];
+        let mut reader = BitReader::new(&data, BitOrder::MsbFirst);
+        let _ = reader.read_bits(65);
+    });
+}
+
+#[test]
+fn read_bitpieces() {
+    // kind = C, flag = true, len = 9, then an SB3 of -2
+    let data = [0b1001_1_10_1, 0b10_000000];
+    let mut reader = BitReader::new(&data, BitOrder::MsbFirst);
+    let header: Header = reader.read().unwrap();
+    assert_eq!(header.to_bits(), 0b1001_1_10);
+    assert_eq!(reader.read::<SB3>(), Ok(SB3::new(-2)));
+    assert_eq!(reader.position(), 10);
+}
+
+#[test]
+fn read_validates_values() {
+    // kind = 3 is not a valid variant
+    let data = [0b11_000000];
+    let mut reader = BitReader::new(&data, BitOrder::MsbFirst);
+    assert_eq!(reader.read::<Kind>(), Err(BitStreamError::InvalidValue));
+    // the position is left unchanged on failure
+    assert_eq!(reader.position(), 0);
+    assert_eq!(reader.read::<B2>(), Ok(B2::new(3)));
+}
+
+#[test]
+fn reader_align_to_byte() {
+    let data = [0xff, 0xab];
+    let mut reader = BitReader::new(&data, BitOrder::MsbFirst);
+    reader.align_to_byte();
+    assert_eq!(reader.position(), 0);
+    reader.read_bits(3).unwrap();
+    assert!(!reader.is_byte_aligned());
+    reader.align_to_byte();
+    assert!(reader.is_byte_aligned());
+    assert_eq!(reader.read::<u8>(), Ok(0xab));
+}
+
+#[test]
+fn reader_skip_bits() {
+    let data = [0b0000_1111];
+    let mut reader = BitReader::new(&data, BitOrder::MsbFirst);
+    reader.skip_bits(4).unwrap();
+    assert_eq!(reader.read::<B4>(), Ok(B4::new(0xf)));
+    assert_eq!(reader.skip_bits(1), Err(BitStreamError::EndOfStream));
+}
+
+// =============================================================================
+// Writing
+// =============================================================================
+
+#[test]
+fn write_bits_msb_first() {
+    let mut buf = [0u8; 2];
+    let mut writer = BitWriter::new(&mut buf, BitOrder::MsbFirst);
+    writer.write_bits(0b1, 1).unwrap();
+    writer.write_bits(0b011, 3).unwrap();
+    writer.write_bits(0b0010_11, 6).unwrap();
+    assert_eq!(writer.position(), 10);
+    assert_eq!(writer.written(), &[0b1011_0010, 0b1100_0000]);
+}
+
+#[test]
+fn write_bits_lsb_first() {
+    let mut buf = [0u8; 2];
+    let mut writer = BitWriter::new(&mut buf, BitOrder::LsbFirst);
+    writer.write_bits(0b0, 1).unwrap();
+    writer.write_bits(0b001, 3).unwrap();
+    writer.write_bits(0b11_1011, 6).unwrap();
+    assert_eq!(writer.written(), &[0b1011_0010, 0b0000_0011]);
+}
+
+#[test]
+fn write_bits_ignores_high_bits() {
+    let mut buf = [0u8; 1];
+    let mut writer = BitWriter::new(&mut buf, BitOrder::MsbFirst);
+    writer.write_bits(0xff_f0, 4).unwrap();
+    writer.write_bits(0xff_ff, 4).unwrap();
+    assert_eq!(buf, [0x0f]);
+}
+
+#[test]
+fn write_overwrites_existing_bits() {
+    let mut buf = [0xff; 2];
+    let mut writer = BitWriter::new(&mut buf, BitOrder::LsbFirst);
+    writer.write(B3::new(0)).unwrap();
+    writer.write(B7::new(0b1010101)).unwrap();
+    // the rest of the buffer is left untouched
+    assert_eq!(buf, [0b1010_1000, 0b1111_1110]);
+}
+
+#[test]
+fn write_past_end_fails_without_consuming() {
+    let mut buf = [0u8; 1];
+    let mut writer = BitWriter::new(&mut buf, BitOrder::MsbFirst);
+    writer.write(B6::new(0b111111)).unwrap();
+    assert_eq!(writer.write(B3::new(0)), Err(BitStreamError::EndOfStream));
+    assert_eq!(writer.position(), 6);
+    writer.write(B2::new(0b01)).unwrap();
+    assert_eq!(writer.remaining(), 0);
+    assert_eq!(buf, [0b1111_1101]);
+}
+
+#[test]
+fn writer_align_to_byte_pads_with_zeroes() {
+    let mut buf = [0xff; 2];
+    let mut writer = BitWriter::new(&mut buf, BitOrder::MsbFirst);
+    writer.write(true).unwrap();
+    writer.align_to_byte();
+    assert_eq!(writer.position(), 8);
+    writer.align_to_byte();
+    assert_eq!(writer.position(), 8);
+    assert_eq!(writer.written(), &[0b1000_0000]);
+}
+
+// =============================================================================
+// Roundtrips
+// =============================================================================
+
+fn roundtrip(bit_order: BitOrder) {
+    let header = Header::from_fields(HeaderFields {
+        kind: Kind::B,
+        flag: true,
+        len: B4::new(11),
+    });
+    let mut buf = [0u8; 16];
+    let mut writer = BitWriter::new(&mut buf, bit_order);
+    writer.write(header).unwrap();
+    writer.write(SB13::new(-1234)).unwrap();
+    writer.write(0xdead_beef_u32).unwrap();
+    writer.write(-5i8).unwrap();
+    writer.align_to_byte();
+    writer.write(B1::new(1)).unwrap();
+    let written_bits = writer.position();
+
+    let mut reader = BitReader::new(&buf, bit_order);
+    assert_eq!(reader.read::<Header>(), Ok(header));
+    assert_eq!(reader.read::<SB13>(), Ok(SB13::new(-1234)));
+    assert_eq!(reader.read::<u32>(), Ok(0xdead_beef));
+    assert_eq!(reader.read::<i8>(), Ok(-5));
+    reader.align_to_byte();
+    assert_eq!(reader.read::<B1>(), Ok(B1::new(1)));
+    assert_eq!(reader.position(), written_bits);
+}
+
+#[test]
+fn roundtrip_msb_first() {
+    roundtrip(BitOrder::MsbFirst);
+}
+
+#[test]
+fn roundtrip_lsb_first() {
+    roundtrip(BitOrder::LsbFirst);
+}
+
+#[test]
+fn const_bit_reader() {
+    const DATA: [u8; 2] = [0xab, 0xcd];
+    const VALUE: u64 = {
+        let mut reader = BitReader::new(&DATA, BitOrder::MsbFirst);
+        if reader.skip_bits(4).is_err() {
+            panic!()
+        }
+        match reader.read_bits(8) {
+            Ok(value) => value,
+            Err(_) => panic!(),
+        }
+    };
+    assert_eq!(VALUE, 0xbc);
+}
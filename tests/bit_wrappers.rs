@@ -0,0 +1,179 @@
+//! Tests for the `Inverted` and `Reversed` bit wrapper types.
+#![allow(clippy::assertions_on_constants, clippy::unusual_byte_groupings)]
+
+use bitpiece::*;
+
+#[bitpiece(16, all, debug, layout, visit)]
+#[derive(PartialEq, Eq)]
+struct Control {
+    reset: Inverted<bool>,
+    chip_select: Inverted<bool>,
+    gain: Inverted<B3>,
+    channel: Reversed<B5>,
+    trim: Reversed<SB6>,
+}
+
+#[bitpiece(2, all, layout)]
+#[derive(Debug, PartialEq, Eq)]
+enum Mode {
+    Off = 0,
+    Low = 1,
+    High = 2,
+}
+
+// =============================================================================
+// Inverted tests
+// =============================================================================
+
+#[test]
+fn inverted_complements_bits() {
+    assert_eq!(Inverted(true).to_bits(), 0);
+    assert_eq!(Inverted::<bool>::from_bits(0), Inverted(true));
+    assert_eq!(Inverted(B3::new(0b001)).to_bits(), 0b110);
+    assert_eq!(Inverted::<B3>::from_bits(0b010).get(), B3::new(0b101));
+    assert_eq!(Inverted(0x0fu8).to_bits(), 0xf0);
+    assert_eq!(Inverted(SB4::new(-1)).to_bits(), 0b0000);
+    assert_eq!(Inverted::<B3>::try_from_bits(0b1000), None);
+    assert_eq!(<Inverted<u16> as BitPiece>::BITS, 16);
+}
+
+#[test]
+fn inverted_constants() {
+    assert_eq!(<Inverted<bool> as BitPiece>::ZEROES, Inverted(true));
+    assert_eq!(<Inverted<bool> as BitPiece>::ONES, Inverted(false));
+    assert_eq!(<Inverted<B3> as BitPiece>::ONES.to_bits(), 0b111);
+    assert_eq!(<Inverted<B3> as BitPiece>::MIN, Inverted(B3::MIN));
+    assert_eq!(<Inverted<B3> as BitPiece>::MAX, Inverted(B3::MAX));
+}
+
+// =============================================================================
+// Reversed tests
+// =============================================================================
+
+#[test]
+fn reversed_reverses_bit_order() {
+    assert_eq!(Reversed(B4::new(0b0001)).to_bits(), 0b1000);
+    assert_eq!(Reversed::<B5>::from_bits(0b00011).get(), B5::new(0b11000));
+    assert_eq!(Reversed(0x01u8).to_bits(), 0x80);
+    assert_eq!(Reversed(1u64).to_bits(), 1 << 63);
+    assert_eq!(Reversed(true).to_bits(), 1);
+    assert_eq!(Reversed(SB4::new(1)).to_bits(), 0b1000);
+    assert_eq!(Reversed::<SB4>::from_bits(0b0001).get(), SB4::MIN);
+    assert_eq!(Reversed::<B5>::try_from_bits(0b100000), None);
+    assert_eq!(<Reversed<B5> as BitPiece>::ONES.to_bits(), 0b11111);
+}
+
+#[test]
+fn wraps_any_bitpiece_type() {
+    fn roundtrip<T: BitPiece>(value: T) -> u64 {
+        let bits = value.to_bits();
+        assert_eq!(T::from_bits(bits).to_bits().to_u64(), bits.to_u64());
+        bits.to_u64()
+    }
+    assert_eq!(roundtrip(Inverted(Mode::Off)), 0b11);
+    assert_eq!(roundtrip(Reversed(Mode::Low)), 0b10);
+    assert_eq!(<Inverted<Mode> as BitPiece>::try_from_bits(0b00), None);
+    assert_eq!(<Reversed<Mode> as BitPiece>::try_from_bits(0b11), None);
+    assert_eq!(<Inverted<Mode> as BitPieceHasLayout>::TYPE_NAME, "Inverted");
+
+    let mut storage = 0u8;
+    let mut mode = <Reversed<Mode> as BitPieceHasMutRef>::MutRef::new(
+        BitPieceStorageMutRef::U8(&mut storage),
+        1,
+    );
+    mode.set(Reversed(Mode::High));
+    assert_eq!(mode.get(), Reversed(Mode::High));
+    assert_eq!(storage, 0b010);
+}
+
+// =============================================================================
+// struct fields
+// =============================================================================
+
+#[test]
+fn struct_fields() {
+    let mut control = Control::from_bits(0xffff);
+    assert_eq!(control.reset(), Inverted(false));
+    assert_eq!(control.gain().get(), B3::new(0));
+
+    control.set_reset(Inverted(true));
+    control.set_gain(Inverted(B3::new(0b011)));
+    control.set_channel(Reversed(B5::new(0b00001)));
+    control.set_trim(Reversed(SB6::new(0)));
+    assert_eq!(control.storage, 0b000000_10000_100_1_0);
+    assert!(control.reset().get());
+    assert_eq!(control.channel().get(), B5::new(1));
+    assert_eq!(
+        format!("{control:?}"),
+        "Control { reset: true, chip_select: false, gain: 3, channel: 1, trim: 0 }"
+    );
+}
+
+#[test]
+fn layout_and_visit() {
+    let gain = &Control::FIELDS[2];
+    assert_eq!(gain.type_name, "Inverted");
+    assert_eq!((gain.offset, gain.len), (2, 3));
+    assert_eq!(Control::FIELDS[3].type_name, "Reversed");
+    assert!(Control::FIELDS[4].is_signed);
+
+    // raw field access works on the physical bits
+    let control = Control::from_bits(0).with_gain(Inverted(B3::new(0b001)));
+    assert_eq!(control.get_field("gain"), Some(0b110));
+
+    struct Collect(Vec<String>);
+    impl BitPieceVisitor for Collect {
+        fn visit_bool(&mut self, name: &str, value: bool) {
+            self.0.push(format!("{name}: {value}"));
+        }
+        fn visit_unsigned(&mut self, name: &str, value: u64, bits: usize) {
+            self.0.push(format!("{name}: u{bits} = {value}"));
+        }
+        fn visit_signed(&mut self, name: &str, value: i64, bits: usize) {
+            self.0.push(format!("{name}: i{bits} = {value}"));
+        }
+    }
+    let mut collect = Collect(Vec::new());
+    control.visit(&mut collect);
+    assert_eq!(
+        collect.0,
+        [
+            "reset: true",
+            "chip_select: true",
+            "gain: u3 = 1",
+            "channel: u5 = 0",
+            "trim: i6 = 0"
+        ]
+    );
+}
+
+#[test]
+fn mut_struct() {
+    let mut control = Control::from_bits(0);
+    let mut reset = control.reset_mut();
+    assert_eq!(reset.get(), Inverted(true));
+    reset.set(Inverted(false));
+    control.channel_mut().set(Reversed(B5::new(0b10000)));
+    assert_eq!(control.storage, 0b000000_00001_000_0_1);
+}
+
+#[test]
+fn const_context() {
+    const CONTROL: Control = Control::from_bits(0)
+        .with_reset(Inverted(false))
+        .with_trim(Reversed(SB6::new(-32)));
+    const RESET: bool = CONTROL.reset().get();
+    assert!(!RESET);
+    assert_eq!(CONTROL.storage, 0b000001_00000_000_0_1);
+}
+
+#[test]
+fn passes_testing_harness() {
+    testing::check::<Inverted<bool>>();
+    testing::check::<Inverted<SB7>>();
+    testing::check::<Reversed<B12>>();
+    testing::check::<Reversed<i32>>();
+    testing::check::<Inverted<Mode>>();
+    testing::check::<Reversed<Reversed<Mode>>>();
+    testing::check::<Control>();
+}
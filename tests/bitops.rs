@@ -0,0 +1,99 @@
+//! Tests for the bitwise operators of bitpiece structs.
+#![allow(clippy::unusual_byte_groupings)]
+
+use bitpiece::*;
+
+#[bitpiece(3, all, bitops)]
+#[derive(Debug, PartialEq, Eq)]
+struct Perm {
+    read: bool,
+    write: bool,
+    exec: bool,
+}
+
+#[bitpiece(12, all, bitops)]
+#[derive(Debug, PartialEq, Eq)]
+struct WithInts {
+    flag: bool,
+    level: B3,
+    offset: SB4,
+    #[bits(4)]
+    count: u8,
+}
+
+// the inherent methods of `bitops` don't clash with fields of the same name when the opt in is not used.
+#[bitpiece(2, all)]
+struct NamedLikeMethods {
+    is_empty: bool,
+    contains: bool,
+}
+
+const R: Perm = Perm::from_bits(0b001);
+const W: Perm = Perm::from_bits(0b010);
+const X: Perm = Perm::from_bits(0b100);
+
+#[test]
+fn operators() {
+    assert_eq!(R | W, Perm::from_bits(0b011));
+    assert_eq!((R | W) & W, W);
+    assert_eq!((R | W) ^ (W | X), R | X);
+    assert_eq!((R | W | X) & !W, R | X);
+}
+
+#[test]
+fn assign_operators() {
+    let mut perm = R;
+    perm |= X;
+    assert_eq!(perm, R | X);
+    perm &= X | W;
+    assert_eq!(perm, X);
+    perm ^= R | X;
+    assert_eq!(perm, R);
+}
+
+#[test]
+fn not_keeps_unused_bits_zero() {
+    assert_eq!((!R).storage, 0b110);
+    assert_eq!((!Perm::ZEROES).storage, 0b111);
+    assert_eq!(!Perm::ONES, Perm::ZEROES);
+}
+
+#[test]
+fn set_methods() {
+    const RWX: Perm = R.union(W).union(X);
+    const RX: Perm = RWX.difference(W);
+    assert_eq!(RX, R | X);
+    assert_eq!(RWX.intersection(W), W);
+    assert_eq!(RX.symmetric_difference(RWX), W);
+    assert_eq!(R.complement(), W | X);
+    assert!(RWX.contains(RX));
+    assert!(!RX.contains(W));
+    assert!(RX.contains(Perm::ZEROES));
+    assert!(Perm::ZEROES.is_empty());
+    assert!(!R.is_empty());
+}
+
+#[test]
+fn works_with_field_accessors() {
+    let perm = R | X;
+    assert!(perm.read());
+    assert!(!perm.write());
+    assert!(perm.exec());
+}
+
+#[test]
+fn works_with_int_fields() {
+    let a = WithInts::from_bits(0b0011_0101_011_0);
+    let b = WithInts::from_bits(0b0110_1100_001_1);
+    assert_eq!((a | b).storage, 0b0111_1101_011_1);
+    assert_eq!((a & b).offset(), SB4::new(4));
+    assert_eq!((!a).count(), 0b1100);
+    assert_eq!((!a).level(), B3::new(0b100));
+}
+
+#[test]
+fn fields_named_like_methods() {
+    let value = NamedLikeMethods::from_bits(0b01);
+    assert!(value.is_empty());
+    assert!(!value.contains());
+}
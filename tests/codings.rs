@@ -0,0 +1,298 @@
+//! Tests for the variable-length integer codings of the bit stream.
+
+use bitpiece::*;
+
+/// writes the given value into a buffer using MSB first bit order, and returns the written bits as a string of `0`s and `1`s.
+fn encode_to_bit_string<T: BitStreamEncode>(value: T) -> String {
+    let mut buf = [0u8; 32];
+    let mut writer = BitWriter::new(&mut buf, BitOrder::MsbFirst);
+    writer.write(value).unwrap();
+    let len = writer.position();
+    let mut reader = BitReader::new(&buf, BitOrder::MsbFirst);
+    (0..len)
+        .map(|_| if reader.read_bit().unwrap() { '1' } else { '0' })
+        .collect()
+}
+
+/// parses a string of `0`s and `1`s into bytes, padded with zero bits.
+fn bit_string_to_bytes(bits: &str) -> Vec<u8> {
+    let mut buf = vec![0u8; bits.len().div_ceil(8)];
+    let mut writer = BitWriter::new(&mut buf, BitOrder::MsbFirst);
+    for c in bits.chars() {
+        writer.write_bit(c == '1').unwrap();
+    }
+    buf
+}
+
+fn roundtrip<T: BitStreamEncode + Copy + PartialEq + core::fmt::Debug>(values: &[T]) {
+    for bit_order in [BitOrder::MsbFirst, BitOrder::LsbFirst] {
+        let mut buf = [0u8; 64 * 32];
+        let mut writer = BitWriter::new(&mut buf, bit_order);
+        for value in values {
+            writer.write(*value).unwrap();
+        }
+        let len = writer.position();
+        let mut reader = BitReader::new(&buf, bit_order);
+        for value in values {
+            assert_eq!(reader.read::<T>(), Ok(*value));
+        }
+        assert_eq!(reader.position(), len);
+    }
+}
+
+// =============================================================================
+// LEB128
+// =============================================================================
+
+#[test]
+fn leb128_known_values() {
+    let mut buf = [0u8; 3];
+    let mut writer = BitWriter::new(&mut buf, BitOrder::MsbFirst);
+    writer.write(Leb128(624485)).unwrap();
+    assert_eq!(buf, [0xe5, 0x8e, 0x26]);
+    assert_eq!(encode_to_bit_string(Leb128(0)), "00000000");
+    assert_eq!(encode_to_bit_string(Leb128(127)), "01111111");
+    assert_eq!(encode_to_bit_string(Leb128(128)).len(), 16);
+}
+
+#[test]
+fn leb128_roundtrip() {
+    roundtrip(&[
+        Leb128(0),
+        Leb128(1),
+        Leb128(127),
+        Leb128(128),
+        Leb128(300),
+        Leb128(u32::MAX as u64),
+        Leb128(u64::MAX),
+    ]);
+}
+
+#[test]
+fn leb128_rejects_overflow() {
+    // 11 groups is more than a u64 can hold
+    let data = [0xff; 10]
+        .iter()
+        .chain(&[0x01])
+        .copied()
+        .collect::<Vec<_>>();
+    let mut reader = BitReader::new(&data, BitOrder::MsbFirst);
+    assert_eq!(reader.read::<Leb128>(), Err(BitStreamError::InvalidValue));
+    assert_eq!(reader.position(), 0);
+
+    // the 10th group may only hold a single bit
+    let data = [0xff; 9].iter().chain(&[0x02]).copied().collect::<Vec<_>>();
+    let mut reader = BitReader::new(&data, BitOrder::MsbFirst);
+    assert_eq!(reader.read::<Leb128>(), Err(BitStreamError::InvalidValue));
+
+    let data = [0xff; 9].iter().chain(&[0x01]).copied().collect::<Vec<_>>();
+    let mut reader = BitReader::new(&data, BitOrder::MsbFirst);
+    assert_eq!(reader.read::<Leb128>(), Ok(Leb128(u64::MAX)));
+}
+
+#[test]
+fn leb128_truncated() {
+    let data = [0x80, 0x80];
+    let mut reader = BitReader::new(&data, BitOrder::MsbFirst);
+    assert_eq!(reader.read::<Leb128>(), Err(BitStreamError::EndOfStream));
+    assert_eq!(reader.position(), 0);
+}
+
+#[test]
+fn leb128_write_without_space_leaves_buffer_untouched() {
+    let mut buf = [0xaa; 2];
+    let mut writer = BitWriter::new(&mut buf, BitOrder::MsbFirst);
+    writer.write_bits(0, 4).unwrap();
+    // 300 needs two groups, and only the first one fits
+    assert_eq!(writer.write(Leb128(300)), Err(BitStreamError::EndOfStream));
+    assert_eq!(writer.position(), 4);
+    assert_eq!(buf, [0x0a, 0xaa]);
+}
+
+#[test]
+fn sleb128_known_values() {
+    let mut buf = [0u8; 3];
+    let mut writer = BitWriter::new(&mut buf, BitOrder::MsbFirst);
+    writer.write(SLeb128(SB64::new(-123456))).unwrap();
+    assert_eq!(buf, [0xc0, 0xbb, 0x78]);
+    assert_eq!(encode_to_bit_string(SLeb128(SB64::new(-1))), "01111111");
+    assert_eq!(encode_to_bit_string(SLeb128(SB64::new(63))), "00111111");
+    assert_eq!(encode_to_bit_string(SLeb128(SB64::new(64))).len(), 16);
+}
+
+#[test]
+fn sleb128_roundtrip() {
+    roundtrip(&[
+        SLeb128(SB64::new(0)),
+        SLeb128(SB64::new(-1)),
+        SLeb128(SB64::new(63)),
+        SLeb128(SB64::new(-64)),
+        SLeb128(SB64::new(64)),
+        SLeb128(SB64::new(-65)),
+        SLeb128(SB64::MAX),
+        SLeb128(SB64::MIN),
+    ]);
+}
+
+// =============================================================================
+// Exp-Golomb
+// =============================================================================
+
+#[test]
+fn exp_golomb_known_values() {
+    assert_eq!(encode_to_bit_string(ExpGolomb(0)), "1");
+    assert_eq!(encode_to_bit_string(ExpGolomb(1)), "010");
+    assert_eq!(encode_to_bit_string(ExpGolomb(2)), "011");
+    assert_eq!(encode_to_bit_string(ExpGolomb(3)), "00100");
+    assert_eq!(encode_to_bit_string(ExpGolomb(8)), "0001001");
+}
+
+#[test]
+fn signed_exp_golomb_known_values() {
+    assert_eq!(encode_to_bit_string(SignedExpGolomb(SB64::new(0))), "1");
+    assert_eq!(encode_to_bit_string(SignedExpGolomb(SB64::new(1))), "010");
+    assert_eq!(encode_to_bit_string(SignedExpGolomb(SB64::new(-1))), "011");
+    assert_eq!(encode_to_bit_string(SignedExpGolomb(SB64::new(2))), "00100");
+    assert_eq!(
+        encode_to_bit_string(SignedExpGolomb(SB64::new(-2))),
+        "00101"
+    );
+}
+
+#[test]
+fn exp_golomb_roundtrip() {
+    roundtrip(&[
+        ExpGolomb(0),
+        ExpGolomb(1),
+        ExpGolomb(1000),
+        ExpGolomb(u64::MAX - 1),
+        ExpGolomb(u64::MAX),
+    ]);
+    roundtrip(&[
+        SignedExpGolomb(SB64::new(0)),
+        SignedExpGolomb(SB64::new(-1)),
+        SignedExpGolomb(SB64::new(1000)),
+        SignedExpGolomb(SB64::new(-1000)),
+        SignedExpGolomb(SB64::MAX),
+        SignedExpGolomb(SB64::MIN),
+    ]);
+}
+
+#[test]
+fn exp_golomb_rejects_too_many_leading_zeroes() {
+    let data = bit_string_to_bytes(&"0".repeat(70));
+    let mut reader = BitReader::new(&data, BitOrder::MsbFirst);
+    assert_eq!(
+        reader.read::<ExpGolomb>(),
+        Err(BitStreamError::InvalidValue)
+    );
+    assert_eq!(reader.position(), 0);
+}
+
+#[test]
+fn exp_golomb_mixed_with_bitpieces() {
+    let mut buf = [0u8; 4];
+    let mut writer = BitWriter::new(&mut buf, BitOrder::MsbFirst);
+    writer.write(B3::new(5)).unwrap();
+    writer.write(ExpGolomb(7)).unwrap();
+    writer.write(SignedExpGolomb(SB64::new(-3))).unwrap();
+    writer.write(true).unwrap();
+
+    let mut reader = BitReader::new(&buf, BitOrder::MsbFirst);
+    assert_eq!(reader.read::<B3>(), Ok(B3::new(5)));
+    assert_eq!(reader.read::<ExpGolomb>(), Ok(ExpGolomb(7)));
+    assert_eq!(
+        reader.read::<SignedExpGolomb>(),
+        Ok(SignedExpGolomb(SB64::new(-3)))
+    );
+    assert_eq!(reader.read::<bool>(), Ok(true));
+}
+
+// =============================================================================
+// Elias gamma / delta
+// =============================================================================
+
+#[test]
+fn elias_gamma_known_values() {
+    assert_eq!(encode_to_bit_string(EliasGamma(1)), "1");
+    assert_eq!(encode_to_bit_string(EliasGamma(2)), "010");
+    assert_eq!(encode_to_bit_string(EliasGamma(5)), "00101");
+    assert_eq!(encode_to_bit_string(EliasGamma(17)), "000010001");
+}
+
+#[test]
+fn elias_delta_known_values() {
+    assert_eq!(encode_to_bit_string(EliasDelta(1)), "1");
+    assert_eq!(encode_to_bit_string(EliasDelta(2)), "0100");
+    assert_eq!(encode_to_bit_string(EliasDelta(10)), "00100010");
+    assert_eq!(encode_to_bit_string(EliasDelta(17)), "001010001");
+}
+
+#[test]
+fn elias_roundtrip() {
+    roundtrip(&[
+        EliasGamma(1),
+        EliasGamma(2),
+        EliasGamma(12345),
+        EliasGamma(u64::MAX),
+    ]);
+    roundtrip(&[
+        EliasDelta(1),
+        EliasDelta(2),
+        EliasDelta(12345),
+        EliasDelta(u64::MAX),
+    ]);
+}
+
+#[test]
+fn elias_rejects_zero() {
+    let mut buf = [0xaa; 2];
+    let mut writer = BitWriter::new(&mut buf, BitOrder::MsbFirst);
+    assert_eq!(
+        writer.write(EliasGamma(0)),
+        Err(BitStreamError::InvalidValue)
+    );
+    assert_eq!(
+        writer.write(EliasDelta(0)),
+        Err(BitStreamError::InvalidValue)
+    );
+    assert_eq!(writer.position(), 0);
+}
+
+#[test]
+fn elias_delta_rejects_too_long_length() {
+    // a length of 65 bits
+    let data = bit_string_to_bytes("00000011000001");
+    let mut reader = BitReader::new(&data, BitOrder::MsbFirst);
+    assert_eq!(
+        reader.read::<EliasDelta>(),
+        Err(BitStreamError::InvalidValue)
+    );
+}
+
+// =============================================================================
+// Zig-zag
+// =============================================================================
+
+#[test]
+fn zig_zag_known_values() {
+    assert_eq!(encode_to_bit_string(ZigZag(SB5::new(0))), "00000");
+    assert_eq!(encode_to_bit_string(ZigZag(SB5::new(-1))), "00001");
+    assert_eq!(encode_to_bit_string(ZigZag(SB5::new(1))), "00010");
+    assert_eq!(encode_to_bit_string(ZigZag(SB5::new(-2))), "00011");
+    assert_eq!(encode_to_bit_string(ZigZag(SB5::new(15))), "11110");
+    assert_eq!(encode_to_bit_string(ZigZag(SB5::new(-16))), "11111");
+    assert_eq!(encode_to_bit_string(ZigZag(-1i8)), "00000001");
+}
+
+#[test]
+fn zig_zag_roundtrip() {
+    roundtrip(&[
+        ZigZag(SB5::new(0)),
+        ZigZag(SB5::new(-16)),
+        ZigZag(SB5::new(15)),
+        ZigZag(SB5::new(-7)),
+    ]);
+    roundtrip(&[ZigZag(SB64::MIN), ZigZag(SB64::MAX), ZigZag(SB64::new(-1))]);
+    roundtrip(&[ZigZag(i16::MIN), ZigZag(i16::MAX), ZigZag(0i16)]);
+}
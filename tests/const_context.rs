@@ -1,4 +1,10 @@
 //! Tests for const context usage.
+#![allow(
+    clippy::assertions_on_constants,
+    clippy::bool_assert_comparison,
+    clippy::bool_comparison,
+    clippy::unusual_byte_groupings
+)]
 
 use bitpiece::*;
 
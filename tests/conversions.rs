@@ -0,0 +1,106 @@
+//! Tests for the conversions between bitpiece integer types and primitive integer types.
+
+use bitpiece::*;
+
+#[test]
+fn widening_between_b_types() {
+    assert_eq!(B5::from(B3::new(7)), B5::new(7));
+    assert_eq!(B64::from(B1::new(1)), B64::new(1));
+    let wide: B12 = B8::new(200).into();
+    assert_eq!(wide, B12::new(200));
+}
+
+#[test]
+fn widening_between_sb_types() {
+    assert_eq!(SB8::from(SB5::new(-16)), SB8::new(-16));
+    assert_eq!(SB64::from(SB63::MIN), SB64::new(SB63::MIN.get()));
+}
+
+#[test]
+fn widening_to_primitives() {
+    assert_eq!(u8::from(B8::new(255)), 255);
+    assert_eq!(u64::from(B33::MAX), (1 << 33) - 1);
+    assert_eq!(i8::from(SB5::new(-16)), -16);
+    assert_eq!(i16::from(B15::MAX), i16::MAX);
+    assert_eq!(i64::from(SB64::MIN), i64::MIN);
+}
+
+#[test]
+fn widening_from_primitives() {
+    assert_eq!(B8::from(255u8), B8::new(255));
+    assert_eq!(B12::from(200u8), B12::new(200));
+    assert_eq!(SB9::from(255u8), SB9::new(255));
+    assert_eq!(SB16::from(i16::MIN), SB16::MIN);
+}
+
+#[test]
+fn widening_b_to_sb() {
+    assert_eq!(SB6::from(B5::MAX), SB6::new(31));
+    assert_eq!(SB9::from(B8::MAX), SB9::new(255));
+}
+
+#[test]
+fn narrowing_between_b_types() {
+    assert_eq!(B8::try_from(B12::new(255)), Ok(B8::new(255)));
+    assert!(B8::try_from(B12::new(256)).is_err());
+    assert_eq!(B1::try_from(B64::new(1)), Ok(B1::new(1)));
+}
+
+#[test]
+fn narrowing_between_sb_types() {
+    assert_eq!(SB5::try_from(SB8::new(-16)), Ok(SB5::MIN));
+    assert!(SB5::try_from(SB8::new(-17)).is_err());
+    assert!(SB5::try_from(SB8::new(16)).is_err());
+}
+
+#[test]
+fn narrowing_from_primitives() {
+    assert_eq!(B5::try_from(31u8), Ok(B5::new(31)));
+    assert!(B5::try_from(32u8).is_err());
+    assert_eq!(B5::try_from(31i64), Ok(B5::new(31)));
+    assert!(B5::try_from(-1i8).is_err());
+    assert_eq!(SB5::try_from(-16i32), Ok(SB5::MIN));
+    assert!(SB5::try_from(16u8).is_err());
+    assert!(SB8::try_from(128u8).is_err());
+    assert_eq!(B64::try_from(i64::MAX), Ok(B64::new(i64::MAX as u64)));
+}
+
+#[test]
+fn narrowing_to_primitives() {
+    assert_eq!(u8::try_from(B12::new(255)), Ok(255));
+    assert!(u8::try_from(B12::new(256)).is_err());
+    assert_eq!(u32::try_from(SB5::new(3)), Ok(3));
+    assert!(u32::try_from(SB5::new(-3)).is_err());
+    assert!(i8::try_from(B8::MAX).is_err());
+}
+
+#[test]
+fn conversions_between_signedness() {
+    assert_eq!(B5::try_from(SB8::new(31)), Ok(B5::MAX));
+    assert!(B5::try_from(SB8::new(-1)).is_err());
+    assert_eq!(SB5::try_from(B5::new(15)), Ok(SB5::MAX));
+    assert!(SB5::try_from(B5::new(16)).is_err());
+}
+
+#[test]
+fn error_display() {
+    let err = B5::try_from(32u8).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "out of range integral type conversion attempted"
+    );
+}
+
+#[test]
+fn cast_and_truncate() {
+    const CAST: Option<B5> = B5::cast(B12::new(31).get() as u64);
+    const TRUNCATED: B5 = B5::truncate(0b111_00101);
+    assert_eq!(CAST, Some(B5::MAX));
+    assert_eq!(B5::cast(32), None);
+    assert_eq!(TRUNCATED, B5::new(0b00101));
+    assert_eq!(SB5::cast(-16), Some(SB5::MIN));
+    assert_eq!(SB5::cast(-17), None);
+    assert_eq!(SB5::truncate(0b1_10101), SB5::new(-11));
+    assert_eq!(SB5::truncate(-17), SB5::new(15));
+    assert_eq!(B64::truncate(u64::MAX), B64::MAX);
+}
@@ -0,0 +1,264 @@
+//! Tests for fields with custom converter modules.
+#![allow(clippy::unusual_byte_groupings)]
+
+mod common;
+
+use std::time::Duration;
+
+use bitpiece::*;
+use common::{expect_panic, expect_panic_with_message};
+
+/// a power of two, stored as its exponent.
+mod pow2 {
+    pub const fn encode(value: u32) -> u8 {
+        value.trailing_zeros() as u8
+    }
+    pub const fn decode(bits: u8) -> u32 {
+        1 << bits
+    }
+}
+
+mod codecs {
+    /// a duration, stored as a whole amount of milliseconds.
+    pub mod millis {
+        use std::time::Duration;
+
+        pub const fn encode(value: Duration) -> u16 {
+            value.as_millis() as u16
+        }
+        pub const fn decode(bits: u16) -> Duration {
+            Duration::from_millis(bits as u64)
+        }
+    }
+
+    /// a percentage, where values above 100 are invalid.
+    pub mod percent {
+        pub const fn encode(value: u8) -> u8 {
+            value
+        }
+        pub const fn decode(bits: u8) -> u8 {
+            bits
+        }
+        pub const fn is_valid(bits: u8) -> bool {
+            bits <= 100
+        }
+    }
+}
+
+#[bitpiece(24, all, layout, visit)]
+#[derive(Debug, PartialEq, Eq)]
+struct Timer {
+    #[bitpiece(with = pow2, bits = 4)]
+    scale: u32,
+    #[bitpiece(with = codecs::millis, bits = 12)]
+    timeout: Duration,
+    #[bitpiece(with = self::codecs::percent, bits = 7, validate = self::codecs::percent::is_valid)]
+    duty: u8,
+    enabled: bool,
+}
+
+#[bitpiece(7, all, layout)]
+#[derive(Debug, PartialEq, Eq)]
+struct Duty {
+    #[bitpiece(with = codecs::percent, bits = 7, validate = codecs::percent::is_valid)]
+    percent: u8,
+}
+
+#[bitpiece(32, all, layout)]
+#[derive(Debug, PartialEq, Eq)]
+struct Channel {
+    timer: Timer,
+    duty: Duty,
+    id: bool,
+}
+
+#[test]
+fn get_converts_from_bits() {
+    let timer = Timer::from_bits(0x80_0fa_3 | (50 << 16));
+    assert_eq!(timer.scale(), 8);
+    assert_eq!(timer.timeout(), Duration::from_millis(250));
+    assert_eq!(timer.duty(), 50);
+    assert!(timer.enabled());
+}
+
+#[test]
+fn set_and_with_convert_to_bits() {
+    let mut timer = Timer::from_bits(0);
+    timer.set_scale(1024);
+    timer.set_timeout(Duration::from_millis(4095));
+    assert_eq!(timer.storage, 0x00_fff_a);
+    let timer = timer.with_duty(100).with_enabled(true);
+    assert_eq!(timer.storage, 0x80_fff_a | (100 << 16));
+    assert_eq!(timer.timeout(), Duration::from_millis(4095));
+}
+
+#[test]
+fn encoded_value_must_fit() {
+    expect_panic_with_message(
+        || {
+            let _ = Timer::from_bits(0).with_timeout(Duration::from_millis(4096));
+        },
+        "encoded value does not fit",
+    );
+    expect_panic(|| {
+        let _ = Timer::from_bits(0).with_scale(1 << 16);
+    });
+}
+
+#[test]
+fn validate_rejects_bits() {
+    assert!(Timer::try_from_bits(100 << 16).is_some());
+    assert_eq!(Timer::try_from_bits(101 << 16), None);
+    // a field without a validation function accepts all bits
+    assert!(Timer::try_from_bits(0xfff_f).is_some());
+}
+
+#[test]
+fn fields_struct() {
+    let fields = TimerFields {
+        scale: 2,
+        timeout: Duration::from_millis(7),
+        duty: 30,
+        enabled: false,
+    };
+    let timer = Timer::from_fields(fields);
+    assert_eq!(timer.storage, 0x1e_007_1);
+    let fields = timer.to_fields();
+    assert_eq!(fields.timeout, Duration::from_millis(7));
+    assert_eq!(
+        Timer::try_from_fields(TimerFields {
+            duty: 101,
+            ..fields
+        }),
+        None
+    );
+}
+
+#[test]
+fn try_set_and_try_with() {
+    let mut timer = Timer::from_bits(0);
+    assert_eq!(timer.try_set_scale(8), Ok(()));
+    assert_eq!(timer.scale(), 8);
+    assert!(timer.try_set_timeout(Duration::from_millis(4096)).is_err());
+    assert_eq!(timer.storage, 0x00_000_3);
+    assert_eq!(
+        timer
+            .try_with_timeout(Duration::from_millis(5))
+            .map(|timer| timer.timeout()),
+        Ok(Duration::from_millis(5))
+    );
+    assert!(timer.try_with_scale(1 << 16).is_err());
+}
+
+#[test]
+fn mut_accessor() {
+    let mut timer = Timer::from_bits(0);
+    timer.timeout_mut().set(Duration::from_millis(9));
+    assert_eq!(timer.timeout_mut().get(), Duration::from_millis(9));
+    assert!(timer.scale_mut().try_set(1 << 16).is_err());
+    assert_eq!(timer.storage, 0x00_009_0);
+    expect_panic_with_message(
+        || {
+            Timer::from_bits(0)
+                .timeout_mut()
+                .set(Duration::from_millis(4096))
+        },
+        "encoded value does not fit",
+    );
+
+    let mut storage = Timer::from_bits(0);
+    let mut timer = TimerMutRef::new(BitPieceStorageMutRef::U32(&mut storage.storage), 0);
+    timer.duty_mut().set(42);
+    assert_eq!(storage.duty(), 42);
+}
+
+#[test]
+fn mut_struct() {
+    let mut storage = Timer::from_bits(0);
+    let mut timer = TimerMutRef::new(BitPieceStorageMutRef::U32(&mut storage.storage), 0);
+    timer.set_scale(4);
+    timer.set_timeout(Duration::from_millis(3));
+    assert_eq!(timer.scale(), 4);
+    assert_eq!(timer.timeout(), Duration::from_millis(3));
+    assert_eq!(storage.storage, 0x00_003_2);
+}
+
+#[test]
+fn layout() {
+    let timeout = &Timer::FIELDS[1];
+    assert_eq!(timeout.name, "timeout");
+    assert_eq!((timeout.offset, timeout.len), (4, 12));
+    assert_eq!(timeout.type_name, "Duration");
+    assert!(timeout.variants.is_empty() && timeout.fields.is_empty());
+
+    let mut timer = Timer::from_bits(0);
+    assert_eq!(
+        timer.set_field("duty", 101),
+        Err(FieldAccessError::InvalidValue)
+    );
+    assert_eq!(
+        timer.set_field("duty", 128),
+        Err(FieldAccessError::ValueTooLarge)
+    );
+    assert_eq!(timer.set_field("duty", 99), Ok(()));
+    assert_eq!(timer.duty(), 99);
+}
+
+#[test]
+fn visit_uses_raw_bits() {
+    struct Collect(Vec<(String, u64, usize)>);
+    impl BitPieceVisitor for Collect {
+        fn visit_unsigned(&mut self, name: &str, value: u64, bits: usize) {
+            self.0.push((name.to_string(), value, bits));
+        }
+    }
+    let mut collect = Collect(Vec::new());
+    Timer::from_bits(0x0a_010_3).visit(&mut collect);
+    assert_eq!(
+        collect.0,
+        [
+            ("scale".to_string(), 3, 4),
+            ("timeout".to_string(), 0x10, 12),
+            ("duty".to_string(), 10, 7),
+        ]
+    );
+}
+
+#[test]
+fn const_context() {
+    const TIMER: Timer = Timer::from_bits(0).with_scale(16);
+    const SCALE: u32 = TIMER.scale();
+    assert_eq!(SCALE, 16);
+    assert_eq!(TIMER.storage, 4);
+}
+
+#[test]
+fn passes_testing_harness() {
+    testing::check::<Timer>();
+}
+
+#[test]
+fn constants_use_valid_bits() {
+    // bits above 100 are rejected by the validation function, so the highest valid bits are used instead of all ones
+    assert_eq!(Duty::MAX.percent(), 100);
+    assert_eq!(Duty::ONES.percent(), 100);
+    assert_eq!(Duty::MIN.percent(), 0);
+    assert_eq!(Timer::MAX.duty(), 100);
+    assert_eq!(Timer::MAX.timeout(), Duration::from_millis(4095));
+    assert_eq!(Timer::ZEROES.storage, 0);
+    assert_eq!(Timer::try_from_bits(Timer::ONES.storage), Some(Timer::ONES));
+}
+
+#[test]
+fn nested_struct_with_converted_fields() {
+    const CHANNEL: Channel = Channel::MAX.with_id(false);
+    assert_eq!(CHANNEL.timer(), Timer::MAX);
+    assert_eq!(CHANNEL.duty().percent(), 100);
+    let channel = Channel::ZEROES
+        .with_timer(Timer::from_bits(0).with_duty(40))
+        .with_duty(Duty::from_bits(75));
+    assert_eq!(channel.timer().duty(), 40);
+    assert_eq!(Channel::try_from_bits(channel.storage), Some(channel));
+    assert_eq!(Channel::try_from_bits(101 << 24), None);
+    testing::check::<Channel>();
+}
@@ -1,4 +1,5 @@
 //! Tests for edge cases and error handling.
+#![allow(clippy::bool_assert_comparison, clippy::unusual_byte_groupings)]
 
 mod common;
 
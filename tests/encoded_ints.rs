@@ -0,0 +1,175 @@
+//! Tests for the BCD and Gray code integer types.
+
+use bitpiece::*;
+
+#[bitpiece(28, all, debug, layout, visit)]
+#[derive(PartialEq, Eq)]
+struct RtcTime {
+    seconds: Bcd<2>,
+    minutes: Bcd<2>,
+    hours: Bcd<2>,
+    position: Gray<4>,
+}
+
+// =============================================================================
+// BCD tests
+// =============================================================================
+
+#[test]
+fn bcd_bit_lens_and_constants() {
+    assert_eq!(Bcd::<1>::BITS, 4);
+    assert_eq!(Bcd::<2>::BITS, 8);
+    assert_eq!(Bcd::<16>::BITS, 64);
+    assert_eq!(Bcd::<2>::MIN.get(), 0);
+    assert_eq!(Bcd::<2>::MAX.get(), 99);
+    assert_eq!(Bcd::<16>::MAX.get(), 9_999_999_999_999_999);
+    assert_eq!(Bcd::<2>::ZEROES.get(), 0);
+    // all ones is not a valid BCD encoding, so the max value is used instead.
+    assert_eq!(Bcd::<2>::ONES, Bcd::<2>::MAX);
+}
+
+#[test]
+fn bcd_encoding() {
+    assert_eq!(Bcd::<2>::new(59).to_bits(), 0x59);
+    assert_eq!(Bcd::<4>::new(2024).to_bits(), 0x2024);
+    assert_eq!(Bcd::<4>::new(7).to_bits(), 0x0007);
+    assert_eq!(Bcd::<2>::from_bits(0x42).get(), 42);
+    assert_eq!(Bcd::<16>::MAX.to_bits(), 0x9999_9999_9999_9999);
+    assert_eq!(
+        Bcd::<16>::from_bits(0x1234_5678_9012_3456).get(),
+        1_234_567_890_123_456
+    );
+}
+
+#[test]
+fn bcd_rejects_invalid_digits() {
+    assert_eq!(Bcd::<2>::try_from_bits(0x0a), None);
+    assert_eq!(Bcd::<2>::try_from_bits(0xa0), None);
+    assert_eq!(Bcd::<2>::try_from_bits(0x1f), None);
+    assert_eq!(Bcd::<2>::try_from_bits(0x100), None);
+    assert_eq!(Bcd::<16>::try_from_bits(0xf000_0000_0000_0000), None);
+    assert_eq!(Bcd::<2>::try_new(100), None);
+    assert_eq!(Bcd::<2>::try_new(99).map(|bcd| bcd.to_bits()), Some(0x99));
+}
+
+// =============================================================================
+// Gray code tests
+// =============================================================================
+
+#[test]
+fn gray_bit_lens_and_constants() {
+    assert_eq!(Gray::<3>::BITS, 3);
+    assert_eq!(Gray::<64>::BITS, 64);
+    assert_eq!(Gray::<3>::MAX.get(), 7);
+    assert_eq!(Gray::<64>::MAX.get(), u64::MAX);
+    assert_eq!(Gray::<3>::ONES.get(), 5);
+    assert_eq!(Gray::<3>::try_new(8), None);
+}
+
+#[test]
+fn gray_encoding() {
+    let codes: Vec<u64> = (0..8)
+        .map(|value| Gray::<3>::new(value).to_bits())
+        .collect();
+    assert_eq!(
+        codes,
+        [0b000, 0b001, 0b011, 0b010, 0b110, 0b111, 0b101, 0b100]
+    );
+    for value in 0..16 {
+        let gray = Gray::<4>::new(value);
+        assert_eq!(Gray::<4>::from_bits(gray.to_bits()), gray);
+        if value > 0 {
+            let previous = Gray::<4>::new(value - 1).to_bits();
+            assert_eq!((previous ^ gray.to_bits()).count_ones(), 1);
+        }
+    }
+    assert_eq!(Gray::<64>::from_bits(1 << 63).get(), u64::MAX);
+    assert_eq!(Gray::<64>::MAX.to_bits(), 1 << 63);
+    assert_eq!(Gray::<3>::try_from_bits(0b1000), None);
+}
+
+// =============================================================================
+// struct fields
+// =============================================================================
+
+#[test]
+fn struct_fields() {
+    let mut time = RtcTime::from_bits(0);
+    time.set_seconds(Bcd::new(45));
+    time.set_minutes(Bcd::new(30));
+    time.set_hours(Bcd::new(12));
+    time.set_position(Gray::new(2));
+    assert_eq!(time.storage, 0x312_3045);
+    assert_eq!(time.minutes().get(), 30);
+    assert_eq!(RtcTime::from_bits(0x512_3045).position().get(), 6);
+    assert_eq!(RtcTime::try_from_bits(0x0a), None);
+    assert_eq!(
+        format!("{time:?}"),
+        "RtcTime { seconds: 45, minutes: 30, hours: 12, position: 2 }"
+    );
+}
+
+#[test]
+fn layout_and_visit() {
+    let hours = &RtcTime::FIELDS[2];
+    assert_eq!(hours.type_name, "Bcd");
+    assert!(!hours.is_signed);
+    assert_eq!((hours.offset, hours.len), (16, 8));
+    assert_eq!(RtcTime::FIELDS[3].type_name, "Gray");
+
+    let mut time = RtcTime::from_bits(0);
+    assert_eq!(
+        time.set_field("seconds", 0x6a),
+        Err(FieldAccessError::InvalidValue)
+    );
+    assert_eq!(time.set_field("seconds", 0x59), Ok(()));
+    assert_eq!(time.seconds().get(), 59);
+
+    struct Collect(Vec<String>);
+    impl BitPieceVisitor for Collect {
+        fn visit_unsigned(&mut self, name: &str, value: u64, bits: usize) {
+            self.0.push(format!("{name}: u{bits} = {value}"));
+        }
+    }
+    let mut collect = Collect(Vec::new());
+    time.with_position(Gray::new(15)).visit(&mut collect);
+    assert_eq!(
+        collect.0,
+        [
+            "seconds: u8 = 59",
+            "minutes: u8 = 0",
+            "hours: u8 = 0",
+            "position: u4 = 15"
+        ]
+    );
+}
+
+#[test]
+fn mut_struct() {
+    let mut time = RtcTime::from_bits(0);
+    let mut hours = time.hours_mut();
+    hours.set(Bcd::new(23));
+    assert_eq!(hours.get().get(), 23);
+    time.position_mut().set(Gray::new(4));
+    assert_eq!(time.storage, 0x623_0000);
+}
+
+#[test]
+fn const_context() {
+    const TIME: RtcTime = RtcTime::from_bits(0)
+        .with_hours(Bcd::new(9))
+        .with_position(Gray::new(3));
+    const HOURS: u64 = TIME.hours().get();
+    const POSITION_BITS: u64 = TIME.position().to_bits();
+    assert_eq!(HOURS, 9);
+    assert_eq!(POSITION_BITS, 0b0010);
+}
+
+#[test]
+fn passes_testing_harness() {
+    testing::check::<Bcd<2>>();
+    testing::check::<Bcd<16>>();
+    testing::check::<Gray<5>>();
+    testing::check::<Gray<64>>();
+    testing::check::<RtcTime>();
+}
@@ -1,4 +1,10 @@
 //! Tests for enum bitfields.
+#![allow(
+    clippy::bool_assert_comparison,
+    clippy::clone_on_copy,
+    clippy::enum_clike_unportable_variant,
+    clippy::unusual_byte_groupings
+)]
 
 mod common;
 
@@ -0,0 +1,155 @@
+//! Tests for accessing fields dynamically by their name or index.
+#![allow(clippy::bool_assert_comparison, clippy::unusual_byte_groupings)]
+
+use bitpiece::*;
+
+#[bitpiece(2, all, layout)]
+#[derive(Debug, PartialEq, Eq)]
+enum Mode {
+    Off = 0,
+    On = 1,
+    Auto = 3,
+}
+
+#[bitpiece(8, all, layout)]
+#[derive(Debug, PartialEq, Eq)]
+struct Control {
+    irq_en: bool,
+    mode: Mode,
+    prio: B5,
+}
+
+#[bitpiece(20, all, layout)]
+#[derive(Debug, PartialEq, Eq)]
+struct Register {
+    ctrl: Control,
+    offset: SB4,
+    id: u8,
+}
+
+#[test]
+fn get_field_by_name() {
+    let ctrl = Control::from_bits(0b10110_11_1);
+    assert_eq!(ctrl.get_field("irq_en"), Some(1));
+    assert_eq!(ctrl.get_field("mode"), Some(3));
+    assert_eq!(ctrl.get_field("prio"), Some(0b10110));
+    assert_eq!(ctrl.get_field("missing"), None);
+    assert_eq!(ctrl.get_field(""), None);
+}
+
+#[test]
+fn set_field_by_name() {
+    let mut ctrl = Control::from_bits(0);
+    ctrl.set_field("prio", 5).unwrap();
+    ctrl.set_field("mode", 1).unwrap();
+    assert_eq!(ctrl.prio(), B5::new(5));
+    assert_eq!(ctrl.mode(), Mode::On);
+    assert_eq!(ctrl.irq_en(), false);
+}
+
+#[test]
+fn set_field_validates_value() {
+    let mut ctrl = Control::from_bits(0b00001_01_0);
+    assert_eq!(
+        ctrl.set_field("prio", 32),
+        Err(FieldAccessError::ValueTooLarge)
+    );
+    assert_eq!(
+        ctrl.set_field("irq_en", 2),
+        Err(FieldAccessError::ValueTooLarge)
+    );
+    // 2 is not a valid variant of `Mode`
+    assert_eq!(
+        ctrl.set_field("mode", 2),
+        Err(FieldAccessError::InvalidValue)
+    );
+    assert_eq!(
+        ctrl.set_field("missing", 0),
+        Err(FieldAccessError::UnknownField)
+    );
+    // the value is left unchanged on failure
+    assert_eq!(ctrl.to_bits(), 0b00001_01_0);
+}
+
+#[test]
+fn access_by_index() {
+    let mut ctrl = Control::from_bits(0);
+    ctrl.set_field_by_index(2, 17).unwrap();
+    assert_eq!(ctrl.get_field_by_index(2), Some(17));
+    assert_eq!(ctrl.prio(), B5::new(17));
+    assert_eq!(ctrl.get_field_by_index(3), None);
+    assert_eq!(
+        ctrl.set_field_by_index(3, 0),
+        Err(FieldAccessError::UnknownField)
+    );
+    assert_eq!(
+        ctrl.set_field_by_index(1, 2),
+        Err(FieldAccessError::InvalidValue)
+    );
+}
+
+#[test]
+fn dotted_paths_reach_nested_fields() {
+    let mut reg = Register::from_bits(0);
+    reg.set_field("ctrl.mode", 3).unwrap();
+    reg.set_field("ctrl.prio", 9).unwrap();
+    reg.set_field("id", 0xab).unwrap();
+    assert_eq!(reg.ctrl().mode(), Mode::Auto);
+    assert_eq!(reg.ctrl().prio(), B5::new(9));
+    assert_eq!(reg.id(), 0xab);
+    assert_eq!(reg.get_field("ctrl.mode"), Some(3));
+    assert_eq!(reg.get_field("ctrl"), Some(reg.ctrl().to_bits() as u64));
+    assert_eq!(reg.get_field("ctrl.missing"), None);
+    assert_eq!(reg.get_field("id.x"), None);
+    assert_eq!(
+        reg.set_field("ctrl.mode", 2),
+        Err(FieldAccessError::InvalidValue)
+    );
+}
+
+#[test]
+fn set_whole_nested_struct_validates_its_fields() {
+    let mut reg = Register::from_bits(0);
+    // mode = 2 is not valid
+    assert_eq!(
+        reg.set_field("ctrl", 0b100),
+        Err(FieldAccessError::InvalidValue)
+    );
+    reg.set_field("ctrl", 0b110).unwrap();
+    assert_eq!(reg.ctrl().mode(), Mode::Auto);
+}
+
+#[test]
+fn signed_fields_use_raw_bits() {
+    let mut reg = Register::from_bits(0);
+    reg.set_field("offset", 0b1110).unwrap();
+    assert_eq!(reg.offset(), SB4::new(-2));
+    assert_eq!(reg.get_field("offset"), Some(0b1110));
+
+    let (_, field) = find_field_by_path(Register::FIELDS, "offset").unwrap();
+    assert!(field.is_signed);
+    assert_eq!(
+        sign_extend_bits(reg.get_field("offset").unwrap(), field.len),
+        -2
+    );
+}
+
+#[test]
+fn find_field_by_path_reports_absolute_offset() {
+    let (offset, field) = find_field_by_path(Register::FIELDS, "ctrl.prio").unwrap();
+    assert_eq!(offset, 3);
+    assert_eq!(field.name, "prio");
+    assert_eq!(field.len, 5);
+    let (offset, _) = find_field_by_path(Register::FIELDS, "id").unwrap();
+    assert_eq!(offset, 12);
+}
+
+#[test]
+fn is_valid_bits_of_types() {
+    assert!(is_valid_bits::<Mode>(3));
+    assert!(!is_valid_bits::<Mode>(2));
+    assert!(!is_valid_bits::<bool>(2));
+    assert!(is_valid_bits::<B5>(31));
+    assert!(!is_valid_bits::<B5>(32));
+    assert!(is_valid_bits::<u64>(u64::MAX));
+}
@@ -0,0 +1,223 @@
+//! Tests for the fixed-point number types.
+
+use bitpiece::*;
+
+type Q1_15 = SFixed<1, 15>;
+type UQ4_12 = UFixed<4, 12>;
+type Q4_4 = SFixed<4, 4>;
+type UQ4_4 = UFixed<4, 4>;
+
+#[bitpiece(32, all, debug, layout, visit)]
+#[derive(PartialEq, Eq)]
+struct Sensor {
+    gain: Q1_15,
+    offset: UQ4_12,
+}
+
+#[test]
+fn bit_lens_and_constants() {
+    assert_eq!(Q1_15::BITS, 16);
+    assert_eq!(UQ4_12::BITS, 16);
+    assert_eq!(UFixed::<0, 64>::BITS, 64);
+    assert_eq!(Q1_15::MIN.to_f32(), -1.0);
+    assert_eq!(Q1_15::MAX.to_f64(), 1.0 - 1.0 / 32768.0);
+    assert_eq!(Q1_15::ONES.raw(), -1);
+    assert_eq!(UQ4_12::MIN.raw(), 0);
+    assert_eq!(UQ4_12::MAX.raw(), 0xffff);
+    assert_eq!(UQ4_12::ONES, UQ4_12::MAX);
+}
+
+#[test]
+fn smallest_storage_type() {
+    assert_eq!(core::mem::size_of::<UQ4_4>(), 1);
+    assert_eq!(core::mem::size_of::<Q1_15>(), 2);
+    assert_eq!(core::mem::size_of::<SFixed<10, 10>>(), 4);
+    assert_eq!(core::mem::size_of::<UFixed<0, 64>>(), 8);
+    let bits: <UQ4_12 as BitPiece>::Bits = 0x1800u16;
+    assert_eq!(UQ4_12::from_bits(bits).to_bits(), bits);
+    let bits: <Q4_4 as BitPiece>::Bits = 0x80u8;
+    assert_eq!(Q4_4::from_bits(bits).raw(), -128);
+}
+
+#[test]
+fn raw_bits_conversions() {
+    assert_eq!(UQ4_12::from_bits(0x1800).to_f32(), 1.5);
+    assert_eq!(UQ4_12::from_bits(0x0001).to_f64(), 1.0 / 4096.0);
+    assert_eq!(Q1_15::from_bits(0x8000).to_f32(), -1.0);
+    assert_eq!(Q1_15::from_bits(0xc000).raw(), -0x4000);
+    assert_eq!(Q1_15::from_raw(-0x4000).to_bits(), 0xc000);
+    // bits above the bit length of the type, but within its storage type, are invalid
+    assert_eq!(UFixed::<3, 4>::try_from_bits(0x80), None);
+    assert_eq!(SFixed::<1, 14>::try_from_bits(0xc000), None);
+    assert_eq!(Q1_15::try_from_raw(0x8000), None);
+    assert_eq!(Q1_15::try_from_raw(-0x8001), None);
+}
+
+#[test]
+fn from_float_rounds_to_nearest() {
+    assert_eq!(UQ4_4::from_f32(1.5).raw(), 24);
+    assert_eq!(UQ4_4::from_f32(1.03).raw(), 16);
+    assert_eq!(UQ4_4::from_f32(1.04).raw(), 17);
+    // ties are rounded away from zero
+    assert_eq!(UQ4_4::from_f32(1.03125).raw(), 17);
+    assert_eq!(Q4_4::from_f32(-1.03125).raw(), -17);
+    assert_eq!(Q4_4::from_f64(-0.01).raw(), 0);
+}
+
+#[test]
+fn from_float_saturates() {
+    assert_eq!(UQ4_4::from_f32(16.0), UQ4_4::MAX);
+    assert_eq!(UQ4_4::from_f32(-1.0), UQ4_4::MIN);
+    assert_eq!(Q1_15::from_f32(1.0), Q1_15::MAX);
+    assert_eq!(Q1_15::from_f32(-2.0), Q1_15::MIN);
+    assert_eq!(Q1_15::from_f32(f32::INFINITY), Q1_15::MAX);
+    assert_eq!(Q1_15::from_f32(f32::NAN), Q1_15::ZEROES);
+    assert_eq!(SFixed::<64, 0>::from_f64(1e30), SFixed::<64, 0>::MAX);
+}
+
+#[test]
+fn addition_and_subtraction() {
+    let a = Q4_4::from_f32(5.5);
+    let b = Q4_4::from_f32(3.25);
+    assert_eq!(a.wrapping_add(b).to_f32(), -7.25);
+    assert_eq!(a.saturating_add(b), Q4_4::MAX);
+    assert_eq!(a.checked_add(b), None);
+    assert_eq!(a.checked_sub(b).map(Q4_4::to_f32), Some(2.25));
+    assert_eq!(Q4_4::MIN.saturating_sub(b), Q4_4::MIN);
+
+    let a = UQ4_4::from_f32(1.0);
+    let b = UQ4_4::from_f32(1.5);
+    assert_eq!(a.wrapping_sub(b).to_f32(), 15.5);
+    assert_eq!(a.saturating_sub(b), UQ4_4::MIN);
+    assert_eq!(a.checked_sub(b), None);
+    assert_eq!(b.saturating_add(b).to_f32(), 3.0);
+}
+
+#[test]
+fn multiplication() {
+    let half = Q1_15::from_f32(0.5);
+    let quarter = Q1_15::from_f32(-0.25);
+    assert_eq!(half.wrapping_mul(quarter).to_f32(), -0.125);
+    assert_eq!(Q1_15::MIN.checked_mul(Q1_15::MIN), None);
+    assert_eq!(Q1_15::MIN.saturating_mul(Q1_15::MIN), Q1_15::MAX);
+    assert_eq!(Q1_15::MIN.saturating_mul(half).to_f32(), -0.5);
+
+    // the product of the smallest steps is rounded to the nearest step
+    let step = UQ4_4::from_raw(1);
+    assert_eq!(step.wrapping_mul(step).raw(), 0);
+    assert_eq!(
+        UQ4_4::from_raw(24).wrapping_mul(UQ4_4::from_raw(9)).raw(),
+        14
+    );
+    assert_eq!(
+        UQ4_4::from_f32(4.0).saturating_mul(UQ4_4::from_f32(4.0)),
+        UQ4_4::MAX
+    );
+    assert_eq!(
+        UQ4_4::from_f32(4.0)
+            .wrapping_mul(UQ4_4::from_f32(4.0))
+            .raw(),
+        0
+    );
+    assert_eq!(UFixed::<64, 0>::MAX.checked_mul(UFixed::<64, 0>::MAX), None);
+    assert_eq!(
+        UFixed::<64, 0>::MAX
+            .wrapping_mul(UFixed::<64, 0>::MAX)
+            .raw(),
+        1
+    );
+}
+
+#[test]
+fn display_as_decimal() {
+    assert_eq!(UQ4_12::from_f32(1.5).to_string(), "1.5");
+    assert_eq!(UQ4_12::from_f32(3.0).to_string(), "3");
+    assert_eq!(UQ4_12::from_raw(1).to_string(), "0.000244140625");
+    assert_eq!(Q1_15::MIN.to_string(), "-1");
+    assert_eq!(Q1_15::from_f32(-0.75).to_string(), "-0.75");
+    assert_eq!(format!("{:?}", Q4_4::from_f32(-2.5)), "-2.5");
+    assert_eq!(
+        UFixed::<0, 64>::from_raw(1).to_string(),
+        format!("0.{:0>64}", "542101086242752217003726400434970855712890625")
+    );
+}
+
+#[test]
+fn display_with_precision_and_width() {
+    let value = UQ4_12::from_f32(1.5);
+    assert_eq!(format!("{value:.3}"), "1.500");
+    assert_eq!(format!("{value:.0}"), "2");
+    assert_eq!(format!("{:.2}", UQ4_4::from_f32(9.9375)), "9.94");
+    assert_eq!(format!("{:.1}", UQ4_4::from_f32(9.9375)), "9.9");
+    assert_eq!(format!("{:.1}", UFixed::<4, 5>::from_f32(9.96875)), "10.0");
+    assert_eq!(format!("{:.1}", Q4_4::from_f32(-0.9375)), "-0.9");
+    assert_eq!(format!("{:.1}", SFixed::<4, 5>::from_f32(-0.96875)), "-1.0");
+    // negative values which round to zero are printed without a sign
+    assert_eq!(format!("{:.1}", Q1_15::from_f32(-0.01)), "0.0");
+    assert_eq!(format!("{:.0}", Q4_4::from_f32(-0.25)), "0");
+    assert_eq!(format!("{value:>6}"), "   1.5");
+    assert_eq!(format!("{value:+}"), "+1.5");
+    assert_eq!(format!("{:08.2}", Q4_4::from_f32(-2.5)), "-0002.50");
+}
+
+#[test]
+fn bit_formatting() {
+    assert_eq!(format!("{:x}", Q1_15::MIN), "8000");
+    assert_eq!(format!("{:#b}", Q4_4::from_f32(-0.5)), "0b11111000");
+}
+
+#[test]
+fn struct_fields() {
+    let mut sensor = Sensor::from_bits(0);
+    sensor.set_gain(Q1_15::from_f32(-0.5));
+    sensor.set_offset(UQ4_12::from_f32(2.25));
+    assert_eq!(sensor.storage, 0x2400_c000);
+    assert_eq!(sensor.gain().to_f32(), -0.5);
+    assert_eq!(sensor.offset().to_f32(), 2.25);
+    assert_eq!(format!("{sensor:?}"), "Sensor { gain: -0.5, offset: 2.25 }");
+}
+
+#[test]
+fn layout_and_visit() {
+    let gain = &Sensor::FIELDS[0];
+    assert_eq!(gain.type_name, "SFixed");
+    assert!(gain.is_signed);
+    assert_eq!((gain.offset, gain.len), (0, 16));
+
+    struct Collect(Vec<String>);
+    impl BitPieceVisitor for Collect {
+        fn visit_unsigned(&mut self, name: &str, value: u64, bits: usize) {
+            self.0.push(format!("{name}: u{bits} = {value}"));
+        }
+        fn visit_signed(&mut self, name: &str, value: i64, bits: usize) {
+            self.0.push(format!("{name}: i{bits} = {value}"));
+        }
+    }
+    let mut collect = Collect(Vec::new());
+    Sensor::from_bits(0x0001_ffff).visit(&mut collect);
+    assert_eq!(collect.0, ["gain: i16 = -1", "offset: u16 = 1"]);
+}
+
+#[test]
+fn mut_struct() {
+    let mut sensor = Sensor::from_bits(0);
+    let mut gain = sensor.gain_mut();
+    gain.set(Q1_15::MIN);
+    assert_eq!(gain.get(), Q1_15::MIN);
+    assert_eq!(sensor.storage, 0x8000);
+}
+
+#[test]
+fn const_context() {
+    const GAIN: Q1_15 = Q1_15::from_f32(0.25);
+    const SENSOR: Sensor = Sensor::from_bits(0).with_gain(GAIN.saturating_mul(GAIN));
+    assert_eq!(SENSOR.gain().raw(), 0x0800);
+    assert_eq!(SENSOR.gain().to_f32(), 0.0625);
+}
+
+#[test]
+fn passes_testing_harness() {
+    testing::check::<Q1_15>();
+    testing::check::<UQ4_12>();
+    testing::check::<Sensor>();
+}
@@ -0,0 +1,108 @@
+//! Tests for flag sets generated using the `flags` opt in.
+
+use bitpiece::*;
+
+#[bitpiece(3, all, flags)]
+#[derive(Debug, PartialEq, Eq)]
+struct Perm {
+    read: bool,
+    write: bool,
+    exec: bool,
+}
+
+#[bitpiece(8, basic, flags)]
+#[derive(Debug, PartialEq, Eq)]
+struct Status {
+    irq_pending: bool,
+    tx_ready: bool,
+    rx_ready: bool,
+    error: bool,
+    reserved0: bool,
+    reserved1: bool,
+    reserved2: bool,
+    overflow: bool,
+}
+
+#[test]
+fn single_flag_constants() {
+    assert_eq!(Perm::READ.storage, 0b001);
+    assert_eq!(Perm::WRITE.storage, 0b010);
+    assert_eq!(Perm::EXEC.storage, 0b100);
+    assert_eq!(Status::IRQ_PENDING.storage, 0x01);
+    assert_eq!(Status::OVERFLOW.storage, 0x80);
+    assert!(Perm::WRITE.write());
+}
+
+#[test]
+fn flags_table() {
+    assert_eq!(
+        Perm::FLAGS,
+        &[
+            ("READ", Perm::READ),
+            ("WRITE", Perm::WRITE),
+            ("EXEC", Perm::EXEC)
+        ]
+    );
+}
+
+#[test]
+fn combine_with_operators() {
+    const RX: Perm = Perm::READ.union(Perm::EXEC);
+    assert_eq!(Perm::READ | Perm::EXEC, RX);
+    assert!(RX.contains(Perm::EXEC));
+    assert_eq!(RX & !Perm::READ, Perm::EXEC);
+}
+
+#[test]
+fn iter_over_set_flags() {
+    let perm = Perm::READ | Perm::EXEC;
+    assert_eq!(perm.iter().collect::<Vec<_>>(), [Perm::READ, Perm::EXEC]);
+    assert_eq!(
+        perm.iter_names().map(|(name, _)| name).collect::<Vec<_>>(),
+        ["READ", "EXEC"]
+    );
+    assert_eq!(Perm::ZEROES.iter().count(), 0);
+}
+
+#[test]
+fn from_name() {
+    assert_eq!(Perm::from_name("WRITE"), Some(Perm::WRITE));
+    assert_eq!(Status::from_name("TX_READY"), Some(Status::TX_READY));
+    assert_eq!(Perm::from_name("write"), None);
+    assert_eq!(Perm::from_name("DELETE"), None);
+}
+
+#[test]
+fn display() {
+    assert_eq!((Perm::READ | Perm::EXEC).to_string(), "READ | EXEC");
+    assert_eq!(Perm::WRITE.to_string(), "WRITE");
+    assert_eq!(Perm::ZEROES.to_string(), "");
+    assert_eq!(
+        (Status::OVERFLOW | Status::IRQ_PENDING).to_string(),
+        "IRQ_PENDING | OVERFLOW"
+    );
+}
+
+#[test]
+fn from_str() {
+    assert_eq!("READ | EXEC".parse::<Perm>(), Ok(Perm::READ | Perm::EXEC));
+    assert_eq!("EXEC|READ".parse::<Perm>(), Ok(Perm::READ | Perm::EXEC));
+    assert_eq!("  WRITE ".parse::<Perm>(), Ok(Perm::WRITE));
+    assert_eq!("".parse::<Perm>(), Ok(Perm::ZEROES));
+    assert_eq!(
+        "READ | DELETE".parse::<Perm>(),
+        Err(ParseFlagsError::UnknownFlag)
+    );
+    assert_eq!(
+        "READ | | EXEC".parse::<Perm>(),
+        Err(ParseFlagsError::EmptyFlag)
+    );
+}
+
+#[test]
+fn display_roundtrip() {
+    for bits in 0..8 {
+        let perm = Perm::from_bits(bits);
+        assert_eq!(perm.to_string().parse::<Perm>(), Ok(perm));
+    }
+}
@@ -0,0 +1,140 @@
+//! Tests for the formatting implementations of bitpiece types.
+
+use bitpiece::*;
+
+#[bitpiece(2, all, fmt)]
+#[derive(Debug, PartialEq, Eq)]
+enum Mode {
+    Off = 0,
+    On = 1,
+    Auto = 3,
+}
+
+#[bitpiece(16, all, debug, fmt)]
+#[derive(PartialEq, Eq)]
+struct Control {
+    enabled: bool,
+    mode: Mode,
+    level: B5,
+    offset: SB8,
+}
+
+#[bitpiece(20, all, debug, fmt)]
+struct Nested {
+    control: Control,
+    id: B4,
+}
+
+fn control() -> Control {
+    Control::from_fields(ControlFields {
+        enabled: true,
+        mode: Mode::Auto,
+        level: B5::new(17),
+        offset: SB8::new(-3),
+    })
+}
+
+// =============================================================================
+// Debug
+// =============================================================================
+
+#[test]
+fn debug_lists_fields() {
+    assert_eq!(
+        format!("{:?}", control()),
+        "Control { enabled: true, mode: Auto, level: 17, offset: -3 }"
+    );
+}
+
+#[test]
+fn debug_nested() {
+    let nested = Nested::from_fields(NestedFields {
+        control: control(),
+        id: B4::new(9),
+    });
+    assert_eq!(
+        format!("{:?}", nested),
+        "Nested { control: Control { enabled: true, mode: Auto, level: 17, offset: -3 }, id: 9 }"
+    );
+}
+
+#[test]
+fn debug_alternate_shows_storage() {
+    assert_eq!(
+        format!("{:#?}", control()),
+        "Control {\n    storage: 0xfd8f,\n    enabled: true,\n    mode: Auto,\n    level: 17,\n    offset: -3,\n}"
+    );
+}
+
+// =============================================================================
+// Binary / octal / hex
+// =============================================================================
+
+#[test]
+fn b_types_padded_to_bit_len() {
+    assert_eq!(format!("{:b}", B5::new(3)), "00011");
+    assert_eq!(format!("{:#b}", B5::new(3)), "0b00011");
+    assert_eq!(format!("{:x}", B9::new(0x1f)), "01f");
+    assert_eq!(format!("{:#X}", B9::new(0x1f)), "0x01F");
+    assert_eq!(format!("{:o}", B7::new(8)), "010");
+    assert_eq!(format!("{:#o}", B7::new(8)), "0o010");
+    assert_eq!(format!("{:x}", B64::MAX), "ffffffffffffffff");
+}
+
+#[test]
+fn sb_types_format_twos_complement_bits() {
+    assert_eq!(format!("{:b}", SB5::new(-1)), "11111");
+    assert_eq!(format!("{:b}", SB5::new(-16)), "10000");
+    assert_eq!(format!("{:x}", SB12::new(-2)), "ffe");
+    assert_eq!(format!("{:#x}", SB12::new(5)), "0x005");
+}
+
+#[test]
+fn struct_binary_split_at_field_boundaries() {
+    assert_eq!(format!("{:b}", control()), "11111101_10001_11_1");
+    assert_eq!(format!("{:#b}", control()), "0b11111101_10001_11_1");
+    assert_eq!(format!("{:b}", Control::ZEROES), "00000000_00000_00_0");
+}
+
+#[test]
+fn struct_hex_and_octal() {
+    assert_eq!(format!("{:x}", control()), "fd8f");
+    assert_eq!(format!("{:#X}", control()), "0xFD8F");
+    assert_eq!(format!("{:o}", Control::from_bits(8)), "000010");
+}
+
+#[test]
+fn enum_formatting() {
+    assert_eq!(format!("{:b}", Mode::On), "01");
+    assert_eq!(format!("{:#x}", Mode::Auto), "0x3");
+}
+
+// =============================================================================
+// Layout diagrams
+// =============================================================================
+
+#[test]
+fn layout_diagram() {
+    assert_eq!(
+        control().layout_diagram().to_string(),
+        "Control (16 bits) = 0b11111101_10001_11_1\n\
+         \x20 [15:8] offset  = 0b11111101 (253)\n\
+         \x20 [7:3]  level   = 0b10001 (17)\n\
+         \x20 [2:1]  mode    = 0b11 (3)\n\
+         \x20 [0]    enabled = 0b1 (1)"
+    );
+}
+
+#[test]
+fn layout_diagram_of_nested_struct() {
+    let nested = Nested::from_fields(NestedFields {
+        control: control(),
+        id: B4::new(9),
+    });
+    assert_eq!(
+        nested.layout_diagram().to_string(),
+        "Nested (20 bits) = 0b1001_1111110110001111\n\
+         \x20 [19:16] id      = 0b1001 (9)\n\
+         \x20 [15:0]  control = 0b1111110110001111 (64911)"
+    );
+}
@@ -0,0 +1,154 @@
+//! Tests for the runtime layout metadata of bitpiece types.
+#![allow(clippy::assertions_on_constants, clippy::unusual_byte_groupings)]
+
+use bitpiece::*;
+
+#[bitpiece(2, all, layout)]
+#[derive(Debug, PartialEq, Eq)]
+enum Mode {
+    Off = 0,
+    On = 1,
+    Auto = 3,
+}
+
+#[bitpiece(16, all, layout)]
+#[derive(Debug, PartialEq, Eq)]
+struct Control {
+    enabled: bool,
+    mode: Mode,
+    level: B5,
+    offset: SB8,
+}
+
+#[bitpiece(32, all, layout)]
+#[derive(Debug, PartialEq, Eq)]
+struct Register {
+    control: Control,
+    id: u8,
+    parity: i8,
+}
+
+#[test]
+fn built_in_types() {
+    assert_eq!(<B5 as BitPieceHasLayout>::TYPE_NAME, "B5");
+    assert!(!<B5 as BitPieceHasLayout>::IS_SIGNED);
+    assert_eq!(<SB13 as BitPieceHasLayout>::TYPE_NAME, "SB13");
+    assert!(<SB13 as BitPieceHasLayout>::IS_SIGNED);
+    assert_eq!(<u16 as BitPieceHasLayout>::TYPE_NAME, "u16");
+    assert!(!<u16 as BitPieceHasLayout>::IS_SIGNED);
+    assert_eq!(<i64 as BitPieceHasLayout>::TYPE_NAME, "i64");
+    assert!(<i64 as BitPieceHasLayout>::IS_SIGNED);
+    assert_eq!(<bool as BitPieceHasLayout>::TYPE_NAME, "bool");
+    assert!(<bool as BitPieceHasLayout>::FIELDS.is_empty());
+    assert!(<bool as BitPieceHasLayout>::VARIANTS.is_empty());
+}
+
+#[test]
+fn enum_variants() {
+    assert_eq!(Mode::TYPE_NAME, "Mode");
+    assert!(Mode::FIELDS.is_empty());
+    assert_eq!(
+        Mode::VARIANTS,
+        &[
+            VariantInfo {
+                name: "Off",
+                value: 0
+            },
+            VariantInfo {
+                name: "On",
+                value: 1
+            },
+            VariantInfo {
+                name: "Auto",
+                value: 3
+            },
+        ]
+    );
+}
+
+#[test]
+fn struct_fields() {
+    assert_eq!(Control::TYPE_NAME, "Control");
+    assert!(Control::VARIANTS.is_empty());
+    let names_offsets_lens: Vec<_> = Control::FIELDS
+        .iter()
+        .map(|field| {
+            (
+                field.name,
+                field.offset,
+                field.len,
+                field.type_name,
+                field.is_signed,
+            )
+        })
+        .collect();
+    assert_eq!(
+        names_offsets_lens,
+        [
+            ("enabled", 0, 1, "bool", false),
+            ("mode", 1, 2, "Mode", false),
+            ("level", 3, 5, "B5", false),
+            ("offset", 8, 8, "SB8", true),
+        ]
+    );
+    assert_eq!(Control::FIELDS[1].variants, Mode::VARIANTS);
+    assert_eq!(Control::FIELDS[1].variant_by_value(3).unwrap().name, "Auto");
+    assert_eq!(Control::FIELDS[1].variant_by_value(2), None);
+    assert_eq!(Control::FIELDS[2].mask(), 0b11111_000);
+}
+
+#[test]
+fn nested_struct_fields() {
+    let control = &Register::FIELDS[0];
+    assert_eq!(control.name, "control");
+    assert_eq!(control.type_name, "Control");
+    assert_eq!(control.fields, Control::FIELDS);
+    assert_eq!(Register::FIELDS[2].offset, 24);
+    assert!(Register::FIELDS[2].is_signed);
+}
+
+/// walks the fields of a type recursively, collecting the dotted path and absolute offset of each leaf field.
+fn collect_leaves(
+    fields: &[FieldInfo],
+    prefix: &str,
+    base_offset: usize,
+    out: &mut Vec<(String, usize)>,
+) {
+    for field in fields {
+        let path = format!("{prefix}{}", field.name);
+        if field.fields.is_empty() {
+            out.push((path, base_offset + field.offset));
+        } else {
+            collect_leaves(
+                field.fields,
+                &format!("{path}."),
+                base_offset + field.offset,
+                out,
+            );
+        }
+    }
+}
+
+#[test]
+fn walk_recursively() {
+    let mut leaves = Vec::new();
+    collect_leaves(Register::FIELDS, "", 0, &mut leaves);
+    let expected = [
+        ("control.enabled", 0),
+        ("control.mode", 1),
+        ("control.level", 3),
+        ("control.offset", 8),
+        ("id", 16),
+        ("parity", 24),
+    ];
+    assert_eq!(
+        leaves,
+        expected.map(|(path, offset)| (path.to_string(), offset))
+    );
+}
+
+#[test]
+fn usable_in_const_context() {
+    const LEVEL_OFFSET: usize = Control::FIELDS[2].offset;
+    const _: () = assert!(LEVEL_OFFSET == Control::LEVEL_OFFSET);
+}
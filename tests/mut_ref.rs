@@ -1,4 +1,5 @@
 //! Tests for mutable references (MutRef).
+#![allow(clippy::bool_assert_comparison, clippy::unusual_byte_groupings)]
 
 use bitpiece::*;
 
@@ -0,0 +1,185 @@
+//! Tests for native integer fields with an explicit bit length.
+#![allow(clippy::unusual_byte_groupings)]
+
+mod common;
+
+use bitpiece::*;
+use common::{expect_panic, expect_panic_with_message};
+
+#[bitpiece(16, all, layout, visit)]
+#[derive(Debug, PartialEq, Eq)]
+struct Sample {
+    #[bits(3)]
+    channel: u8,
+    #[bits(5)]
+    offset: i8,
+    #[bits(7)]
+    level: u32,
+    valid: bool,
+}
+
+#[test]
+fn lens_and_offsets() {
+    assert_eq!(Sample::CHANNEL_LEN, 3);
+    assert_eq!(Sample::OFFSET_OFFSET, 3);
+    assert_eq!(Sample::OFFSET_LEN, 5);
+    assert_eq!(Sample::LEVEL_OFFSET, 8);
+    assert_eq!(Sample::LEVEL_LEN, 7);
+    assert_eq!(Sample::VALID_OFFSET, 15);
+    assert_eq!(SAMPLE_BIT_LEN, 16);
+}
+
+#[test]
+fn get_returns_native_integers() {
+    let sample = Sample::from_bits(0b1_1111111_10000_101);
+    let channel: u8 = sample.channel();
+    assert_eq!(channel, 5);
+    assert_eq!(sample.offset(), -16);
+    assert_eq!(sample.level(), 127);
+    assert!(sample.valid());
+
+    let sample = Sample::from_bits(0b0_0000001_01111_000);
+    assert_eq!(sample.offset(), 15);
+    assert_eq!(sample.level(), 1);
+}
+
+#[test]
+fn set_and_with() {
+    let mut sample = Sample::from_bits(0);
+    sample.set_channel(7);
+    sample.set_offset(-1);
+    sample.set_level(100);
+    assert_eq!(sample.storage, 0b0_1100100_11111_111);
+    let sample = sample.with_offset(-16).with_channel(0);
+    assert_eq!(sample.storage, 0b0_1100100_10000_000);
+}
+
+#[test]
+fn set_panics_on_out_of_range_values() {
+    expect_panic_with_message(
+        || Sample::from_bits(0).set_channel(8),
+        "value does not fit in the bit length of the field",
+    );
+    expect_panic(|| {
+        let _ = Sample::from_bits(0).with_offset(16);
+    });
+    expect_panic_with_message(
+        || Sample::from_bits(0).set_offset(-17),
+        "value does not fit",
+    );
+    expect_panic_with_message(|| Sample::from_bits(0).set_level(128), "value does not fit");
+}
+
+#[test]
+fn try_set_and_try_with() {
+    let mut sample = Sample::from_bits(0b1_0000011_00000_001);
+    assert_eq!(sample.try_set_channel(6), Ok(()));
+    assert_eq!(sample.channel(), 6);
+    assert!(sample.try_set_channel(8).is_err());
+    assert!(sample.try_set_offset(16).is_err());
+    assert!(sample.try_set_level(1 << 31).is_err());
+    // the value is left unchanged on failure
+    assert_eq!(sample.storage, 0b1_0000011_00000_110);
+
+    assert_eq!(sample.try_with_offset(-16).map(|s| s.offset()), Ok(-16));
+    let err = sample.try_with_offset(-17).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "out of range integral type conversion attempted"
+    );
+}
+
+#[test]
+fn constants() {
+    assert_eq!(Sample::ZEROES.storage, 0);
+    assert_eq!(Sample::ONES.storage, 0xffff);
+    assert_eq!(Sample::MIN.offset(), -16);
+    assert_eq!(Sample::MAX.offset(), 15);
+    assert_eq!(Sample::MIN.level(), 0);
+    assert_eq!(Sample::MAX.level(), 127);
+}
+
+#[test]
+fn fields_struct() {
+    let fields = SampleFields {
+        channel: 3,
+        offset: -2,
+        level: 9,
+        valid: true,
+    };
+    let sample = Sample::from_fields(fields);
+    assert_eq!(sample.offset(), -2);
+    assert_eq!(sample.to_fields().level, 9);
+    assert_eq!(
+        Sample::try_from_fields(SampleFields {
+            channel: 8,
+            ..fields
+        }),
+        None
+    );
+    assert_eq!(
+        Sample::try_from_fields(SampleFields {
+            offset: -17,
+            ..fields
+        }),
+        None
+    );
+}
+
+#[test]
+fn layout() {
+    let offset = &Sample::FIELDS[1];
+    assert_eq!(offset.type_name, "i8");
+    assert!(offset.is_signed);
+    assert_eq!((offset.offset, offset.len), (3, 5));
+
+    let mut sample = Sample::from_bits(0);
+    assert_eq!(
+        sample.set_field("channel", 8),
+        Err(FieldAccessError::ValueTooLarge)
+    );
+    assert_eq!(sample.set_field("offset", 0b10000), Ok(()));
+    assert_eq!(sample.offset(), -16);
+}
+
+#[test]
+fn visit() {
+    #[derive(Default)]
+    struct Collect(Vec<String>);
+    impl BitPieceVisitor for Collect {
+        fn visit_unsigned(&mut self, name: &str, value: u64, bits: usize) {
+            self.0.push(format!("{name}: u{bits} = {value}"));
+        }
+        fn visit_signed(&mut self, name: &str, value: i64, bits: usize) {
+            self.0.push(format!("{name}: i{bits} = {value}"));
+        }
+    }
+    let mut collect = Collect::default();
+    Sample::from_bits(0b0_0000010_11110_001).visit(&mut collect);
+    assert_eq!(
+        collect.0,
+        ["channel: u3 = 1", "offset: i5 = -2", "level: u7 = 2"]
+    );
+}
+
+#[test]
+fn mut_struct() {
+    let mut sample = Sample::from_bits(0);
+    let mut sample_ref = SampleMutRef::new(BitPieceStorageMutRef::U16(&mut sample.storage), 0);
+    sample_ref.set_offset(-3);
+    assert_eq!(sample_ref.offset(), -3);
+    assert_eq!(sample.offset(), -3);
+}
+
+#[test]
+fn const_context() {
+    const SAMPLE: Sample = Sample::from_bits(0).with_offset(-5).with_level(64);
+    const OFFSET: i8 = SAMPLE.offset();
+    assert_eq!(OFFSET, -5);
+    assert_eq!(SAMPLE.level(), 64);
+}
+
+#[test]
+fn passes_testing_harness() {
+    testing::check::<Sample>();
+}
@@ -0,0 +1,52 @@
+//! Tests for the `num-traits` implementations of the `B*` and `SB*` types.
+#![cfg(feature = "num-traits")]
+
+use bitpiece::*;
+use num_traits::{Bounded, CheckedAdd, CheckedMul, CheckedSub, FromPrimitive, ToPrimitive, Zero};
+
+/// sums the given values, returning `None` on overflow, using only `num-traits` traits.
+fn checked_sum<T: Zero + CheckedAdd + Copy>(values: &[T]) -> Option<T> {
+    values
+        .iter()
+        .try_fold(T::zero(), |acc, value| acc.checked_add(value))
+}
+
+#[test]
+fn bounded() {
+    assert_eq!(<B5 as Bounded>::min_value(), B5::new(0));
+    assert_eq!(<B5 as Bounded>::max_value(), B5::MAX);
+    assert_eq!(<SB5 as Bounded>::min_value(), SB5::MIN);
+}
+
+#[test]
+fn primitive_conversions() {
+    assert_eq!(ToPrimitive::to_i64(&B5::new(17)), Some(17));
+    assert_eq!(ToPrimitive::to_u64(&SB5::new(-3)), None);
+    assert_eq!(B5::from_u64(31), Some(B5::MAX));
+    assert_eq!(B5::from_i64(-1), None);
+    assert_eq!(SB5::from_i64(-16), Some(SB5::MIN));
+}
+
+#[test]
+fn checked_arithmetic() {
+    assert_eq!(CheckedAdd::checked_add(&B5::new(30), &B5::new(2)), None);
+    assert_eq!(
+        CheckedSub::checked_sub(&SB5::new(-10), &SB5::new(6)),
+        Some(SB5::MIN)
+    );
+    assert_eq!(
+        CheckedMul::checked_mul(&SB5::new(-4), &SB5::new(4)),
+        Some(SB5::MIN)
+    );
+    assert!(B5::zero().is_zero());
+}
+
+#[test]
+fn generic_code() {
+    assert_eq!(checked_sum(&[B5::new(10), B5::new(20)]), Some(B5::new(30)));
+    assert_eq!(checked_sum(&[B5::new(10), B5::new(22)]), None);
+    assert_eq!(
+        checked_sum(&[SB5::new(-10), SB5::new(4)]),
+        Some(SB5::new(-6))
+    );
+}
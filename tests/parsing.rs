@@ -0,0 +1,157 @@
+//! Tests for parsing and displaying enums and `B*`/`SB*` types.
+
+use bitpiece::*;
+
+#[bitpiece(2, all, display, from_str)]
+#[derive(Debug, PartialEq, Eq)]
+enum Priority {
+    Low = 0,
+    Medium = 1,
+    High = 3,
+}
+
+#[bitpiece(all)]
+#[derive(Debug, PartialEq, Eq)]
+enum Opcode {
+    Nop = 0,
+    Load = 0x10,
+    Store = 0x11,
+}
+
+#[bitpiece(1, display)]
+#[derive(Debug, PartialEq, Eq)]
+enum Direction {
+    In = 0,
+    Out = 1,
+}
+
+#[bitpiece(1)]
+#[derive(Debug, PartialEq, Eq)]
+enum Edge {
+    Rising = 0,
+    Falling = 1,
+}
+
+// without the `variants` opt in, the enum may define items of the same names itself.
+impl Edge {
+    const fn variant_name(self) -> &'static str {
+        match self {
+            Edge::Rising => "rising edge",
+            Edge::Falling => "falling edge",
+        }
+    }
+}
+
+#[test]
+fn variant_names_and_counts() {
+    const NAME: &str = Priority::High.variant_name();
+    assert_eq!(NAME, "High");
+    assert_eq!(Priority::VARIANT_COUNT, 3);
+    assert_eq!(
+        Priority::ALL_VARIANTS,
+        [Priority::Low, Priority::Medium, Priority::High]
+    );
+    // generated by the `all` preset, even without the `display` and `from_str` opt ins
+    assert_eq!(Opcode::Store.variant_name(), "Store");
+    assert_eq!(Opcode::VARIANT_COUNT, 3);
+    // implied by the `display` opt in
+    assert_eq!(Direction::ALL_VARIANTS, [Direction::In, Direction::Out]);
+    assert_eq!(Direction::Out.to_string(), "Out");
+    assert_eq!(Edge::Falling.variant_name(), "falling edge");
+}
+
+#[test]
+fn enum_display() {
+    assert_eq!(Priority::Medium.to_string(), "Medium");
+    assert_eq!(format!("[{:>6}]", Priority::Low), "[   Low]");
+}
+
+#[test]
+fn enum_from_str_by_name() {
+    assert_eq!("High".parse::<Priority>(), Ok(Priority::High));
+    assert_eq!("medium".parse::<Priority>(), Ok(Priority::Medium));
+    assert_eq!("LOW".parse::<Priority>(), Ok(Priority::Low));
+    assert_eq!(
+        "Urgent".parse::<Priority>(),
+        Err(ParseEnumError::UnknownVariant)
+    );
+    assert_eq!("".parse::<Priority>(), Err(ParseEnumError::UnknownVariant));
+}
+
+#[test]
+fn enum_from_str_by_value() {
+    assert_eq!("3".parse::<Priority>(), Ok(Priority::High));
+    assert_eq!("0b01".parse::<Priority>(), Ok(Priority::Medium));
+    assert_eq!("2".parse::<Priority>(), Err(ParseEnumError::InvalidValue));
+    assert_eq!("256".parse::<Priority>(), Err(ParseEnumError::InvalidValue));
+    assert_eq!(
+        "99999999999999999999".parse::<Priority>(),
+        Err(ParseEnumError::InvalidValue)
+    );
+}
+
+#[test]
+fn enum_display_roundtrip() {
+    for variant in Priority::ALL_VARIANTS {
+        assert_eq!(variant.to_string().parse::<Priority>(), Ok(variant));
+    }
+}
+
+#[test]
+fn b_type_from_str() {
+    assert_eq!("31".parse::<B5>(), Ok(B5::new(31)));
+    assert_eq!("0x1f".parse::<B5>(), Ok(B5::new(31)));
+    assert_eq!("0X1F".parse::<B5>(), Ok(B5::new(31)));
+    assert_eq!("0b10101".parse::<B5>(), Ok(B5::new(21)));
+    assert_eq!("32".parse::<B5>(), Err(ParseBitIntError::OutOfRange));
+    assert_eq!("0x20".parse::<B5>(), Err(ParseBitIntError::OutOfRange));
+    assert_eq!("".parse::<B5>(), Err(ParseBitIntError::Empty));
+    assert_eq!("0x".parse::<B5>(), Err(ParseBitIntError::Empty));
+    assert_eq!("0b2".parse::<B5>(), Err(ParseBitIntError::InvalidDigit));
+    assert_eq!("+1".parse::<B5>(), Err(ParseBitIntError::InvalidDigit));
+    assert_eq!("-1".parse::<B5>(), Err(ParseBitIntError::InvalidDigit));
+    assert_eq!("18446744073709551615".parse::<B64>(), Ok(B64::MAX));
+    assert_eq!(
+        "18446744073709551616".parse::<B64>(),
+        Err(ParseBitIntError::OutOfRange)
+    );
+}
+
+#[test]
+fn sb_type_from_str() {
+    assert_eq!("-16".parse::<SB5>(), Ok(SB5::MIN));
+    assert_eq!("15".parse::<SB5>(), Ok(SB5::MAX));
+    assert_eq!("-0x10".parse::<SB5>(), Ok(SB5::MIN));
+    assert_eq!("-0b11".parse::<SB5>(), Ok(SB5::new(-3)));
+    assert_eq!("16".parse::<SB5>(), Err(ParseBitIntError::OutOfRange));
+    assert_eq!("-17".parse::<SB5>(), Err(ParseBitIntError::OutOfRange));
+    assert_eq!("-".parse::<SB5>(), Err(ParseBitIntError::Empty));
+    assert_eq!("--1".parse::<SB5>(), Err(ParseBitIntError::InvalidDigit));
+    assert_eq!("-9223372036854775808".parse::<SB64>(), Ok(SB64::MIN));
+    assert_eq!(
+        "-9223372036854775809".parse::<SB64>(),
+        Err(ParseBitIntError::OutOfRange)
+    );
+}
+
+#[test]
+fn b_type_display_roundtrip() {
+    for value in [B12::new(0), B12::new(1234), B12::MAX] {
+        assert_eq!(value.to_string().parse::<B12>(), Ok(value));
+    }
+    for value in [SB12::MIN, SB12::new(-1), SB12::MAX] {
+        assert_eq!(value.to_string().parse::<SB12>(), Ok(value));
+    }
+}
+
+#[test]
+fn error_display() {
+    assert_eq!(
+        ParseBitIntError::OutOfRange.to_string(),
+        "number out of range of the type"
+    );
+    assert_eq!(
+        ParseEnumError::UnknownVariant.to_string(),
+        "unknown variant name"
+    );
+}
@@ -1,4 +1,5 @@
 //! Tests for primitive types (bool, u8-u64, i8-i64) implementing BitPiece.
+#![allow(clippy::bool_assert_comparison)]
 
 use bitpiece::*;
 
@@ -0,0 +1,143 @@
+//! Tests for the `proptest` strategies of bitpiece types.
+#![cfg(feature = "proptest")]
+
+use bitpiece::proptest::prelude::*;
+use bitpiece::*;
+
+#[bitpiece(2, all, proptest)]
+#[derive(Debug, PartialEq, Eq)]
+enum Kind {
+    Data = 0,
+    Ack = 1,
+    Reset = 3,
+}
+
+#[bitpiece(20, all, proptest)]
+#[derive(Debug, PartialEq, Eq)]
+struct Header {
+    kind: Kind,
+    len: B7,
+    offset: SB3,
+    seq: u8,
+}
+
+/// a struct with more fields than the largest tuple strategy supported by proptest.
+#[bitpiece(14, all, proptest)]
+#[derive(Debug, PartialEq, Eq)]
+struct ManyFlags {
+    f0: bool,
+    f1: bool,
+    f2: bool,
+    f3: bool,
+    f4: bool,
+    f5: bool,
+    f6: bool,
+    f7: bool,
+    f8: bool,
+    f9: bool,
+    f10: bool,
+    f11: bool,
+    f12: bool,
+    f13: bool,
+}
+
+/// a percentage, where values above 100 are invalid.
+mod percent {
+    pub const fn encode(value: u8) -> u8 {
+        value
+    }
+    pub const fn decode(bits: u8) -> u8 {
+        bits
+    }
+    pub const fn is_valid(bits: u8) -> bool {
+        bits <= 100
+    }
+}
+
+#[bitpiece(8, all, proptest)]
+#[derive(Debug, PartialEq, Eq)]
+struct Duty {
+    #[bitpiece(with = percent, bits = 7, validate = percent::is_valid)]
+    percent: u8,
+    enabled: bool,
+}
+
+const fn is_valid_range(range: Range) -> bool {
+    range.start().get() <= range.end().get()
+}
+
+#[bitpiece(8, all, proptest, validate = is_valid_range)]
+#[derive(Debug, PartialEq, Eq)]
+struct Range {
+    start: B4,
+    end: B4,
+}
+
+proptest! {
+    #[test]
+    fn b_and_sb_types_stay_in_range(b in any::<B5>(), sb in any::<SB5>()) {
+        prop_assert!(b <= B5::MAX);
+        prop_assert!(sb >= SB5::MIN && sb <= SB5::MAX);
+    }
+
+    #[test]
+    fn fixed_types_stay_in_range(value in any::<UFixed<3, 2>>()) {
+        prop_assert!(value.raw() <= 31);
+        prop_assert_eq!(UFixed::<3, 2>::from_f32(value.to_f32()), value);
+    }
+
+    #[test]
+    fn ranged_types_stay_in_range(value in any::<Ranged<1, 10>>()) {
+        prop_assert!((1..=10).contains(&value.get()));
+    }
+
+    #[test]
+    fn encoded_int_types_roundtrip_through_bits(bcd in any::<Bcd<3>>(), gray in any::<Gray<5>>()) {
+        prop_assert!(bcd.get() <= 999);
+        prop_assert_eq!(Bcd::<3>::try_from_bits(bcd.to_bits()), Some(bcd));
+        prop_assert_eq!(Gray::<5>::from_bits(gray.to_bits()), gray);
+    }
+
+    #[test]
+    fn bit_wrappers_roundtrip_through_bits(inverted in any::<Inverted<B5>>(), reversed in any::<Reversed<SB5>>()) {
+        prop_assert_eq!(inverted.to_bits(), !inverted.get().to_bits() & 0b11111);
+        prop_assert_eq!(Reversed::<SB5>::from_bits(reversed.to_bits()), reversed);
+    }
+
+    #[test]
+    fn enums_only_generate_valid_variants(kind in any::<Kind>()) {
+        prop_assert!(Kind::ALL_VARIANTS.contains(&kind));
+    }
+
+    #[test]
+    fn structs_roundtrip_through_bits(header in any::<Header>()) {
+        prop_assert_eq!(Header::try_from_bits(header.to_bits()), Some(header));
+    }
+
+    #[test]
+    fn structs_with_many_fields(flags in any::<ManyFlags>()) {
+        prop_assert_eq!(ManyFlags::from_bits(flags.to_bits()), flags);
+    }
+
+    #[test]
+    fn converted_fields_stay_valid(duty in any::<Duty>()) {
+        prop_assert!(duty.percent() <= 100);
+    }
+
+    #[test]
+    fn structs_keep_their_invariants(range in any::<Range>()) {
+        prop_assert!(range.start() <= range.end());
+    }
+
+    #[test]
+    fn raw_bits_fit_in_bit_length(bits in proptest_raw_bits::<Header>()) {
+        prop_assert!(bits < (1 << 20));
+    }
+
+    #[test]
+    fn raw_bits_of_single_bit_signed_type(bits in proptest_raw_bits::<SB1>(), value in any::<SB1>()) {
+        // the bits of the min value of `SB1` are above the bits of its max value
+        prop_assert!(bits <= 1);
+        prop_assert!(value == SB1::MIN || value == SB1::MAX);
+    }
+}
@@ -1,4 +1,5 @@
 //! Tests for signed arbitrary-width types (SB1-SB64).
+#![allow(clippy::clone_on_copy)]
 
 mod common;
 
@@ -517,3 +518,166 @@ fn sb_type_ranges() {
     assert_eq!(SB10::MIN.get(), -512);
     assert_eq!(SB10::MAX.get(), 511);
 }
+
+// =============================================================================
+// Arithmetic tests
+// =============================================================================
+
+#[test]
+fn sb_type_wrapping_arithmetic() {
+    assert_eq!(SB5::new(14).wrapping_add(SB5::new(5)), SB5::new(-13));
+    assert_eq!(SB5::new(-14).wrapping_sub(SB5::new(5)), SB5::new(13));
+    assert_eq!(SB5::new(6).wrapping_mul(SB5::new(3)), SB5::new(-14));
+    assert_eq!(SB5::MIN.wrapping_neg(), SB5::MIN);
+    assert_eq!(SB5::new(3).wrapping_neg(), SB5::new(-3));
+    assert_eq!(SB64::MAX.wrapping_add(SB64::new(1)), SB64::MIN);
+}
+
+#[test]
+fn sb_type_checked_arithmetic() {
+    assert_eq!(SB5::new(14).checked_add(SB5::new(1)), Some(SB5::new(15)));
+    assert_eq!(SB5::new(14).checked_add(SB5::new(2)), None);
+    assert_eq!(SB5::new(-14).checked_sub(SB5::new(3)), None);
+    assert_eq!(SB5::new(-4).checked_mul(SB5::new(4)), Some(SB5::new(-16)));
+    assert_eq!(SB5::new(4).checked_mul(SB5::new(4)), None);
+    assert_eq!(SB5::MIN.checked_neg(), None);
+    assert_eq!(SB5::MAX.checked_neg(), Some(SB5::new(-15)));
+    assert_eq!(SB64::MIN.checked_mul(SB64::new(-1)), None);
+}
+
+#[test]
+fn sb_type_saturating_and_overflowing_arithmetic() {
+    assert_eq!(SB5::new(14).saturating_add(SB5::new(5)), SB5::MAX);
+    assert_eq!(SB5::new(-14).saturating_add(SB5::new(-5)), SB5::MIN);
+    assert_eq!(SB5::new(-14).saturating_sub(SB5::new(5)), SB5::MIN);
+    assert_eq!(SB5::new(14).saturating_sub(SB5::new(-5)), SB5::MAX);
+    assert_eq!(SB5::new(-8).saturating_mul(SB5::new(8)), SB5::MIN);
+    assert_eq!(SB5::new(-8).saturating_mul(SB5::new(-8)), SB5::MAX);
+    assert_eq!(SB5::MIN.saturating_neg(), SB5::MAX);
+    assert_eq!(
+        SB5::new(14).overflowing_add(SB5::new(5)),
+        (SB5::new(-13), true)
+    );
+    assert_eq!(
+        SB5::new(-1).overflowing_add(SB5::new(1)),
+        (SB5::new(0), false)
+    );
+    assert_eq!(SB5::MIN.overflowing_neg(), (SB5::MIN, true));
+}
+
+#[test]
+fn sb_type_wrapping_and_saturating_new() {
+    assert_eq!(SB5::wrapping_new(17), SB5::new(-15));
+    assert_eq!(SB5::wrapping_new(-17), SB5::new(15));
+    assert_eq!(SB5::wrapping_new(-16), SB5::MIN);
+    assert_eq!(SB5::saturating_new(100), SB5::MAX);
+    assert_eq!(SB5::saturating_new(-100), SB5::MIN);
+    assert_eq!(SB5::saturating_new(-3), SB5::new(-3));
+}
+
+#[test]
+fn sb_type_shifts() {
+    assert_eq!(SB5::new(5).wrapping_shl(2), SB5::new(-12));
+    assert_eq!(SB5::new(-12).wrapping_shr(2), SB5::new(-3));
+    assert_eq!(SB5::new(1).checked_shl(4), Some(SB5::MIN));
+    assert_eq!(SB5::new(1).checked_shl(5), None);
+    assert_eq!(SB5::new(-1).checked_shr(5), None);
+}
+
+#[test]
+fn sb_type_operators() {
+    assert_eq!(SB5::new(3) + SB5::new(-4), SB5::new(-1));
+    assert_eq!(SB5::new(-7) - SB5::new(4), SB5::new(-11));
+    assert_eq!(SB5::new(3) * SB5::new(-4), SB5::new(-12));
+    assert_eq!(-SB5::new(3), SB5::new(-3));
+    assert_eq!(SB5::new(-8) >> 2, SB5::new(-2));
+    assert_eq!(SB5::new(3) << 2, SB5::new(12));
+
+    let mut value = SB5::new(-3);
+    value += SB5::new(5);
+    value -= SB5::new(10);
+    value *= SB5::new(-1);
+    assert_eq!(value, SB5::new(8));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn sb_type_operators_panic_on_overflow() {
+    expect_panic(|| {
+        let _ = SB5::MAX + SB5::new(1);
+    });
+    expect_panic(|| {
+        let _ = SB5::MIN - SB5::new(1);
+    });
+    expect_panic(|| {
+        let _ = SB5::new(-4) * SB5::new(5);
+    });
+    expect_panic(|| {
+        let _ = -SB5::MIN;
+    });
+    expect_panic(|| {
+        let _ = SB5::new(1) << 5;
+    });
+}
+
+// =============================================================================
+// Generic width tests
+// =============================================================================
+
+/// returns the min and max of any signed bit length, using only the `BitPiece` trait.
+fn range_of<const N: usize>() -> (SBits<N>, SBits<N>)
+where
+    BitLength<N>: AssociatedStorage,
+{
+    (<SBits<N> as BitPiece>::MIN, <SBits<N> as BitPiece>::MAX)
+}
+
+/// negates a value of any signed bit length, saturating at its max value, using only the `BitInt` trait.
+fn saturating_negate<const N: usize>(value: SBits<N>) -> SBits<N>
+where
+    BitLength<N>: AssociatedStorage,
+{
+    SBits::<N>::saturating_from_i128(-value.to_i128())
+}
+
+#[test]
+fn sb_types_are_aliases_of_sbits() {
+    let value: SBits<5> = SB5::new(-7);
+    assert_eq!(value, SBits::<5>::new(-7));
+    assert_eq!(<SBits<12> as BitPiece>::BITS, 12);
+}
+
+#[test]
+fn sb_generic_over_width() {
+    assert_eq!(range_of::<5>(), (SB5::MIN, SB5::MAX));
+    assert_eq!(range_of::<33>(), (SB33::MIN, SB33::MAX));
+}
+
+#[test]
+fn sb_generic_arithmetic_using_bit_int() {
+    assert_eq!(saturating_negate(SB5::new(-7)), SB5::new(7));
+    assert_eq!(saturating_negate(SB5::MIN), SB5::MAX);
+    assert_eq!(saturating_negate(SB64::new(3)), SB64::new(-3));
+    assert_eq!(SBits::<12>::try_from_i128(-2049), None);
+}
+
+#[test]
+fn sb_generic_constructors_and_arithmetic() {
+    fn negate_and_halve<const N: usize>(value: SBits<N>) -> (Option<SBits<N>>, SBits<N>)
+    where
+        BitLength<N>: AssociatedStorage,
+    {
+        (value.checked_neg(), -SBits::<N>::new(value.get()) >> 1)
+    }
+    assert_eq!(
+        negate_and_halve(SB5::new(-7)),
+        (Some(SB5::new(7)), SB5::new(3))
+    );
+    assert_eq!(
+        negate_and_halve(SB5::new(7)),
+        (Some(SB5::new(-7)), SB5::new(-4))
+    );
+    assert_eq!(SBits::<5>::try_new(16), None);
+    assert_eq!(SBits::<5>::wrapping_new(17), SB5::new(-15));
+    assert_eq!(SBits::<5>::saturating_new(-17), SB5::MIN);
+}
@@ -0,0 +1,250 @@
+//! Tests for the serde integration.
+#![cfg(feature = "serde")]
+
+use bitpiece::*;
+
+#[bitpiece(2, all, serde)]
+#[derive(Debug, PartialEq, Eq)]
+enum Mode {
+    Off = 0,
+    On = 1,
+    Auto = 3,
+}
+
+#[bitpiece(2, all, serde_storage)]
+#[derive(Debug, PartialEq, Eq)]
+enum RawMode {
+    Off = 0,
+    On = 1,
+    Auto = 3,
+}
+
+#[bitpiece(16, all, serde)]
+#[derive(Debug, PartialEq, Eq)]
+struct Control {
+    enabled: bool,
+    mode: Mode,
+    level: B5,
+    offset: SB8,
+}
+
+#[bitpiece(16, all, serde_storage)]
+#[derive(Debug, PartialEq, Eq)]
+struct RawControl {
+    enabled: bool,
+    mode: Mode,
+    level: B5,
+    offset: SB8,
+}
+
+#[bitpiece(20, basic, serde)]
+#[derive(Debug, PartialEq, Eq)]
+struct Nested {
+    control: Control,
+    id: B4,
+}
+
+fn control() -> Control {
+    Control::from_fields(ControlFields {
+        enabled: true,
+        mode: Mode::Auto,
+        level: B5::new(17),
+        offset: SB8::new(-3),
+    })
+}
+
+// =============================================================================
+// B / SB types
+// =============================================================================
+
+#[test]
+fn b_types_as_integers() {
+    assert_eq!(serde_json::to_string(&B5::new(17)).unwrap(), "17");
+    assert_eq!(
+        serde_json::to_string(&B64::MAX).unwrap(),
+        u64::MAX.to_string()
+    );
+    assert_eq!(serde_json::from_str::<B5>("31").unwrap(), B5::new(31));
+    assert!(serde_json::from_str::<B5>("32").is_err());
+    assert!(serde_json::from_str::<B5>("300").is_err());
+    assert!(serde_json::from_str::<B5>("-1").is_err());
+}
+
+#[test]
+fn sb_types_as_integers() {
+    assert_eq!(serde_json::to_string(&SB5::new(-16)).unwrap(), "-16");
+    assert_eq!(serde_json::from_str::<SB5>("15").unwrap(), SB5::new(15));
+    assert_eq!(
+        serde_json::from_str::<SB64>("-9223372036854775808").unwrap(),
+        SB64::MIN
+    );
+    assert!(serde_json::from_str::<SB5>("16").is_err());
+    assert!(serde_json::from_str::<SB5>("-17").is_err());
+}
+
+#[test]
+fn fixed_types_as_raw_integers() {
+    assert_eq!(
+        serde_json::to_string(&UFixed::<4, 4>::from_f32(1.5)).unwrap(),
+        "24"
+    );
+    assert_eq!(
+        serde_json::to_string(&SFixed::<1, 15>::MIN).unwrap(),
+        "-32768"
+    );
+    assert_eq!(
+        serde_json::from_str::<SFixed<1, 15>>("-16384")
+            .unwrap()
+            .to_f32(),
+        -0.5
+    );
+    assert!(serde_json::from_str::<SFixed<1, 15>>("32768").is_err());
+    assert!(serde_json::from_str::<UFixed<4, 4>>("256").is_err());
+}
+
+#[test]
+fn biased_types_as_values() {
+    assert_eq!(
+        serde_json::to_string(&Biased::<B4, 1>::new(16)).unwrap(),
+        "16"
+    );
+    assert_eq!(
+        serde_json::from_str::<Ranged<1980, 2107>>("2024")
+            .unwrap()
+            .to_bits(),
+        44
+    );
+    assert!(serde_json::from_str::<Biased<B4, 1>>("0").is_err());
+    assert!(serde_json::from_str::<Ranged<1, 10>>("11").is_err());
+}
+
+#[test]
+fn encoded_int_types_as_values() {
+    assert_eq!(
+        serde_json::to_string(&Bcd::<2>::from_bits(0x59)).unwrap(),
+        "59"
+    );
+    assert_eq!(
+        serde_json::from_str::<Gray<3>>("2").unwrap().to_bits(),
+        0b011
+    );
+    assert!(serde_json::from_str::<Bcd<2>>("100").is_err());
+    assert!(serde_json::from_str::<Gray<3>>("8").is_err());
+}
+
+#[test]
+fn bit_wrappers_as_logical_values() {
+    assert_eq!(serde_json::to_string(&Inverted(true)).unwrap(), "true");
+    assert_eq!(
+        serde_json::from_str::<Reversed<B4>>("1").unwrap().to_bits(),
+        0b1000
+    );
+    assert!(serde_json::from_str::<Inverted<B3>>("8").is_err());
+}
+
+#[test]
+fn b_type_error_message() {
+    let err = serde_json::from_str::<B3>("9").unwrap_err().to_string();
+    assert!(err.contains("fits in 3 bits"), "{err}");
+}
+
+// =============================================================================
+// enums
+// =============================================================================
+
+#[test]
+fn enum_as_variant_name() {
+    assert_eq!(serde_json::to_string(&Mode::Auto).unwrap(), "\"Auto\"");
+    assert_eq!(serde_json::from_str::<Mode>("\"On\"").unwrap(), Mode::On);
+    assert!(serde_json::from_str::<Mode>("\"Manual\"").is_err());
+}
+
+#[test]
+fn enum_as_storage() {
+    assert_eq!(serde_json::to_string(&RawMode::Auto).unwrap(), "3");
+    assert_eq!(serde_json::from_str::<RawMode>("1").unwrap(), RawMode::On);
+    // 2 is not a valid variant
+    let err = serde_json::from_str::<RawMode>("2")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("valid bit pattern of `RawMode`"), "{err}");
+}
+
+// =============================================================================
+// structs
+// =============================================================================
+
+#[test]
+fn struct_as_map_of_fields() {
+    let json = serde_json::to_string(&control()).unwrap();
+    assert_eq!(
+        json,
+        r#"{"enabled":true,"mode":"Auto","level":17,"offset":-3}"#
+    );
+    assert_eq!(serde_json::from_str::<Control>(&json).unwrap(), control());
+}
+
+#[test]
+fn struct_as_map_validates_fields() {
+    assert!(serde_json::from_str::<Control>(
+        r#"{"enabled":true,"mode":"Auto","level":32,"offset":-3}"#
+    )
+    .is_err());
+    assert!(serde_json::from_str::<Control>(
+        r#"{"enabled":true,"mode":"Manual","level":1,"offset":-3}"#
+    )
+    .is_err());
+    // missing field
+    assert!(
+        serde_json::from_str::<Control>(r#"{"enabled":true,"mode":"Auto","level":1}"#).is_err()
+    );
+}
+
+#[test]
+fn struct_as_storage() {
+    let raw = RawControl::from_bits(control().to_bits());
+    let json = serde_json::to_string(&raw).unwrap();
+    assert_eq!(json, control().to_bits().to_string());
+    assert_eq!(serde_json::from_str::<RawControl>(&json).unwrap(), raw);
+}
+
+#[test]
+fn struct_as_storage_validates_bits() {
+    // mode = 2 is not a valid variant
+    assert!(serde_json::from_str::<RawControl>("4").is_err());
+    assert!(serde_json::from_str::<RawControl>("65536").is_err());
+}
+
+#[test]
+fn nested_struct_as_map() {
+    let nested = Nested::from_bits(0)
+        .with_control(control())
+        .with_id(B4::new(9));
+    let json = serde_json::to_string(&nested).unwrap();
+    assert_eq!(
+        json,
+        r#"{"control":{"enabled":true,"mode":"Auto","level":17,"offset":-3},"id":9}"#
+    );
+    assert_eq!(serde_json::from_str::<Nested>(&json).unwrap(), nested);
+}
+
+const fn is_valid_range(range: Range) -> bool {
+    range.start().get() <= range.end().get()
+}
+
+#[bitpiece(8, all, serde, validate = is_valid_range)]
+#[derive(Debug, PartialEq, Eq)]
+struct Range {
+    start: B4,
+    end: B4,
+}
+
+#[test]
+fn struct_as_map_validates_invariants() {
+    assert_eq!(
+        serde_json::from_str::<Range>(r#"{"start":2,"end":5}"#).unwrap(),
+        Range::from_bits(0x52)
+    );
+    let err = serde_json::from_str::<Range>(r#"{"start":5,"end":2}"#).unwrap_err();
+    assert!(err.to_string().contains("invalid value of `Range`"));
+}
@@ -0,0 +1,156 @@
+//! Tests for the standard library types implementing BitPiece: `NonZero*`, `char`, `f32`/`f64` and `Wrapping`.
+#![allow(clippy::assertions_on_constants)]
+
+use core::num::{NonZeroI8, NonZeroU16, NonZeroU8, Wrapping};
+
+use bitpiece::*;
+
+#[bitpiece(64, all, debug, layout, visit)]
+#[derive(PartialEq)]
+struct Record {
+    id: NonZeroU8,
+    letter: char,
+    seq: Wrapping<B3>,
+    scale: f32,
+}
+
+// =============================================================================
+// NonZero* tests
+// =============================================================================
+
+#[test]
+fn non_zero_rejects_zero() {
+    assert_eq!(NonZeroU8::try_from_bits(0), None);
+    assert_eq!(NonZeroU8::try_from_bits(5), NonZeroU8::new(5));
+    assert_eq!(NonZeroI8::try_from_bits(0xff), NonZeroI8::new(-1));
+    assert_eq!(NonZeroI8::new(-128).unwrap().to_bits(), 0x80);
+    assert_eq!(<NonZeroU16 as BitPiece>::BITS, 16);
+}
+
+#[test]
+fn non_zero_constants() {
+    assert_eq!(<NonZeroU8 as BitPiece>::ZEROES.get(), 1);
+    assert_eq!(<NonZeroU8 as BitPiece>::ONES.get(), 255);
+    assert_eq!(<NonZeroI8 as BitPiece>::MIN.get(), -128);
+    assert_eq!(<NonZeroI8 as BitPiece>::ONES.get(), -1);
+}
+
+// =============================================================================
+// char tests
+// =============================================================================
+
+#[test]
+fn char_is_21_bits_with_scalar_validation() {
+    assert_eq!(<char as BitPiece>::BITS, 21);
+    assert_eq!(char::from_bits(0x41), 'A');
+    assert_eq!('\u{10ffff}'.to_bits(), 0x10ffff);
+    assert_eq!(char::try_from_bits(0xd800), None);
+    assert_eq!(char::try_from_bits(0x110000), None);
+    assert_eq!(<char as BitPiece>::ONES, char::MAX);
+}
+
+// =============================================================================
+// float tests
+// =============================================================================
+
+#[test]
+fn floats_are_stored_by_bit_pattern() {
+    assert_eq!(1.5f32.to_bits(), 0x3fc0_0000);
+    assert_eq!(<f32 as BitPiece>::from_bits(0xbf80_0000), -1.0);
+    assert_eq!(<f64 as BitPiece>::from_bits(0x4000_0000_0000_0000), 2.0);
+    assert!(<f32 as BitPiece>::ONES.is_nan());
+    assert!(BitPieceF32Converter::const_eq(f32::NAN, f32::NAN));
+    assert!(!BitPieceF32Converter::const_eq(0.0, -0.0));
+}
+
+// =============================================================================
+// Wrapping tests
+// =============================================================================
+
+#[test]
+fn wrapping_forwards_to_inner_type() {
+    assert_eq!(<Wrapping<B3> as BitPiece>::BITS, 3);
+    assert_eq!(Wrapping::<B3>::from_bits(5), Wrapping(B3::new(5)));
+    assert_eq!(Wrapping::<B3>::try_from_bits(8), None);
+    assert_eq!(Wrapping(-1i8).to_bits(), 0xff);
+    assert_eq!(<Wrapping<SB4> as BitPiece>::MIN, Wrapping(SB4::MIN));
+    assert_eq!(
+        <Wrapping<u16> as BitPieceHasLayout>::TYPE_NAME,
+        "Wrapping<u16>"
+    );
+    assert!(<Wrapping<i8> as BitPieceHasLayout>::IS_SIGNED);
+}
+
+// =============================================================================
+// struct fields
+// =============================================================================
+
+#[test]
+fn struct_fields() {
+    let mut record = Record::from_bits(0x0001);
+    assert_eq!(Record::try_from_bits(0), None);
+    record.set_letter('z');
+    record.set_seq(Wrapping(B3::new(7)));
+    record.set_scale(0.5);
+    assert_eq!(record.id().get(), 1);
+    assert_eq!(record.letter(), 'z');
+    assert_eq!(record.seq(), Wrapping(B3::new(7)));
+    assert_eq!(record.scale(), 0.5);
+    assert_eq!(
+        format!("{record:?}"),
+        "Record { id: 1, letter: 'z', seq: 7, scale: 0.5 }"
+    );
+    assert_eq!(Record::LETTER_LEN, 21);
+    assert_eq!(Record::FIELDS[1].type_name, "char");
+    assert_eq!(Record::FIELDS[2].type_name, "Wrapping<B3>");
+}
+
+#[test]
+fn struct_with_invalid_char_bits_is_rejected() {
+    let record = Record::from_bits(0x0001).with_letter('a');
+    let surrogate = (record.storage & !(0x1f_ffff << 8)) | (0xd800 << 8);
+    assert_eq!(Record::try_from_bits(surrogate), None);
+}
+
+#[test]
+fn mut_struct() {
+    let mut record = Record::from_bits(0x0001);
+    record.scale_mut().set(-3.0);
+    record.seq_mut().set(Wrapping(B3::new(2)));
+    record.id_mut().set(NonZeroU8::new(9).unwrap());
+    assert_eq!(record.scale(), -3.0);
+    assert_eq!(record.seq().0.get(), 2);
+    assert_eq!(record.id().get(), 9);
+}
+
+#[test]
+fn visit_floats() {
+    struct Collect(Vec<String>);
+    impl BitPieceVisitor for Collect {
+        fn visit_float(&mut self, name: &str, value: f64, bits: usize) {
+            self.0.push(format!("{name}: f{bits} = {value}"));
+        }
+    }
+    let mut collect = Collect(Vec::new());
+    Record::from_bits(0x0001)
+        .with_scale(0.25)
+        .visit(&mut collect);
+    assert_eq!(collect.0, ["scale: f32 = 0.25"]);
+}
+
+#[test]
+fn const_context() {
+    const RECORD: Record = Record::from_bits(0x0001).with_letter('q').with_scale(2.0);
+    const LETTER: char = RECORD.letter();
+    assert_eq!(LETTER, 'q');
+    assert_eq!(RECORD.scale(), 2.0);
+}
+
+#[test]
+fn passes_testing_harness() {
+    testing::check::<NonZeroU8>();
+    testing::check::<char>();
+    testing::check::<f64>();
+    testing::check::<Wrapping<SB5>>();
+    testing::check::<Record>();
+}
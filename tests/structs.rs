@@ -1,4 +1,9 @@
 //! Tests for struct bitfields.
+#![allow(
+    clippy::bool_assert_comparison,
+    clippy::clone_on_copy,
+    clippy::unusual_byte_groupings
+)]
 
 mod common;
 
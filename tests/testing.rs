@@ -0,0 +1,133 @@
+//! Tests for the `bitpiece::testing` verification harness.
+
+#[allow(dead_code)]
+mod common;
+
+use bitpiece::*;
+use common::expect_panic_with_message;
+
+#[bitpiece(2, all, layout)]
+#[derive(Debug, PartialEq, Eq)]
+enum Mode {
+    Off = 0,
+    On = 1,
+    Auto = 3,
+}
+
+#[bitpiece(16, all, layout)]
+#[derive(Debug, PartialEq, Eq)]
+struct Control {
+    enabled: bool,
+    mode: Mode,
+    level: B5,
+    offset: SB8,
+}
+
+#[bitpiece(56, all, layout)]
+#[derive(Debug, PartialEq, Eq)]
+struct Register {
+    control: Control,
+    id: u8,
+    parity: i8,
+    mode: Mode,
+    address: B22,
+}
+
+#[bitpiece(8, get, set, fields_struct, mut_struct)]
+#[derive(Debug, PartialEq, Eq)]
+struct Overlapping {
+    low: B4,
+    high: B4,
+}
+impl BitPieceHasLayout for Overlapping {
+    const TYPE_NAME: &'static str = "Overlapping";
+    const FIELDS: &'static [FieldInfo] = &[
+        FieldInfo {
+            name: "low",
+            offset: 0,
+            len: 4,
+            type_name: "B4",
+            is_signed: false,
+            variants: &[],
+            fields: &[],
+            is_valid_bits: is_valid_bits::<B4>,
+        },
+        FieldInfo {
+            name: "high",
+            offset: 3,
+            len: 4,
+            type_name: "B4",
+            is_signed: false,
+            variants: &[],
+            fields: &[],
+            is_valid_bits: is_valid_bits::<B4>,
+        },
+    ];
+}
+
+#[test]
+fn built_in_types() {
+    testing::check::<bool>();
+    testing::check::<u8>();
+    testing::check::<i16>();
+    testing::check::<u64>();
+    testing::check::<B13>();
+    testing::check::<B20>();
+    testing::check::<SB7>();
+    testing::check::<SB40>();
+}
+
+#[test]
+fn exhaustive() {
+    testing::check::<Mode>();
+    testing::check::<Control>();
+}
+
+#[test]
+fn sampled() {
+    testing::check::<Register>();
+}
+
+#[test]
+fn layout() {
+    testing::check_layout::<Mode>();
+    testing::check_layout::<Control>();
+    testing::check_layout::<Register>();
+}
+
+#[test]
+fn setters_are_called() {
+    let mut setters = Vec::new();
+    Control::call_setters(Control::ZEROES, Control::ZEROES, &mut |call| {
+        setters.push((call.field, call.setter))
+    });
+    for setter in [
+        ("level", "set_level"),
+        ("level", "with_level"),
+        ("level", "level_mut().set"),
+        ("offset", "set_offset"),
+        ("mode", "with_mode"),
+    ] {
+        assert!(setters.contains(&setter), "{:?} was not called", setter);
+    }
+}
+
+#[test]
+fn types_without_layout() {
+    #[bitpiece(12, all)]
+    #[derive(Debug, PartialEq, Eq)]
+    struct Plain {
+        low: B4,
+        high: SB8,
+    }
+
+    testing::check::<Plain>();
+}
+
+#[test]
+fn overlapping_fields_are_reported() {
+    expect_panic_with_message(
+        testing::check_layout::<Overlapping>,
+        "field `high` of `Overlapping` overlaps another field",
+    );
+}
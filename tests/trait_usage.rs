@@ -1,4 +1,5 @@
 //! Tests for BitPiece trait generic usage.
+#![allow(clippy::bool_assert_comparison, clippy::extra_unused_type_parameters)]
 
 use bitpiece::*;
 
@@ -0,0 +1,184 @@
+//! Tests for struct-level validation functions.
+#![allow(clippy::unusual_byte_groupings)]
+
+mod common;
+
+use bitpiece::*;
+use common::expect_panic;
+
+#[bitpiece(1, all, layout)]
+#[derive(Debug, PartialEq, Eq)]
+enum Mode {
+    Pio = 0,
+    Dma = 1,
+}
+
+/// a dma transfer must use a non-zero channel.
+const fn is_valid_transfer(transfer: Transfer) -> bool {
+    !matches!(transfer.mode(), Mode::Dma) || transfer.channel().get() != 0
+}
+
+#[bitpiece(8, all, layout, validate = is_valid_transfer)]
+#[derive(Debug, PartialEq, Eq)]
+struct Transfer {
+    mode: Mode,
+    channel: B3,
+    priority: B4,
+}
+
+mod packet {
+    use bitpiece::*;
+
+    pub const fn is_valid(header: Header) -> bool {
+        header.len().get() >= header.header_len().get() as u16
+    }
+
+    #[bitpiece(16, all, layout, validate = self::is_valid)]
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct Header {
+        pub header_len: B4,
+        pub len: B12,
+    }
+}
+use packet::{Header, HeaderFields};
+
+/// a clock divider must not be zero.
+const fn is_nonzero(divider: Divider) -> bool {
+    divider.value().get() != 0
+}
+
+#[bitpiece(4, all, layout, validate = is_nonzero)]
+#[derive(Debug, PartialEq, Eq)]
+struct Divider {
+    value: B4,
+}
+
+#[bitpiece(12, all, layout)]
+#[derive(Debug, PartialEq, Eq)]
+struct Clock {
+    divider: Divider,
+    source: B4,
+    prescaler: Divider,
+}
+
+#[test]
+fn try_from_bits() {
+    assert!(Transfer::try_from_bits(0b0000_000_0).is_some());
+    assert!(Transfer::try_from_bits(0b0000_010_1).is_some());
+    assert_eq!(Transfer::try_from_bits(0b0000_000_1), None);
+    assert_eq!(Transfer::try_from_bits(0b1111_000_1), None);
+    expect_panic(|| {
+        let _ = Transfer::from_bits(0b0000_000_1);
+    });
+}
+
+#[test]
+fn from_fields() {
+    let valid = HeaderFields {
+        header_len: B4::new(4),
+        len: B12::new(20),
+    };
+    let invalid = HeaderFields {
+        header_len: B4::new(4),
+        len: B12::new(3),
+    };
+    assert_eq!(Header::from_fields(valid).len(), B12::new(20));
+    assert!(Header::try_from_fields(valid).is_some());
+    assert_eq!(Header::try_from_fields(invalid), None);
+    expect_panic(|| {
+        let _ = Header::from_fields(invalid);
+    });
+}
+
+#[test]
+fn try_from_fields_struct() {
+    let valid = HeaderFields {
+        header_len: B4::new(4),
+        len: B12::new(20),
+    };
+    let invalid = HeaderFields {
+        header_len: B4::new(4),
+        len: B12::new(3),
+    };
+    assert_eq!(Header::try_from(valid), Ok(Header::from_fields(valid)));
+    assert_eq!(Header::try_from(invalid), Err(InvalidFieldsError));
+    let header: Result<Header, _> = invalid.try_into();
+    assert!(header.is_err());
+}
+
+#[test]
+fn checked_with() {
+    let transfer = Transfer::from_bits(0b0000_011_0);
+    assert_eq!(
+        transfer
+            .checked_with_mode(Mode::Dma)
+            .map(|transfer| transfer.mode()),
+        Some(Mode::Dma)
+    );
+    assert_eq!(
+        transfer.checked_with_channel(B3::new(0)),
+        Some(transfer.with_channel(B3::new(0)))
+    );
+
+    let dma = transfer.with_mode(Mode::Dma);
+    assert_eq!(dma.checked_with_channel(B3::new(0)), None);
+    assert_eq!(
+        dma.checked_with_priority(B4::new(9))
+            .map(|transfer| transfer.priority()),
+        Some(B4::new(9))
+    );
+}
+
+#[test]
+fn const_context() {
+    const HEADER: Option<Header> = Header::try_from_bits(0x0142);
+    const INVALID: Option<Header> = Header::try_from_bits(0x0038);
+    assert_eq!(HEADER.map(|header| header.len()), Some(B12::new(0x14)));
+    assert_eq!(INVALID, None);
+}
+
+#[test]
+fn field_access_respects_validation() {
+    let mut header = Header::from_bits(0x0142);
+    assert_eq!(
+        header.set_field("len", 1),
+        Err(FieldAccessError::InvalidValue)
+    );
+    assert_eq!(header.storage, 0x0142);
+    assert_eq!(header.set_field("len", 8), Ok(()));
+    assert_eq!(header.len(), B12::new(8));
+}
+
+#[test]
+fn constants_skip_validation() {
+    // the constants are built from the constants of the fields, even if they break the invariants
+    assert_eq!(Divider::ZEROES.value(), B4::new(0));
+    assert_eq!(Divider::ONES.value(), B4::MAX);
+    assert_eq!(Divider::try_from_bits(Divider::ZEROES.storage), None);
+    assert_eq!(Transfer::MAX.storage, 0b1111_111_1);
+}
+
+#[test]
+fn nested_validated_struct() {
+    const CLOCK: Clock = Clock::ZEROES.with_divider(Divider::from_bits(3));
+    assert_eq!(CLOCK.divider().value(), B4::new(3));
+    assert_eq!(Clock::MAX.storage, 0xfff);
+    assert_eq!(
+        Clock::try_from_bits(0x101).map(|clock| clock.source()),
+        Some(B4::new(0))
+    );
+    // the validation function of the nested struct is still called
+    assert_eq!(Clock::try_from_bits(0x100), None);
+    assert_eq!(Clock::try_from_bits(0x001), None);
+    expect_panic(|| {
+        let _ = Clock::from_bits(0x010);
+    });
+}
+
+#[test]
+fn passes_testing_harness() {
+    testing::check::<Transfer>();
+    testing::check::<Header>();
+    testing::check::<Divider>();
+    testing::check::<Clock>();
+}
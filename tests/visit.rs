@@ -0,0 +1,134 @@
+//! Tests for walking bitpiece values using a visitor.
+
+use bitpiece::*;
+
+#[bitpiece(2, all, visit)]
+#[derive(Debug, PartialEq, Eq)]
+enum Mode {
+    Off = 0,
+    On = 1,
+    Auto = 3,
+}
+
+#[bitpiece(16, all, visit)]
+#[derive(Debug, PartialEq, Eq)]
+struct Control {
+    enabled: bool,
+    mode: Mode,
+    level: B5,
+    offset: SB8,
+}
+
+#[bitpiece(32, all, visit)]
+#[derive(Debug, PartialEq, Eq)]
+struct Register {
+    control: Control,
+    id: u8,
+    parity: i8,
+}
+
+/// a visitor which records each call as a line of text.
+#[derive(Default)]
+struct Recorder(Vec<String>);
+impl BitPieceVisitor for Recorder {
+    fn visit_bool(&mut self, name: &str, value: bool) {
+        self.0.push(format!("bool {name}={value}"));
+    }
+    fn visit_unsigned(&mut self, name: &str, value: u64, bits: usize) {
+        self.0.push(format!("unsigned {name}={value}:{bits}"));
+    }
+    fn visit_signed(&mut self, name: &str, value: i64, bits: usize) {
+        self.0.push(format!("signed {name}={value}:{bits}"));
+    }
+    fn visit_enum(&mut self, name: &str, variant_name: &str, raw: u64) {
+        self.0.push(format!("enum {name}={variant_name}({raw})"));
+    }
+    fn enter_struct(&mut self, name: &str, type_name: &str) {
+        self.0.push(format!("enter {name}:{type_name}"));
+    }
+    fn exit_struct(&mut self, name: &str, type_name: &str) {
+        self.0.push(format!("exit {name}:{type_name}"));
+    }
+}
+
+fn record<T: BitPieceVisit>(value: T) -> Vec<String> {
+    let mut recorder = Recorder::default();
+    value.visit(&mut recorder);
+    recorder.0
+}
+
+fn control() -> Control {
+    Control::from_fields(ControlFields {
+        enabled: true,
+        mode: Mode::Auto,
+        level: B5::new(17),
+        offset: SB8::new(-3),
+    })
+}
+
+#[test]
+fn visit_built_in_types() {
+    assert_eq!(record(true), ["bool =true"]);
+    assert_eq!(record(B5::new(17)), ["unsigned =17:5"]);
+    assert_eq!(record(SB5::new(-3)), ["signed =-3:5"]);
+    assert_eq!(record(200u8), ["unsigned =200:8"]);
+    assert_eq!(record(-5i16), ["signed =-5:16"]);
+}
+
+#[test]
+fn visit_enum() {
+    assert_eq!(record(Mode::Auto), ["enum =Auto(3)"]);
+}
+
+#[test]
+fn visit_struct() {
+    assert_eq!(
+        record(control()),
+        [
+            "enter :Control",
+            "bool enabled=true",
+            "enum mode=Auto(3)",
+            "unsigned level=17:5",
+            "signed offset=-3:8",
+            "exit :Control",
+        ]
+    );
+}
+
+#[test]
+fn visit_nested_struct() {
+    let register = Register::from_fields(RegisterFields {
+        control: control(),
+        id: 7,
+        parity: -1,
+    });
+    assert_eq!(
+        record(register),
+        [
+            "enter :Register",
+            "enter control:Control",
+            "bool enabled=true",
+            "enum mode=Auto(3)",
+            "unsigned level=17:5",
+            "signed offset=-3:8",
+            "exit control:Control",
+            "unsigned id=7:8",
+            "signed parity=-1:8",
+            "exit :Register",
+        ]
+    );
+}
+
+#[test]
+fn default_methods_ignore_values() {
+    /// a visitor which only sums the unsigned values.
+    struct Sum(u64);
+    impl BitPieceVisitor for Sum {
+        fn visit_unsigned(&mut self, _name: &str, value: u64, _bits: usize) {
+            self.0 += value;
+        }
+    }
+    let mut sum = Sum(0);
+    control().visit(&mut sum);
+    assert_eq!(sum.0, 17);
+}